@@ -0,0 +1,242 @@
+use crate::telegram::types::Message;
+use std::collections::{HashMap, HashSet};
+
+/// Shortest token counted as a term, in characters
+const MIN_TERM_CHARS: usize = 2;
+
+/// Common English words that carry no topic on their own
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been",
+    "but", "by", "can", "do", "for", "from", "has", "have", "he", "her", "his", "how", "if", "in",
+    "into", "is", "it", "its", "just", "more", "new", "no", "not", "now", "of", "on", "or", "our",
+    "out", "she", "so", "than", "that", "the", "their", "them", "there", "they", "this", "to",
+    "up", "was", "we", "were", "what", "when", "which", "who", "will", "with", "you", "your",
+];
+
+/// Common Russian words that carry no topic on their own
+const RUSSIAN_STOPWORDS: &[&str] = &[
+    "а",
+    "без",
+    "будет",
+    "бы",
+    "был",
+    "была",
+    "были",
+    "было",
+    "в",
+    "вот",
+    "все",
+    "всё",
+    "вы",
+    "где",
+    "да",
+    "для",
+    "до",
+    "его",
+    "её",
+    "если",
+    "есть",
+    "ещё",
+    "же",
+    "за",
+    "и",
+    "из",
+    "или",
+    "им",
+    "их",
+    "к",
+    "как",
+    "когда",
+    "кто",
+    "ли",
+    "мы",
+    "на",
+    "над",
+    "не",
+    "него",
+    "нет",
+    "ни",
+    "но",
+    "о",
+    "об",
+    "он",
+    "она",
+    "они",
+    "оно",
+    "от",
+    "по",
+    "под",
+    "при",
+    "с",
+    "со",
+    "так",
+    "также",
+    "то",
+    "только",
+    "у",
+    "уже",
+    "чем",
+    "что",
+    "это",
+    "этот",
+    "я",
+];
+
+/// Built-in Russian and English stopwords, lowercase
+pub fn default_stopwords() -> HashSet<String> {
+    ENGLISH_STOPWORDS
+        .iter()
+        .chain(RUSSIAN_STOPWORDS)
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// Most frequent terms across messages, most frequent first
+///
+/// Text is split on anything that is not a Unicode letter or digit and lowercased.
+/// Stopwords, numbers and single characters are skipped. A term counts once per
+/// message, and messages with identical text (e.g. cross-posts) count once.
+/// Ties are broken alphabetically so the result is stable.
+pub fn top_terms(
+    messages: &[Message],
+    top_n: usize,
+    stopwords: &HashSet<String>,
+) -> Vec<(String, u32)> {
+    let mut seen_texts = HashSet::new();
+    let mut counts: HashMap<String, u32> = HashMap::new();
+
+    for message in messages {
+        let text = message.text.trim().to_lowercase();
+        if !seen_texts.insert(text.clone()) {
+            continue;
+        }
+
+        let terms: HashSet<&str> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| term.chars().count() >= MIN_TERM_CHARS)
+            .filter(|term| !term.chars().all(|c| c.is_numeric()))
+            .filter(|term| !stopwords.contains(*term))
+            .collect();
+
+        for term in terms {
+            *counts.entry(term.to_string()).or_default() += 1;
+        }
+    }
+
+    let mut terms: Vec<(String, u32)> = counts.into_iter().collect();
+    terms.sort_by(|(a_term, a_count), (b_term, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_term.cmp(b_term))
+    });
+    terms.truncate(top_n);
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram::types::{ChannelId, ChannelName, MediaType, MessageId, Username};
+    use chrono::Utc;
+
+    fn message(id: i64, text: &str) -> Message {
+        Message {
+            id: MessageId::new(id).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test Channel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: text.to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    #[test]
+    fn top_terms_counts_mixed_languages() {
+        let messages = vec![
+            message(1, "Биткоин растёт, Bitcoin up 5%"),
+            message(2, "Курс биткоин снова вырос"),
+            message(3, "BITCOIN ETF approved; биткоин на максимуме"),
+        ];
+
+        let terms = top_terms(&messages, 2, &default_stopwords());
+
+        assert_eq!(
+            terms,
+            vec![("биткоин".to_string(), 3), ("bitcoin".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn top_terms_removes_stopwords() {
+        let messages = vec![
+            message(1, "The rate and the outlook"),
+            message(2, "Это и есть ставка"),
+        ];
+
+        let terms = top_terms(&messages, 10, &default_stopwords());
+        let words: Vec<&str> = terms.iter().map(|(term, _)| term.as_str()).collect();
+
+        assert_eq!(words, vec!["outlook", "rate", "ставка"]);
+    }
+
+    #[test]
+    fn top_terms_uses_custom_stopwords() {
+        let messages = vec![message(1, "crypto news crypto market")];
+        let stopwords: HashSet<String> = ["crypto".to_string()].into_iter().collect();
+
+        let terms = top_terms(&messages, 10, &stopwords);
+
+        assert_eq!(
+            terms,
+            vec![("market".to_string(), 1), ("news".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn top_terms_counts_duplicate_text_and_repeats_once() {
+        let messages = vec![
+            message(1, "Rust rust RUST release"),
+            message(2, "rust rust rust release"),
+            message(3, "Rust tooling"),
+        ];
+
+        let terms = top_terms(&messages, 10, &HashSet::new());
+
+        assert_eq!(
+            terms,
+            vec![
+                ("rust".to_string(), 2),
+                ("release".to_string(), 1),
+                ("tooling".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn top_terms_skips_numbers_and_single_characters() {
+        let messages = vec![message(1, "2025 x 42 ok")];
+
+        let terms = top_terms(&messages, 10, &HashSet::new());
+
+        assert_eq!(terms, vec![("ok".to_string(), 1)]);
+    }
+
+    #[test]
+    fn top_terms_handles_empty_input() {
+        assert!(top_terms(&[], 5, &default_stopwords()).is_empty());
+        assert!(top_terms(&[message(1, "rust")], 0, &default_stopwords()).is_empty());
+    }
+}
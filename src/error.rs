@@ -1,3 +1,5 @@
+use serde::Serialize;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -22,6 +24,117 @@ pub enum Error {
 
     #[error("invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("not found: {0}")]
+    NotFound(String),
+
+    #[error("local index error: {0}")]
+    Index(String),
+
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+impl Error {
+    /// Whether retrying the same operation later may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::RateLimit { .. } | Error::Network(_))
+    }
+
+    /// Delay to wait before retrying, for rate limit errors
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::RateLimit {
+                retry_after_seconds,
+            } => Some(Duration::from_secs(*retry_after_seconds)),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Stable machine-readable code for this error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Auth(_) => "AUTH",
+            Error::TelegramApi(_) => "TELEGRAM_API",
+            Error::RateLimit { .. } => "RATE_LIMIT",
+            Error::Config(_) => "CONFIG",
+            Error::Network(_) => "NETWORK",
+            Error::Mcp(_) => "MCP",
+            Error::InvalidInput(_) => "INVALID_INPUT",
+            Error::NotFound(_) => "NOT_FOUND",
+            Error::Index(_) => "INDEX",
+            Error::Io(_) => "IO",
+        }
+    }
+}
+
+/// Structured error payload returned by MCP tools
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorResponse {
+    pub code: &'static str,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_seconds: Option<u64>,
+}
+
+impl From<&Error> for ErrorResponse {
+    fn from(error: &Error) -> Self {
+        Self {
+            code: error.code(),
+            message: error.to_string(),
+            retry_after_seconds: error.retry_after().map(|delay| delay.as_secs()),
+        }
+    }
+}
+
+impl From<Error> for ErrorResponse {
+    fn from(error: Error) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl std::fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    /// Connection failures become retryable `Network` errors, anything else
+    /// (permissions, missing files, ...) a non-retryable `Io` error
+    fn from(error: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+
+        match error.kind() {
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::AddrNotAvailable
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::UnexpectedEof
+            | ErrorKind::HostUnreachable
+            | ErrorKind::NetworkUnreachable
+            | ErrorKind::NetworkDown => Error::Network(error.to_string()),
+            _ => Error::Io(error.to_string()),
+        }
+    }
+}
+
+impl From<grammers_client::InvocationError> for Error {
+    fn from(error: grammers_client::InvocationError) -> Self {
+        crate::telegram::client::classify_invocation_error(error)
+    }
+}
+
+#[cfg(feature = "index")]
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Self {
+        Error::Index(error.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -86,6 +199,165 @@ mod tests {
         assert_send_sync::<Error>();
     }
 
+    #[test]
+    fn test_rate_limit_is_retryable_with_delay() {
+        let error = Error::RateLimit {
+            retry_after_seconds: 5,
+        };
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_network_is_retryable_without_delay() {
+        let error = Error::Network("connection reset".to_string());
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_invalid_input_is_not_retryable() {
+        let error = Error::InvalidInput("bad id".to_string());
+        assert!(!error.is_retryable());
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_auth_is_not_retryable() {
+        let error = Error::Auth("session expired".to_string());
+        assert!(!error.is_retryable());
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_config_is_not_retryable() {
+        let error = Error::Config("missing api_id".to_string());
+        assert!(!error.is_retryable());
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_telegram_api_and_mcp_are_not_retryable() {
+        assert!(!Error::TelegramApi("CHANNEL_PRIVATE".to_string()).is_retryable());
+        assert!(!Error::Mcp("invalid request".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_from_io_error_maps_to_network() {
+        let io_error = std::io::Error::new(std::io::ErrorKind::TimedOut, "connection timed out");
+        let error = Error::from(io_error);
+
+        assert!(matches!(error, Error::Network(_)));
+        assert_eq!(error.to_string(), "network error: connection timed out");
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied");
+        let error = Error::from(io_error);
+
+        assert!(matches!(error, Error::Io(_)));
+        assert_eq!(error.to_string(), "I/O error: access denied");
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_from_flood_wait_maps_to_rate_limit() {
+        let rpc_error = grammers_client::InvocationError::Rpc(grammers_client::RpcError {
+            code: 420,
+            name: "FLOOD_WAIT".to_string(),
+            value: Some(30),
+            caused_by: None,
+        });
+        let error = Error::from(rpc_error);
+
+        assert!(matches!(
+            error,
+            Error::RateLimit {
+                retry_after_seconds: 30
+            }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "rate limit exceeded, retry after 30 seconds"
+        );
+    }
+
+    #[test]
+    fn test_from_other_rpc_error_maps_to_telegram_api() {
+        let rpc_error = grammers_client::InvocationError::Rpc(grammers_client::RpcError {
+            code: 400,
+            name: "CHANNEL_PRIVATE".to_string(),
+            value: None,
+            caused_by: None,
+        });
+        let error = Error::from(rpc_error);
+
+        assert!(matches!(error, Error::TelegramApi(_)));
+        assert!(error.to_string().starts_with("telegram API error:"));
+        assert!(error.to_string().contains("CHANNEL_PRIVATE"));
+    }
+
+    #[test]
+    fn test_not_found_error_display() {
+        let error = Error::NotFound("channel 123".to_string());
+        assert_eq!(error.to_string(), "not found: channel 123");
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_error_response_serializes_each_variant() {
+        let cases = [
+            (Error::Auth("session expired".to_string()), "AUTH"),
+            (
+                Error::TelegramApi("CHANNEL_PRIVATE".to_string()),
+                "TELEGRAM_API",
+            ),
+            (
+                Error::RateLimit {
+                    retry_after_seconds: 5,
+                },
+                "RATE_LIMIT",
+            ),
+            (Error::Config("missing api_id".to_string()), "CONFIG"),
+            (Error::Network("connection reset".to_string()), "NETWORK"),
+            (Error::Mcp("invalid request".to_string()), "MCP"),
+            (Error::InvalidInput("bad id".to_string()), "INVALID_INPUT"),
+            (Error::NotFound("channel 123".to_string()), "NOT_FOUND"),
+            (Error::Index("database is locked".to_string()), "INDEX"),
+            (Error::Io("permission denied".to_string()), "IO"),
+        ];
+
+        for (error, code) in cases {
+            let json = serde_json::to_value(ErrorResponse::from(&error)).unwrap();
+            assert_eq!(json["code"], code);
+            assert_eq!(json["message"], error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_error_response_includes_retry_after_for_rate_limit() {
+        let error = Error::RateLimit {
+            retry_after_seconds: 30,
+        };
+        let json = serde_json::to_value(ErrorResponse::from(&error)).unwrap();
+
+        assert_eq!(json["retry_after_seconds"], 30);
+    }
+
+    #[test]
+    fn test_error_response_omits_retry_after_for_other_errors() {
+        let error = Error::InvalidInput("bad id".to_string());
+        let json = serde_json::to_value(ErrorResponse::from(&error)).unwrap();
+
+        assert!(json.get("retry_after_seconds").is_none());
+    }
+
+    #[test]
+    fn test_from_dropped_request_maps_to_network() {
+        let error = Error::from(grammers_client::InvocationError::Dropped);
+
+        assert!(matches!(error, Error::Network(_)));
+        assert!(error.is_retryable());
+    }
+
     #[test]
     fn test_invalid_input_error_display() {
         let error = Error::InvalidInput("Channel ID must be positive".to_string());
@@ -0,0 +1,323 @@
+use crate::error::Error;
+use crate::telegram::types::{
+    Message, QueryMetadata, RankMode, SearchParams, SearchResult, normalize_text,
+};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+/// Messages keyed by channel and message ID, plus an FTS5 table over normalized text
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS messages (
+        channel_id INTEGER NOT NULL,
+        message_id INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL,
+        message TEXT NOT NULL,
+        PRIMARY KEY (channel_id, message_id)
+    );
+    CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(text);
+";
+
+/// Local SQLite store of fetched messages with full-text search
+///
+/// Results come from whatever has been stored, so a hit may miss messages that
+/// were never fetched; callers fall back to a live search when nothing matches.
+pub struct MessageIndex {
+    connection: Mutex<Connection>,
+}
+
+impl MessageIndex {
+    /// Open (or create) the index database at `path`
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Index that lives only as long as this value (for tests and dry runs)
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, Error> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> Result<MutexGuard<'_, Connection>, Error> {
+        self.connection
+            .lock()
+            .map_err(|_| Error::Index("index connection lock poisoned".to_string()))
+    }
+
+    /// Store messages, replacing any already indexed under the same channel and ID
+    ///
+    /// Returns the number of messages written.
+    pub fn insert(&self, messages: &[Message]) -> Result<usize, Error> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction()?;
+
+        for message in messages {
+            let channel_id = message.channel_id.get();
+            let message_id = message.id.get();
+
+            // REPLACE assigns a new rowid, so drop the stale full-text row first
+            let old_rowid: Option<i64> = transaction
+                .query_row(
+                    "SELECT rowid FROM messages WHERE channel_id = ?1 AND message_id = ?2",
+                    params![channel_id, message_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(rowid) = old_rowid {
+                transaction.execute("DELETE FROM messages_fts WHERE rowid = ?1", params![rowid])?;
+            }
+
+            let json = serde_json::to_string(message)
+                .map_err(|e| Error::Index(format!("Failed to encode message: {}", e)))?;
+            transaction.execute(
+                "INSERT OR REPLACE INTO messages (channel_id, message_id, timestamp, message)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![channel_id, message_id, message.timestamp.timestamp(), json],
+            )?;
+            transaction.execute(
+                "INSERT INTO messages_fts (rowid, text) VALUES (?1, ?2)",
+                params![
+                    transaction.last_insert_rowid(),
+                    normalize_text(&message.text)
+                ],
+            )?;
+        }
+
+        transaction.commit()?;
+        Ok(messages.len())
+    }
+
+    /// Number of indexed messages
+    pub fn len(&self) -> Result<u64, Error> {
+        let count: i64 =
+            self.connection()?
+                .query_row("SELECT COUNT(*) FROM messages", [], |row| row.get(0))?;
+        Ok(count.max(0) as u64)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Search the index with the same filters as a live search
+    ///
+    /// Returns `None` on a miss (no matching messages), so the caller can search live.
+    pub fn search(&self, params: &SearchParams) -> Result<Option<SearchResult>, Error> {
+        let params = params.clone().sanitized()?;
+        let start = Instant::now();
+        let (from, to) = params.time_range(Utc::now());
+        let terms = params.terms();
+
+        let mut messages = Vec::new();
+        {
+            let connection = self.connection()?;
+            let mut statement = connection.prepare(
+                "SELECT m.message FROM messages_fts f
+                 JOIN messages m ON m.rowid = f.rowid
+                 WHERE messages_fts MATCH ?1
+                   AND m.timestamp BETWEEN ?2 AND ?3
+                   AND (?4 IS NULL OR m.channel_id = ?4)",
+            )?;
+            let rows = statement.query_map(
+                params![
+                    fts_query(&terms),
+                    from.timestamp(),
+                    to.timestamp(),
+                    params.channel_id.map(|id| id.get()),
+                ],
+                |row| row.get::<_, String>(0),
+            )?;
+
+            for json in rows {
+                let message: Message = serde_json::from_str(&json?)
+                    .map_err(|e| Error::Index(format!("Failed to decode message: {}", e)))?;
+                if params.accepts(&message) {
+                    messages.push(message);
+                }
+            }
+        }
+
+        if messages.is_empty() {
+            return Ok(None);
+        }
+
+        let mut result = SearchResult {
+            total_found: messages.len() as u64,
+            messages,
+            search_time_ms: 0,
+            query_metadata: QueryMetadata {
+                query: terms.join(" OR "),
+                hours_back: params.hours_back,
+                channels_searched: 0,
+            },
+        };
+        result.sort_by_timestamp_desc();
+        if params.rank == RankMode::Relevance {
+            result.rank_by_relevance(&terms.join(" "));
+        }
+        result.messages.truncate(params.limit as usize);
+        result.search_time_ms = start.elapsed().as_millis() as u64;
+
+        Ok(Some(result))
+    }
+}
+
+/// FTS5 query matching any of the terms, each as a quoted phrase
+fn fts_query(terms: &[&str]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", normalize_text(term).replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram::types::{ChannelId, ChannelName, MediaType, MessageId, Username};
+
+    fn message(channel_id: i64, id: i64, text: &str, minutes_ago: i64) -> Message {
+        Message {
+            id: MessageId::new(id).unwrap(),
+            channel_id: ChannelId::new(channel_id).unwrap(),
+            channel_name: ChannelName::new("Test Channel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: text.to_string(),
+            timestamp: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    #[test]
+    fn insert_stores_messages() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        assert!(index.is_empty().unwrap());
+
+        let written = index
+            .insert(&[message(1, 10, "first", 5), message(1, 11, "second", 4)])
+            .unwrap();
+
+        assert_eq!(written, 2);
+        assert_eq!(index.len().unwrap(), 2);
+    }
+
+    #[test]
+    fn insert_replaces_same_channel_and_message_id() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        index.insert(&[message(1, 10, "old text", 5)]).unwrap();
+        index.insert(&[message(1, 10, "new text", 5)]).unwrap();
+
+        assert_eq!(index.len().unwrap(), 1);
+        assert!(index.search(&SearchParams::new("old")).unwrap().is_none());
+        let result = index.search(&SearchParams::new("new")).unwrap().unwrap();
+        assert_eq!(result.messages[0].text, "new text");
+    }
+
+    #[test]
+    fn search_finds_terms_with_fts() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        index
+            .insert(&[
+                message(1, 10, "Курс рубля падает", 30),
+                message(1, 11, "Bitcoin hits a new high", 20),
+                message(2, 5, "Рубль и биткоин", 10),
+            ])
+            .unwrap();
+
+        let result = index
+            .search(&SearchParams::new("биткоин"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].id.get(), 5);
+
+        // Several terms match any of them, newest first
+        let params = SearchParams {
+            queries: vec!["bitcoin".to_string()],
+            ..SearchParams::new("рубль")
+        };
+        let result = index.search(&params).unwrap().unwrap();
+        let ids: Vec<i64> = result.messages.iter().map(|m| m.id.get()).collect();
+        assert_eq!(ids, vec![5, 11]);
+        assert_eq!(result.total_found, 2);
+        assert_eq!(result.query_metadata.query, "рубль OR bitcoin");
+    }
+
+    #[test]
+    fn search_applies_channel_and_time_filters() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        index
+            .insert(&[
+                message(1, 10, "rust release", 30),
+                message(2, 20, "rust release", 30),
+                message(1, 11, "rust release", 60 * 24 * 5),
+            ])
+            .unwrap();
+
+        let params = SearchParams {
+            channel_id: Some(ChannelId::new(1).unwrap()),
+            ..SearchParams::new("rust")
+        };
+        let result = index.search(&params).unwrap().unwrap();
+
+        let ids: Vec<i64> = result.messages.iter().map(|m| m.id.get()).collect();
+        assert_eq!(ids, vec![10]);
+    }
+
+    #[test]
+    fn search_matches_normalized_text() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        index
+            .insert(&[message(1, 10, "Курс pyб\u{200B}ль", 5)])
+            .unwrap();
+
+        assert!(index.search(&SearchParams::new("рубль")).unwrap().is_some());
+    }
+
+    #[test]
+    fn search_misses_return_none() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        assert!(index.search(&SearchParams::new("rust")).unwrap().is_none());
+
+        index.insert(&[message(1, 10, "golang news", 5)]).unwrap();
+        assert!(index.search(&SearchParams::new("rust")).unwrap().is_none());
+    }
+
+    #[test]
+    fn search_quotes_fts_syntax_in_terms() {
+        let index = MessageIndex::open_in_memory().unwrap();
+        index
+            .insert(&[message(1, 10, "say \"hi\" OR bye", 5)])
+            .unwrap();
+
+        assert!(
+            index
+                .search(&SearchParams::new("\"hi\" OR"))
+                .unwrap()
+                .is_some()
+        );
+        assert!(index.search(&SearchParams::new("NEAR(")).unwrap().is_none());
+    }
+}
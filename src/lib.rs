@@ -1,5 +1,8 @@
+pub mod analytics;
 pub mod config;
 pub mod error;
+#[cfg(feature = "index")]
+pub mod index;
 pub mod link;
 pub mod logging;
 pub mod mcp;
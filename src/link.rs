@@ -1,31 +1,279 @@
-use crate::telegram::types::{ChannelId, MessageId};
+use crate::error::Error;
+use crate::telegram::types::{ChannelId, MessageId, Username};
 use serde::{Deserialize, Serialize};
 
 /// Generated deep links for a Telegram message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageLink {
-    pub channel_id: ChannelId,
+    /// Channel ID for private-style links (`None` for public username links)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    /// Channel username for public links
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<Username>,
     pub message_id: MessageId,
     pub https_link: String,
     pub tg_protocol_link: String,
 }
 
+/// Channel reference extracted from a parsed link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkChannel {
+    /// Private-style reference (`t.me/c/{id}` or `tg://resolve?channel={id}`)
+    Id(ChannelId),
+    /// Public reference (`t.me/{username}` or `tg://resolve?domain={username}`)
+    Username(Username),
+}
+
+/// Structured result of parsing an `https://t.me` or `tg://` message link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLink {
+    pub channel: LinkChannel,
+    pub message_id: MessageId,
+    pub thread_id: Option<MessageId>,
+    pub single: bool,
+}
+
 impl MessageLink {
     /// Create links for a specific message in a channel
-    pub fn new(channel_id: ChannelId, message_id: MessageId) -> Self {
-        let https_link = format!("https://t.me/c/{}/{}?single", channel_id, message_id);
+    ///
+    /// `single` opens just this message; without it Telegram shows the whole
+    /// album the message belongs to.
+    pub fn new(channel_id: ChannelId, message_id: MessageId, single: bool) -> Self {
+        let https_link = format!(
+            "https://t.me/c/{}/{}{}",
+            channel_id,
+            message_id,
+            if single { "?single" } else { "" }
+        );
         let tg_protocol_link = format!(
-            "tg://resolve?channel={}&post={}&single",
-            channel_id, message_id
+            "tg://resolve?channel={}&post={}{}",
+            channel_id,
+            message_id,
+            if single { "&single" } else { "" }
         );
 
         Self {
-            channel_id,
+            channel_id: Some(channel_id),
+            username: None,
+            message_id,
+            https_link,
+            tg_protocol_link,
+        }
+    }
+
+    /// Create links for a message in a public channel, addressed by username
+    ///
+    /// Unlike `t.me/c/{id}` links, these open for users who are not members.
+    pub fn new_public(username: &Username, message_id: MessageId) -> Self {
+        let https_link = format!("https://t.me/{}/{}", username, message_id);
+        let tg_protocol_link = format!("tg://resolve?domain={}&post={}", username, message_id);
+
+        Self {
+            channel_id: None,
+            username: Some(username.clone()),
             message_id,
             https_link,
             tg_protocol_link,
         }
     }
+
+    /// Parse a message link in either `https://t.me/...` or `tg://resolve?...` form
+    ///
+    /// Query parameters may appear in any order and are percent-decoded.
+    pub fn parse(url: &str) -> Result<ParsedLink, Error> {
+        let url = url.trim();
+
+        if let Some(rest) = url.strip_prefix("tg://") {
+            parse_tg_link(url, rest)
+        } else if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            parse_https_link(url, rest)
+        } else {
+            Err(invalid_link(url, "expected an https://t.me or tg:// link"))
+        }
+    }
+
+    /// Parse a private-style message link into its channel and message IDs
+    ///
+    /// Public `t.me/{username}` links are rejected since they carry no channel ID.
+    pub fn parse_ids(url: &str) -> Result<(ChannelId, MessageId), Error> {
+        let parsed = Self::parse(url)?;
+        match parsed.channel {
+            LinkChannel::Id(channel_id) => Ok((channel_id, parsed.message_id)),
+            LinkChannel::Username(_) => Err(invalid_link(
+                url.trim(),
+                "link uses a username, not a channel ID",
+            )),
+        }
+    }
+}
+
+// =============================================================================
+// Link Parsing Helpers
+// =============================================================================
+
+/// Parse the part of a `tg://` link after the scheme
+fn parse_tg_link(url: &str, rest: &str) -> Result<ParsedLink, Error> {
+    let (action, query) = rest
+        .split_once('?')
+        .ok_or_else(|| invalid_link(url, "missing query parameters"))?;
+
+    if action.trim_end_matches('/') != "resolve" {
+        return Err(invalid_link(url, "only tg://resolve links are supported"));
+    }
+
+    let params = parse_query(query)?;
+
+    let channel = match (
+        query_value(&params, "channel"),
+        query_value(&params, "domain"),
+    ) {
+        (Some(id), None) => {
+            LinkChannel::Id(ChannelId::new(parse_link_number(url, "channel", id)?)?)
+        }
+        (None, Some(domain)) => LinkChannel::Username(Username::new(domain)?),
+        (Some(_), Some(_)) => {
+            return Err(invalid_link(url, "both channel and domain are present"));
+        }
+        (None, None) => return Err(invalid_link(url, "missing channel or domain parameter")),
+    };
+
+    let post =
+        query_value(&params, "post").ok_or_else(|| invalid_link(url, "missing post parameter"))?;
+    let message_id = MessageId::new(parse_link_number(url, "post", post)?)?;
+
+    let thread_id = query_value(&params, "thread")
+        .map(|thread| MessageId::new(parse_link_number(url, "thread", thread)?))
+        .transpose()?;
+
+    Ok(ParsedLink {
+        channel,
+        message_id,
+        thread_id,
+        single: has_query_flag(&params, "single"),
+    })
+}
+
+/// Parse the part of an `https://t.me` link after the scheme
+fn parse_https_link(url: &str, rest: &str) -> Result<ParsedLink, Error> {
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let params = parse_query(query)?;
+
+    let mut segments = path.trim_end_matches('/').split('/');
+    if !matches!(segments.next(), Some("t.me" | "telegram.me")) {
+        return Err(invalid_link(url, "host must be t.me"));
+    }
+    let segments: Vec<&str> = segments.collect();
+
+    let (channel, path_thread, post) = match segments.as_slice() {
+        ["c", channel, post] => (
+            LinkChannel::Id(ChannelId::new(parse_link_number(url, "channel", channel)?)?),
+            None,
+            *post,
+        ),
+        ["c", channel, thread, post] => (
+            LinkChannel::Id(ChannelId::new(parse_link_number(url, "channel", channel)?)?),
+            Some(*thread),
+            *post,
+        ),
+        [username, post] => (
+            LinkChannel::Username(Username::new(*username)?),
+            None,
+            *post,
+        ),
+        _ => return Err(invalid_link(url, "unrecognized path")),
+    };
+
+    let message_id = MessageId::new(parse_link_number(url, "post", post)?)?;
+
+    let thread_id = path_thread
+        .or_else(|| query_value(&params, "thread"))
+        .map(|thread| MessageId::new(parse_link_number(url, "thread", thread)?))
+        .transpose()?;
+
+    Ok(ParsedLink {
+        channel,
+        message_id,
+        thread_id,
+        single: has_query_flag(&params, "single"),
+    })
+}
+
+/// Split a query string into percent-decoded key/value pairs
+///
+/// Flags without a value (e.g. `single`) are returned with `None`.
+fn parse_query(query: &str) -> Result<Vec<(String, Option<String>)>, Error> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => Ok((percent_decode(key)?, Some(percent_decode(value)?))),
+            None => Ok((percent_decode(pair)?, None)),
+        })
+        .collect()
+}
+
+fn query_value<'a>(params: &'a [(String, Option<String>)], key: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(k, _)| k == key)
+        .and_then(|(_, value)| value.as_deref())
+}
+
+fn has_query_flag(params: &[(String, Option<String>)], key: &str) -> bool {
+    params.iter().any(|(k, _)| k == key)
+}
+
+fn percent_decode(value: &str) -> Result<String, Error> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let (Some(high), Some(low)) = (
+                    bytes.get(i + 1).and_then(|b| hex_value(*b)),
+                    bytes.get(i + 2).and_then(|b| hex_value(*b)),
+                ) else {
+                    return Err(Error::InvalidInput(format!(
+                        "Invalid percent-encoding in '{}'",
+                        value
+                    )));
+                };
+                decoded.push((high << 4) | low);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded)
+        .map_err(|_| Error::InvalidInput(format!("Invalid UTF-8 in '{}'", value)))
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+fn parse_link_number(url: &str, name: &str, value: &str) -> Result<i64, Error> {
+    value
+        .parse()
+        .map_err(|_| invalid_link(url, &format!("{} '{}' is not a valid number", name, value)))
+}
+
+fn invalid_link(url: &str, reason: &str) -> Error {
+    Error::InvalidInput(format!("Invalid Telegram link '{}': {}", url, reason))
 }
 
 // =============================================================================
@@ -40,7 +288,7 @@ mod tests {
     fn message_link_https_format() {
         let channel_id = ChannelId::new(123456789).unwrap();
         let message_id = MessageId::new(42).unwrap();
-        let link = MessageLink::new(channel_id, message_id);
+        let link = MessageLink::new(channel_id, message_id, true);
 
         assert_eq!(link.https_link, "https://t.me/c/123456789/42?single");
     }
@@ -49,7 +297,7 @@ mod tests {
     fn message_link_tg_protocol_format() {
         let channel_id = ChannelId::new(123456789).unwrap();
         let message_id = MessageId::new(42).unwrap();
-        let link = MessageLink::new(channel_id, message_id);
+        let link = MessageLink::new(channel_id, message_id, true);
 
         assert_eq!(
             link.tg_protocol_link,
@@ -57,19 +305,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn message_link_without_single_links_whole_album() {
+        let channel_id = ChannelId::new(123456789).unwrap();
+        let message_id = MessageId::new(42).unwrap();
+        let link = MessageLink::new(channel_id, message_id, false);
+
+        assert_eq!(link.https_link, "https://t.me/c/123456789/42");
+        assert_eq!(
+            link.tg_protocol_link,
+            "tg://resolve?channel=123456789&post=42"
+        );
+    }
+
+    #[test]
+    fn message_link_without_single_round_trips_through_parse() {
+        let link = MessageLink::new(
+            ChannelId::new(100).unwrap(),
+            MessageId::new(7).unwrap(),
+            false,
+        );
+
+        let parsed = MessageLink::parse(&link.https_link).unwrap();
+        assert!(!parsed.single);
+        assert_eq!(MessageLink::parse(&link.tg_protocol_link).unwrap(), parsed);
+    }
+
     #[test]
     fn message_link_stores_ids() {
         let channel_id = ChannelId::new(999).unwrap();
         let message_id = MessageId::new(111).unwrap();
-        let link = MessageLink::new(channel_id, message_id);
+        let link = MessageLink::new(channel_id, message_id, true);
 
-        assert_eq!(link.channel_id, channel_id);
+        assert_eq!(link.channel_id, Some(channel_id));
         assert_eq!(link.message_id, message_id);
     }
 
+    #[test]
+    fn public_link_formats() {
+        let username = Username::new("durov_news").unwrap();
+        let link = MessageLink::new_public(&username, MessageId::new(42).unwrap());
+
+        assert_eq!(link.https_link, "https://t.me/durov_news/42");
+        assert_eq!(
+            link.tg_protocol_link,
+            "tg://resolve?domain=durov_news&post=42"
+        );
+        assert_eq!(link.channel_id, None);
+        assert_eq!(link.username, Some(username));
+    }
+
+    #[test]
+    fn public_link_round_trips_through_parse() {
+        let username = Username::new("durov_news").unwrap();
+        let link = MessageLink::new_public(&username, MessageId::new(42).unwrap());
+
+        let from_https = MessageLink::parse(&link.https_link).unwrap();
+        let from_tg = MessageLink::parse(&link.tg_protocol_link).unwrap();
+
+        assert_eq!(from_https, from_tg);
+        assert_eq!(from_https.channel, LinkChannel::Username(username));
+    }
+
     #[test]
     fn message_link_serialization() {
-        let link = MessageLink::new(ChannelId::new(100).unwrap(), MessageId::new(200).unwrap());
+        let link = MessageLink::new(
+            ChannelId::new(100).unwrap(),
+            MessageId::new(200).unwrap(),
+            true,
+        );
 
         let json = serde_json::to_string(&link).unwrap();
         let deserialized: MessageLink = serde_json::from_str(&json).unwrap();
@@ -80,11 +384,167 @@ mod tests {
 
     #[test]
     fn message_link_different_ids() {
-        let link1 = MessageLink::new(ChannelId::new(100).unwrap(), MessageId::new(1).unwrap());
-        let link2 = MessageLink::new(ChannelId::new(200).unwrap(), MessageId::new(2).unwrap());
+        let link1 = MessageLink::new(
+            ChannelId::new(100).unwrap(),
+            MessageId::new(1).unwrap(),
+            true,
+        );
+        let link2 = MessageLink::new(
+            ChannelId::new(200).unwrap(),
+            MessageId::new(2).unwrap(),
+            true,
+        );
 
         assert_eq!(link1.https_link, "https://t.me/c/100/1?single");
         assert_eq!(link2.https_link, "https://t.me/c/200/2?single");
         assert_ne!(link1.https_link, link2.https_link);
     }
+
+    // =========================================================================
+    // Parsing Tests (tg:// protocol)
+    // =========================================================================
+
+    #[test]
+    fn parse_tg_link_with_domain() {
+        let parsed = MessageLink::parse("tg://resolve?domain=durov_news&post=5").unwrap();
+
+        assert_eq!(
+            parsed.channel,
+            LinkChannel::Username(Username::new("durov_news").unwrap())
+        );
+        assert_eq!(parsed.message_id, MessageId::new(5).unwrap());
+        assert_eq!(parsed.thread_id, None);
+        assert!(!parsed.single);
+    }
+
+    #[test]
+    fn parse_tg_link_with_channel_thread_and_single() {
+        let parsed = MessageLink::parse("tg://resolve?channel=123&post=5&thread=2&single").unwrap();
+
+        assert_eq!(
+            parsed.channel,
+            LinkChannel::Id(ChannelId::new(123).unwrap())
+        );
+        assert_eq!(parsed.message_id, MessageId::new(5).unwrap());
+        assert_eq!(parsed.thread_id, Some(MessageId::new(2).unwrap()));
+        assert!(parsed.single);
+    }
+
+    #[test]
+    fn parse_tg_link_parameter_order_independent() {
+        let parsed = MessageLink::parse("tg://resolve?single&post=42&channel=123456789").unwrap();
+        let expected = MessageLink::parse("tg://resolve?channel=123456789&post=42&single").unwrap();
+
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn parse_tg_link_decodes_percent_encoding() {
+        let parsed = MessageLink::parse("tg://resolve?domain=durov%5Fnews&post=%35").unwrap();
+
+        assert_eq!(
+            parsed.channel,
+            LinkChannel::Username(Username::new("durov_news").unwrap())
+        );
+        assert_eq!(parsed.message_id, MessageId::new(5).unwrap());
+    }
+
+    #[test]
+    fn parse_tg_link_matches_https_result() {
+        let link = MessageLink::new(
+            ChannelId::new(123456789).unwrap(),
+            MessageId::new(42).unwrap(),
+            true,
+        );
+
+        let from_tg = MessageLink::parse(&link.tg_protocol_link).unwrap();
+        let from_https = MessageLink::parse(&link.https_link).unwrap();
+
+        assert_eq!(from_tg, from_https);
+    }
+
+    // =========================================================================
+    // Parsing Tests (channel and message IDs)
+    // =========================================================================
+
+    #[test]
+    fn parse_ids_https_link() {
+        let expected = (
+            ChannelId::new(123456789).unwrap(),
+            MessageId::new(42).unwrap(),
+        );
+
+        assert_eq!(
+            MessageLink::parse_ids("https://t.me/c/123456789/42").unwrap(),
+            expected
+        );
+        assert_eq!(
+            MessageLink::parse_ids("https://t.me/c/123456789/42?single").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_ids_tg_link() {
+        let ids = MessageLink::parse_ids("tg://resolve?channel=123456789&post=42").unwrap();
+
+        assert_eq!(ids.0, ChannelId::new(123456789).unwrap());
+        assert_eq!(ids.1, MessageId::new(42).unwrap());
+    }
+
+    #[test]
+    fn parse_ids_ignores_trailing_query_params() {
+        let ids = MessageLink::parse_ids("https://t.me/c/100/7?single&utm_source=share&comment=3")
+            .unwrap();
+
+        assert_eq!(
+            ids,
+            (ChannelId::new(100).unwrap(), MessageId::new(7).unwrap())
+        );
+    }
+
+    #[test]
+    fn parse_ids_malformed_fails() {
+        let malformed = [
+            "",
+            "not a link",
+            "https://example.com/c/100/7",
+            "https://t.me/c/100",
+            "https://t.me/c/abc/7",
+            "https://t.me/c/100/0",
+            "https://t.me/durov_news/7",
+            "tg://resolve?channel=100",
+        ];
+
+        for url in malformed {
+            let result = MessageLink::parse_ids(url);
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "Expected InvalidInput for {}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn parse_tg_link_malformed_fails() {
+        let malformed = [
+            "tg://resolve",
+            "tg://resolve?post=5",
+            "tg://resolve?channel=abc&post=5",
+            "tg://resolve?channel=123",
+            "tg://resolve?channel=123&domain=durov_news&post=5",
+            "tg://resolve?channel=123&post=5%2",
+            "tg://join?invite=abc",
+        ];
+
+        for url in malformed {
+            let result = MessageLink::parse(url);
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "Expected InvalidInput for {}",
+                url
+            );
+        }
+    }
 }
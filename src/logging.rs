@@ -1,73 +1,287 @@
 use crate::config::LoggingConfig;
+use anyhow::Context;
+use std::io::{self, Write};
+use std::path::Path;
+use tracing::Subscriber;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::{BoxMakeWriter, MakeWriter, MakeWriterExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+const REDACTED_PLACEHOLDER: &str = "[REDACTED]";
+const MASK: &str = "***";
+
+/// Values this short are always fully redacted
+const MIN_REDACTABLE_LEN: usize = 6;
+
+/// Shortest hex run treated as a secret (Telegram API hashes are 32 hex digits)
+const MIN_HEX_SECRET_LEN: usize = 32;
+
+/// QR login URLs carry a token that signs in as the user
+const LOGIN_TOKEN_PREFIX: &str = "tg://login?token=";
 
 /// Initialize tracing subscriber with configured format and output
 pub fn init(config: &LoggingConfig) -> anyhow::Result<()> {
-    // Build filter from config level or environment variable
-    let filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.level));
-
-    // Apply format based on config and initialize
     // Use try_init() to gracefully handle already-initialized subscriber (common in tests)
-    let result = match config.format.as_str() {
-        "json" => tracing_subscriber::fmt()
-            .with_writer(std::io::stderr)
-            .json()
-            .with_env_filter(filter)
-            .try_init(),
-        "pretty" => tracing_subscriber::fmt()
-            .with_writer(std::io::stderr)
-            .pretty()
-            .with_env_filter(filter)
-            .try_init(),
-        _ => {
-            // Default to compact
+    let result = build_subscriber(config)?.try_init();
+
+    // Ignore error if subscriber is already initialized (common in tests)
+    result.or(Ok(()))
+}
+
+/// Build the subscriber for the configured level, format and outputs
+fn build_subscriber(config: &LoggingConfig) -> anyhow::Result<Box<dyn Subscriber + Send + Sync>> {
+    // Build filter from environment variable, falling back to the config directives
+    let filter = match EnvFilter::try_from_default_env() {
+        Ok(filter) => filter,
+        Err(_) => parse_filter(&config.level)?,
+    };
+    let writer = make_writer(config)?;
+    // Escape codes would end up verbatim in the log file
+    let ansi = config.file.is_none();
+
+    // Apply format based on config
+    let subscriber: Box<dyn Subscriber + Send + Sync> = match config.format.as_str() {
+        "json" => Box::new(
+            tracing_subscriber::fmt()
+                .with_writer(writer)
+                .json()
+                .with_env_filter(filter)
+                .finish(),
+        ),
+        "pretty" => Box::new(
             tracing_subscriber::fmt()
-                .with_writer(std::io::stderr)
-                .compact()
+                .with_writer(writer)
+                .with_ansi(ansi)
+                .pretty()
                 .with_env_filter(filter)
-                .try_init()
+                .finish(),
+        ),
+        _ => {
+            // Default to compact
+            Box::new(
+                tracing_subscriber::fmt()
+                    .with_writer(writer)
+                    .with_ansi(ansi)
+                    .compact()
+                    .with_env_filter(filter)
+                    .finish(),
+            )
         }
     };
 
-    // Ignore error if subscriber is already initialized (common in tests)
-    result.or(Ok(()))
+    Ok(subscriber)
 }
 
-/// Redact phone number for safe logging
-/// Shows first 4 chars + last 3 chars, hides middle
-/// Returns "[REDACTED]" for strings ≤6 characters
-pub fn redact_phone(phone: &str) -> String {
-    if phone.len() <= 6 {
-        return "[REDACTED]".to_string();
+/// Parse a level or `EnvFilter` directive string, e.g. `info,grammers=warn`
+pub fn parse_filter(directives: &str) -> anyhow::Result<EnvFilter> {
+    EnvFilter::try_new(directives)
+        .with_context(|| format!("Invalid log filter directives: '{}'", directives))
+}
+
+/// Stderr, plus a rolling log file when `file` is configured, with secrets scrubbed
+fn make_writer(config: &LoggingConfig) -> anyhow::Result<BoxMakeWriter> {
+    let Some(path) = &config.file else {
+        return Ok(BoxMakeWriter::new(RedactingMakeWriter(std::io::stderr)));
+    };
+
+    let appender = file_appender(path, config.rotation.as_deref().unwrap_or("never"))?;
+    Ok(BoxMakeWriter::new(RedactingMakeWriter(
+        std::io::stderr.and(appender),
+    )))
+}
+
+/// Wraps a `MakeWriter` so every formatted log line passes through `scrub_secrets`
+///
+/// Defense in depth: catches secrets logged without `SecretString` or `redact_*`,
+/// whatever the output format.
+struct RedactingMakeWriter<M>(M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    // The fmt layer writes each event with a single call, so matches are never split
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(scrub_secrets(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Replace phone numbers, long hex hashes and QR login tokens with "[REDACTED]"
+///
+/// Phone numbers are `+` followed by 7-15 digits (single spaces or dashes allowed
+/// between digits); hashes are runs of 32+ hex digits.
+pub fn scrub_secrets(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = login_token_len(rest) {
+            result.push_str(LOGIN_TOKEN_PREFIX);
+            result.push_str(REDACTED_PLACEHOLDER);
+            rest = &rest[len..];
+            continue;
+        }
+
+        // Only match at the start of a word, e.g. not inside an identifier
+        let at_word_start = !result.chars().next_back().is_some_and(is_word_char);
+        if at_word_start && let Some(len) = phone_len(rest).or_else(|| hex_secret_len(rest)) {
+            result.push_str(REDACTED_PLACEHOLDER);
+            rest = &rest[len..];
+            continue;
+        }
+
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    result
+}
+
+/// Length of a `tg://login?token=...` URL at the start of `text`
+fn login_token_len(text: &str) -> Option<usize> {
+    let token = text.strip_prefix(LOGIN_TOKEN_PREFIX)?;
+    let token_len = token
+        .bytes()
+        .take_while(|&b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'='))
+        .count();
+    (token_len > 0).then_some(LOGIN_TOKEN_PREFIX.len() + token_len)
+}
+
+/// Length of an international phone number at the start of `text`
+fn phone_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if bytes.first() != Some(&b'+') {
+        return None;
     }
 
-    let visible_start = 4;
-    let visible_end = 3;
+    let mut end = 1;
+    let mut digits = 0;
+    while end < bytes.len() {
+        if bytes[end].is_ascii_digit() {
+            digits += 1;
+            end += 1;
+        } else if matches!(bytes[end], b' ' | b'-')
+            && digits > 0
+            && bytes.get(end + 1).is_some_and(u8::is_ascii_digit)
+        {
+            end += 1;
+        } else {
+            break;
+        }
+    }
 
-    format!(
-        "{}***{}",
-        &phone[..visible_start],
-        &phone[phone.len() - visible_end..]
-    )
+    ((7..=15).contains(&digits) && !ends_inside_word(text, end)).then_some(end)
+}
+
+/// Length of a run of 32+ hex digits at the start of `text`
+fn hex_secret_len(text: &str) -> Option<usize> {
+    let len = text.bytes().take_while(u8::is_ascii_hexdigit).count();
+    (len >= MIN_HEX_SECRET_LEN && !ends_inside_word(text, len)).then_some(len)
+}
+
+fn ends_inside_word(text: &str, end: usize) -> bool {
+    text[end..].chars().next().is_some_and(is_word_char)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Rolling appender writing to `path`; rotated files get a date suffix
+fn file_appender(path: &Path, rotation: &str) -> anyhow::Result<RollingFileAppender> {
+    let rotation = match rotation {
+        "daily" => Rotation::DAILY,
+        "hourly" => Rotation::HOURLY,
+        "never" => Rotation::NEVER,
+        other => anyhow::bail!(
+            "Unknown log rotation '{}': expected daily, hourly or never",
+            other
+        ),
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("Log file path has no file name: {}", path.display()))?;
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(file_name.to_string_lossy())
+        .build(directory)
+        .with_context(|| format!("Failed to open log file: {}", path.display()))
+}
+
+/// Redact a secret for safe logging
+/// Shows the first `visible_start` and last `visible_end` chars, hides the middle
+/// Returns "[REDACTED]" when that would reveal the whole value, or for strings ≤6 characters
+///
+/// Counts and slices by `char`, so multi-byte UTF-8 input never splits a character.
+pub fn redact(value: &str, visible_start: usize, visible_end: usize) -> String {
+    let char_count = value.chars().count();
+    if char_count <= MIN_REDACTABLE_LEN.max(visible_start + visible_end) {
+        return REDACTED_PLACEHOLDER.to_string();
+    }
+
+    let redacted = format!(
+        "{}{}{}",
+        &value[..char_boundary(value, visible_start)],
+        MASK,
+        &value[char_boundary(value, char_count - visible_end)..]
+    );
+    debug_assert!(is_redacted(value, &redacted), "redact leaked its input");
+    redacted
+}
+
+/// Byte offset of the `n`-th char, or the end of the string
+fn char_boundary(value: &str, n: usize) -> usize {
+    value
+        .char_indices()
+        .nth(n)
+        .map_or(value.len(), |(index, _)| index)
+}
+
+/// Redact phone number for safe logging
+/// Shows first 4 chars + last 3 chars, hides middle
+pub fn redact_phone(phone: &str) -> String {
+    redact(phone, 4, 3)
 }
 
 /// Redact API hash for safe logging
 /// Shows first 4 chars + last 1 char, hides middle
-/// Returns "[REDACTED]" for strings ≤6 characters
 pub fn redact_hash(hash: &str) -> String {
-    if hash.len() <= 6 {
-        return "[REDACTED]".to_string();
-    }
+    redact(hash, 4, 1)
+}
+
+/// Redact bot token for safe logging
+/// Shows first 4 chars + last 2 chars, hides middle (including the bot ID / secret separator)
+pub fn redact_token(token: &str) -> String {
+    redact(token, 4, 2)
+}
 
-    let visible_start = 4;
-    let visible_end = 1;
+/// Check that `redacted` is a safe redaction of `original`
+/// True only if the full original is absent and the middle is masked
+pub fn is_redacted(original: &str, redacted: &str) -> bool {
+    if redacted == REDACTED_PLACEHOLDER {
+        return true;
+    }
 
-    format!(
-        "{}***{}",
-        &hash[..visible_start],
-        &hash[hash.len() - visible_end..]
-    )
+    redacted.contains(MASK) && !redacted.contains(original)
 }
 
 #[cfg(test)]
@@ -96,10 +310,18 @@ mod tests {
 
     #[test]
     fn redact_phone_exactly_minimum_length() {
-        // Phone with 7 characters (minimum: 4 visible start + 3 visible end)
+        // Phone with 8 characters (minimum: 4 visible start + 3 visible end + 1 hidden)
+        let phone = "+1234567";
+        let redacted = redact_phone(phone);
+        assert_eq!(redacted, "+123***567");
+    }
+
+    #[test]
+    fn redact_phone_fully_visible_is_redacted() {
+        // 7 characters would all be visible around the mask
         let phone = "+123456";
         let redacted = redact_phone(phone);
-        assert_eq!(redacted, "+123***456");
+        assert_eq!(redacted, "[REDACTED]");
     }
 
     #[test]
@@ -117,6 +339,69 @@ mod tests {
         assert_eq!(redacted, "[REDACTED]");
     }
 
+    // ========================================================================
+    // Generic Redaction Tests
+    // ========================================================================
+
+    #[test]
+    fn redact_shows_requested_prefix_and_suffix() {
+        assert_eq!(redact("abcdefghij", 2, 2), "ab***ij");
+        assert_eq!(redact("abcdefghij", 3, 0), "abc***");
+        assert_eq!(redact("abcdefghij", 0, 3), "***hij");
+        assert_eq!(redact("abcdefghij", 0, 0), "***");
+    }
+
+    #[test]
+    fn redact_placeholder_when_everything_would_be_visible() {
+        assert_eq!(redact("abcdefghij", 5, 5), "[REDACTED]");
+        assert_eq!(redact("abcdefghij", 6, 5), "[REDACTED]");
+        assert_eq!(redact("abcdefghijk", 5, 5), "abcde***ghijk");
+    }
+
+    #[test]
+    fn redact_placeholder_for_short_values() {
+        assert_eq!(redact("abcdef", 0, 0), "[REDACTED]");
+        assert_eq!(redact("abcdefg", 0, 0), "***");
+    }
+
+    #[test]
+    fn redact_token_hides_bot_secret() {
+        let token = "123456789:AAHdqTcvCH1vGWJxfSeofSAs0K5PALDsaw";
+        let redacted = redact_token(token);
+        assert_eq!(redacted, "1234***aw");
+        assert!(is_redacted(token, &redacted));
+    }
+
+    // ========================================================================
+    // Unicode Redaction Tests
+    // ========================================================================
+
+    #[test]
+    fn redact_cyrillic_counts_chars_not_bytes() {
+        let redacted = redact("Привет, мир", 4, 3);
+        assert_eq!(redacted, "Прив***мир");
+    }
+
+    #[test]
+    fn redact_emoji_does_not_panic() {
+        let value = "🔑🔑secret🔒🔒";
+        let redacted = redact(value, 2, 2);
+        assert_eq!(redacted, "🔑🔑***🔒🔒");
+        assert!(is_redacted(value, &redacted));
+    }
+
+    #[test]
+    fn redact_short_multibyte_value_uses_placeholder() {
+        // 6 chars but 12 bytes: still too short to show any of it
+        assert_eq!(redact_phone("пароль"), "[REDACTED]");
+        assert_eq!(redact_hash("🙂🙂🙂🙂🙂🙂"), "[REDACTED]");
+    }
+
+    #[test]
+    fn redact_phone_with_non_ascii_digits() {
+        assert_eq!(redact_phone("+٧٩٠١٢٣٤٥٦٧"), "+٧٩٠***٥٦٧");
+    }
+
     // ========================================================================
     // API Hash Redaction Tests
     // ========================================================================
@@ -160,6 +445,40 @@ mod tests {
         assert_eq!(redacted, "[REDACTED]");
     }
 
+    // ========================================================================
+    // Redaction Guard Tests
+    // ========================================================================
+
+    #[test]
+    fn is_redacted_accepts_redacted_phone() {
+        let phone = "+1234567890";
+        assert!(is_redacted(phone, &redact_phone(phone)));
+    }
+
+    #[test]
+    fn is_redacted_accepts_redacted_hash() {
+        let hash = "abc123def456";
+        assert!(is_redacted(hash, &redact_hash(hash)));
+    }
+
+    #[test]
+    fn is_redacted_accepts_placeholder() {
+        assert!(is_redacted("+12345", "[REDACTED]"));
+    }
+
+    #[test]
+    fn is_redacted_rejects_unredacted_value() {
+        // Visible-char counts edited so the whole value slips through
+        let phone = "+1234567890";
+        assert!(!is_redacted(phone, "+1234567890***"));
+        assert!(!is_redacted(phone, phone));
+    }
+
+    #[test]
+    fn is_redacted_rejects_missing_mask() {
+        assert!(!is_redacted("+1234567890", "+123890"));
+    }
+
     // ========================================================================
     // Initialization Tests
     // ========================================================================
@@ -170,6 +489,8 @@ mod tests {
         let config = LoggingConfig {
             level: "info".to_string(),
             format: "compact".to_string(),
+            file: None,
+            rotation: None,
         };
 
         // Should not panic or return error
@@ -186,6 +507,8 @@ mod tests {
             let config = LoggingConfig {
                 level: level.to_string(),
                 format: "compact".to_string(),
+                file: None,
+                rotation: None,
             };
 
             let result = init(&config);
@@ -193,6 +516,163 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_filter_accepts_multi_target_directives() {
+        let filter = parse_filter("info,grammers=warn,telegram_connector=debug").unwrap();
+        let rendered = filter.to_string();
+        assert!(rendered.contains("grammers=warn"));
+        assert!(rendered.contains("telegram_connector=debug"));
+    }
+
+    #[test]
+    fn parse_filter_accepts_plain_level() {
+        assert!(parse_filter("warn").is_ok());
+    }
+
+    #[test]
+    fn parse_filter_rejects_invalid_directive() {
+        let result = parse_filter("grammers=loud");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("grammers=loud"));
+    }
+
+    // ========================================================================
+    // Secret Scrubbing Tests
+    // ========================================================================
+
+    #[test]
+    fn scrub_secrets_redacts_phone_numbers() {
+        assert_eq!(
+            scrub_secrets("phone=+1234567890 connected"),
+            "phone=[REDACTED] connected"
+        );
+        assert_eq!(
+            scrub_secrets("call +7 999 123-45-67 now"),
+            "call [REDACTED] now"
+        );
+    }
+
+    #[test]
+    fn scrub_secrets_redacts_hex_hashes() {
+        let line = r#"TelegramConfig { api_hash: "0123456789abcdef0123456789ABCDEF" }"#;
+        assert_eq!(
+            scrub_secrets(line),
+            r#"TelegramConfig { api_hash: "[REDACTED]" }"#
+        );
+    }
+
+    #[test]
+    fn scrub_secrets_redacts_login_tokens() {
+        assert_eq!(
+            scrub_secrets("scan tg://login?token=AQIDBAUG-_xyz= to sign in"),
+            "scan tg://login?token=[REDACTED] to sign in"
+        );
+    }
+
+    #[test]
+    fn scrub_secrets_keeps_ordinary_text() {
+        let lines = [
+            "Fetched 20 messages from channel 1234567890",
+            "retry in +3 seconds",
+            "timestamp 2024-01-15T10:30:00+00:00",
+            "message_id=42 hash=deadbeef",
+            "phone +123***890 redacted already",
+            "Привет, мир 🙂",
+        ];
+        for line in lines {
+            assert_eq!(scrub_secrets(line), line);
+        }
+    }
+
+    #[test]
+    fn scrub_secrets_ignores_matches_inside_words() {
+        let line = "id_0123456789abcdef0123456789abcdef and x+1234567890";
+        assert_eq!(scrub_secrets(line), line);
+    }
+
+    #[test]
+    fn redacting_writer_scrubs_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("telegram-mcp.log");
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "json".to_string(),
+            file: Some(path.clone()),
+            rotation: None,
+        };
+
+        let subscriber = build_subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(phone = "+1234567890", "signing in");
+        });
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("signing in"));
+        assert!(contents.contains("[REDACTED]"));
+        assert!(!contents.contains("1234567890"));
+    }
+
+    #[test]
+    fn file_logging_creates_and_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("telegram-mcp.log");
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "compact".to_string(),
+            file: Some(path.clone()),
+            rotation: Some("never".to_string()),
+        };
+
+        let subscriber = build_subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("disconnect diagnostics");
+        });
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("disconnect diagnostics"));
+        assert!(
+            !contents.contains('\u{1b}'),
+            "log file contains ANSI escapes"
+        );
+    }
+
+    #[test]
+    fn file_logging_daily_rotation_adds_date_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "json".to_string(),
+            file: Some(dir.path().join("telegram-mcp.log")),
+            rotation: Some("daily".to_string()),
+        };
+
+        let subscriber = build_subscriber(&config).unwrap();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("rotated");
+        });
+
+        let names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names.len(), 1);
+        assert!(names[0].starts_with("telegram-mcp.log."));
+    }
+
+    #[test]
+    fn file_logging_rejects_unknown_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LoggingConfig {
+            level: "info".to_string(),
+            format: "compact".to_string(),
+            file: Some(dir.path().join("telegram-mcp.log")),
+            rotation: Some("weekly".to_string()),
+        };
+
+        let error = build_subscriber(&config).err().unwrap();
+        assert!(error.to_string().contains("weekly"));
+    }
+
     #[test]
     fn init_with_different_formats() {
         // Test various formats
@@ -202,6 +682,8 @@ mod tests {
             let config = LoggingConfig {
                 level: "info".to_string(),
                 format: format.to_string(),
+                file: None,
+                rotation: None,
             };
 
             let result = init(&config);
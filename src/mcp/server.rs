@@ -1,19 +1,145 @@
-use crate::link::MessageLink;
+use crate::error::{Error, ErrorResponse};
+#[cfg(feature = "index")]
+use crate::index::MessageIndex;
+use crate::link::{LinkChannel, MessageLink};
 use crate::mcp::tools::{
-    ChannelsResponse, GenerateLinkRequest, GetChannelInfoRequest, GetChannelsRequest,
-    MessageLinkResponse, OpenMessageRequest, OpenMessageResponse, SearchRequest, StatusResponse,
+    ChannelStatsRequest, ChannelsResponse, DownloadMediaRequest, DownloadMediaResponse,
+    FoldersResponse, ForwardMessageRequest, ForwardMessageResponse, GenerateLinkRequest,
+    GenerateLinksRequest, GenerateLinksResponse, GetChannelInfoRequest, GetChannelsRequest,
+    GetHistoryRequest, HistoryResponse, LinkError, MessageLinkResponse, OpenLinkRequest,
+    OpenMessageRequest, OpenMessageResponse, SearchEtaResponse, SearchRequest, SendMessageRequest,
+    SendMessageResponse, StatusResponse,
 };
 use crate::rate_limiter::RateLimiterTrait;
-use crate::telegram::Channel;
 use crate::telegram::client::TelegramClientTrait;
-use crate::telegram::types::{ChannelId, MessageId, SearchParams, SearchResult};
-use rmcp::model::{Implementation, InitializeResult, ProtocolVersion};
-use rmcp::{Json, ServerHandler, ServiceExt};
+use crate::telegram::types::{
+    ChannelId, ChannelIdentifier, MediaType, MessageId, SearchParams, SearchResult, UserId,
+};
+use crate::telegram::{Channel, ChannelBlocklist, ChannelStats, UserProfile};
+use chrono::{DateTime, Utc};
+use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::handler::server::tool::ToolCallContext;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, Content, Implementation, InitializeResult, IntoContents,
+    ListToolsResult, PaginatedRequestParam, ProtocolVersion, ServerCapabilities,
+};
+use rmcp::service::RequestContext;
+use rmcp::transport::IntoTransport;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
+use rmcp::{ErrorData, Json, RoleServer, ServerHandler, ServiceExt, tool, tool_router};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+
+/// URL path the HTTP transport serves MCP on
+const HTTP_MCP_PATH: &str = "/mcp";
+
+/// Rate limiter tokens consumed by a single search
+const SEARCH_TOKEN_COST: u32 = 1;
+
+/// Characters of context on each side of a match when snippets are requested
+const DEFAULT_SNIPPET_RADIUS: usize = 80;
+
+/// Largest snippet radius a search request may ask for
+const MAX_SNIPPET_RADIUS: usize = 1000;
+
+/// Rate limiter tokens consumed by a single history page
+const HISTORY_TOKEN_COST: u32 = 1;
+
+/// Longest text Telegram accepts in a single message
+const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Rate limiter tokens consumed by a single media download
+const DOWNLOAD_TOKEN_COST: u32 = 1;
+
+/// Rate limiter tokens consumed per channel_stats call
+const STATS_TOKEN_COST: u32 = 1;
+
+/// Longest check_mcp_status waits for a Telegram ping before reporting none
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Rate limiter tokens consumed when a filtered listing walks every dialog
+const LISTING_TOKEN_COST: u32 = 1;
+
+/// Rate limiter tokens consumed per forwarded message
+const FORWARD_TOKEN_COST: u32 = 1;
+
+/// Most message IDs accepted by a single generate_message_links call
+const MAX_LINKS_PER_REQUEST: usize = 100;
+
+/// Default and maximum channels returned by get_subscribed_channels
+const DEFAULT_CHANNELS_LIMIT: u32 = 20;
+const MAX_CHANNELS_LIMIT: u32 = 500;
+
+/// Default and maximum messages returned by get_channel_history
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+const MAX_HISTORY_LIMIT: u32 = 100;
+
+/// Search activity shared by every clone of the server
+#[derive(Default)]
+struct SearchStats {
+    searches_performed: AtomicU64,
+    /// Unix milliseconds of the latest search, 0 before the first one
+    last_search_millis: AtomicI64,
+}
+
+impl SearchStats {
+    fn record(&self, at: DateTime<Utc>) {
+        self.searches_performed.fetch_add(1, Ordering::Relaxed);
+        self.last_search_millis
+            .store(at.timestamp_millis(), Ordering::Relaxed);
+    }
+
+    fn searches_performed(&self) -> u64 {
+        self.searches_performed.load(Ordering::Relaxed)
+    }
+
+    fn last_search_at(&self) -> Option<DateTime<Utc>> {
+        match self.last_search_millis.load(Ordering::Relaxed) {
+            0 => None,
+            millis => DateTime::from_timestamp_millis(millis),
+        }
+    }
+}
 
 pub struct McpServer<T: TelegramClientTrait, R: RateLimiterTrait> {
     telegram_client: Arc<T>,
     rate_limiter: Arc<R>,
+    channel_blocklist: ChannelBlocklist,
+    allow_writes: bool,
+    /// Whether link tools include tg:// links when the request doesn't say
+    default_include_tg: bool,
+    download_dir: PathBuf,
+    started_at: Instant,
+    search_stats: Arc<SearchStats>,
+    #[cfg(feature = "index")]
+    index: Option<Arc<MessageIndex>>,
+    tool_router: ToolRouter<Self>,
+}
+
+// Manual impl: clones share the client and limiter without requiring T: Clone
+impl<T: TelegramClientTrait, R: RateLimiterTrait> Clone for McpServer<T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            telegram_client: Arc::clone(&self.telegram_client),
+            rate_limiter: Arc::clone(&self.rate_limiter),
+            channel_blocklist: self.channel_blocklist.clone(),
+            allow_writes: self.allow_writes,
+            default_include_tg: self.default_include_tg,
+            download_dir: self.download_dir.clone(),
+            started_at: self.started_at,
+            search_stats: Arc::clone(&self.search_stats),
+            #[cfg(feature = "index")]
+            index: self.index.clone(),
+            tool_router: self.tool_router.clone(),
+        }
+    }
 }
 
 impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> McpServer<T, R> {
@@ -21,56 +147,414 @@ impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> McpServer<
         Self {
             telegram_client,
             rate_limiter,
+            channel_blocklist: ChannelBlocklist::default(),
+            allow_writes: false,
+            default_include_tg: true,
+            download_dir: std::env::temp_dir().join("telegram-connector"),
+            started_at: Instant::now(),
+            search_stats: Arc::default(),
+            #[cfg(feature = "index")]
+            index: None,
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    /// Exclude blocklisted channels from every tool result
+    pub fn with_channel_blocklist(mut self, channel_blocklist: ChannelBlocklist) -> Self {
+        self.channel_blocklist = channel_blocklist;
+        self
+    }
+
+    /// Enable tools that post to Telegram (`telegram.allow_writes`)
+    pub fn with_allow_writes(mut self, allow_writes: bool) -> Self {
+        self.allow_writes = allow_writes;
+        self
+    }
+
+    /// Default for `include_tg_protocol` in link tools (`link.default_include_tg_protocol`)
+    pub fn with_default_include_tg_protocol(mut self, include_tg: bool) -> Self {
+        self.default_include_tg = include_tg;
+        self
+    }
+
+    /// Save media fetched by `download_media` into `download_dir`
+    pub fn with_download_dir(mut self, download_dir: impl Into<PathBuf>) -> Self {
+        self.download_dir = download_dir.into();
+        self
+    }
+
+    /// Answer `search_messages` from a local index when it has matches
+    #[cfg(feature = "index")]
+    pub fn with_index(mut self, index: Arc<MessageIndex>) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Look up `params` in the local index; `None` means search live
+    ///
+    /// Index errors are logged and treated as a miss so searching keeps working.
+    #[cfg(feature = "index")]
+    fn search_index(&self, params: &SearchParams) -> Option<SearchResult> {
+        let index = self.index.as_ref()?;
+        match index.search(params) {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!("Local index search failed, searching live: {}", e);
+                None
+            }
+        }
+    }
+
+    #[cfg(not(feature = "index"))]
+    fn search_index(&self, _params: &SearchParams) -> Option<SearchResult> {
+        None
+    }
+
+    /// Reject a blocklisted channel, resolving its username when the blocklist
+    /// has username entries
+    async fn ensure_not_blocked(&self, channel_id: ChannelId) -> Result<(), Error> {
+        let mut blocked = self.channel_blocklist.is_blocked(channel_id, None);
+        if !blocked && self.channel_blocklist.has_usernames() {
+            let channel = self
+                .telegram_client
+                .get_channel_info(&channel_id.to_string())
+                .await?;
+            blocked = self
+                .channel_blocklist
+                .is_blocked(channel.id, channel.username.as_ref());
+        }
+
+        if blocked {
+            return Err(Error::InvalidInput("channel blocked".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Keep live results in the local index so repeated searches stay local
+    #[cfg(feature = "index")]
+    fn store_in_index(&self, result: &SearchResult) {
+        if let Some(index) = &self.index
+            && let Err(e) = index.insert(&result.messages)
+        {
+            tracing::warn!("Failed to store search results in local index: {}", e);
+        }
+    }
+
+    #[cfg(not(feature = "index"))]
+    fn store_in_index(&self, _result: &SearchResult) {}
+
+    /// Page a channel's history back to `since` into the local index
+    ///
+    /// Messages are stored with `INSERT OR REPLACE`, so re-running is safe and
+    /// only refreshes what is already indexed. Each page waits for rate limiter
+    /// tokens instead of failing. Returns the number of messages ingested.
+    #[cfg(feature = "index")]
+    pub async fn backfill_channel(
+        &self,
+        channel: ChannelId,
+        since: DateTime<Utc>,
+    ) -> Result<u64, Error> {
+        let index = self
+            .index
+            .as_ref()
+            .ok_or_else(|| Error::Config("local index is not enabled".to_string()))?;
+        self.ensure_not_blocked(channel).await?;
+
+        let mut ingested = 0u64;
+        let mut before_id = None;
+        loop {
+            self.rate_limiter.acquire_wait(HISTORY_TOKEN_COST).await?;
+            let page = self
+                .telegram_client
+                .get_channel_history(channel, MAX_HISTORY_LIMIT, before_id)
+                .await?;
+
+            let full_page = page.len() >= MAX_HISTORY_LIMIT as usize;
+            before_id = page
+                .iter()
+                .min_by_key(|message| message.id.get())
+                .map(|message| message.id);
+            let fresh: Vec<_> = page
+                .into_iter()
+                .filter(|message| message.timestamp >= since)
+                .collect();
+            let reached_since = fresh.len() < MAX_HISTORY_LIMIT as usize;
+
+            ingested += index.insert(&fresh)? as u64;
+
+            // Pages are newest first: stop once one reaches past `since` or runs short
+            if !full_page || reached_since || before_id.is_none() {
+                break;
+            }
         }
+
+        tracing::info!("Backfilled {} messages from channel {}", ingested, channel);
+        Ok(ingested)
     }
 
+    /// Serve MCP over stdio until the client disconnects or ctrl-c
     pub async fn run_stdio(self) -> anyhow::Result<()> {
         use tokio::io::{stdin, stdout};
 
         // Create stdio transport
         let transport = (stdin(), stdout());
 
-        // Start MCP server with stdio transport
-        let server = self.serve(transport).await?;
+        self.serve_until(transport, async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for ctrl-c: {}", e);
+            }
+        })
+        .await
+    }
+
+    /// Serve MCP on `transport` until it closes or `shutdown` completes, then save the session
+    async fn serve_until<Tr, E, A>(
+        self,
+        transport: Tr,
+        shutdown: impl Future<Output = ()>,
+    ) -> anyhow::Result<()>
+    where
+        Tr: IntoTransport<RoleServer, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let telegram_client = Arc::clone(&self.telegram_client);
+        let serve = async move {
+            let server = self.serve(transport).await?;
+            server.waiting().await?;
+            anyhow::Ok(())
+        };
 
-        // Wait for shutdown signal (blocks until server terminates)
-        server.waiting().await?;
+        // Dropping the running service on shutdown cancels in-flight requests
+        tokio::select! {
+            result = serve => result?,
+            () = shutdown => tracing::info!("Shutting down MCP stdio server"),
+        }
+
+        if let Err(e) = telegram_client.save_session().await {
+            tracing::warn!("Failed to save session on shutdown: {}", e);
+        }
+        tracing::info!("MCP server stopped");
 
         Ok(())
     }
 
-    // ========================================================================
-    // MCP Tools
-    // ========================================================================
+    /// Serve MCP over streamable HTTP (with SSE) at `http://{addr}/mcp` until ctrl-c
+    ///
+    /// Bind to `127.0.0.1` when running behind a reverse proxy; `0.0.0.0` exposes
+    /// the connector, and with it the Telegram account, to the whole network.
+    pub async fn run_http(self, addr: SocketAddr) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        tracing::info!(
+            "MCP HTTP server listening on http://{}{}",
+            listener.local_addr()?,
+            HTTP_MCP_PATH
+        );
+
+        self.serve_http(listener, async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for ctrl-c: {}", e);
+            }
+        })
+        .await
+    }
+
+    /// Serve MCP over HTTP on an already bound listener until `shutdown` completes
+    async fn serve_http(
+        self,
+        listener: tokio::net::TcpListener,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> anyhow::Result<()> {
+        let cancellation_token = CancellationToken::new();
+
+        // Each MCP session gets its own handle sharing the same client and limiter
+        let service = StreamableHttpService::new(
+            move || Ok(self.clone()),
+            Arc::new(LocalSessionManager::default()),
+            StreamableHttpServerConfig {
+                cancellation_token: cancellation_token.child_token(),
+                ..Default::default()
+            },
+        );
+        let router = axum::Router::new()
+            .nest_service(HTTP_MCP_PATH, service)
+            .layer(axum::middleware::map_response(json_error_body));
+
+        // Close open sessions so graceful shutdown isn't held up by SSE streams
+        axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                shutdown.await;
+                tracing::info!("Shutting down MCP HTTP server");
+                cancellation_token.cancel();
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Longest transport error text kept in a JSON error body
+const MAX_HTTP_ERROR_DETAIL_BYTES: usize = 4096;
+
+/// Give HTTP transport failures (bad JSON, wrong headers, unknown path) a JSON body
+///
+/// Non-JSON 4xx/5xx responses are rewritten to `{"error": ErrorResponse}` with the
+/// original text as the message; JSON-RPC errors and successful responses pass through.
+async fn json_error_body(response: axum::response::Response) -> axum::response::Response {
+    use axum::http::{HeaderValue, StatusCode, header};
+
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let detail = axum::body::to_bytes(body, MAX_HTTP_ERROR_DETAIL_BYTES)
+        .await
+        .ok()
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+        .filter(|detail| !detail.is_empty())
+        .unwrap_or_else(|| {
+            status
+                .canonical_reason()
+                .unwrap_or("request failed")
+                .to_string()
+        });
+    let error = match status {
+        StatusCode::NOT_FOUND => Error::NotFound(detail),
+        status if status.is_client_error() => Error::InvalidInput(detail),
+        _ => Error::Mcp(detail),
+    };
+
+    let body = serde_json::json!({ "error": ErrorResponse::from(error) }).to_string();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    axum::response::Response::from_parts(parts, axum::body::Body::from(body))
+}
 
+// ============================================================================
+// MCP Tools
+// ============================================================================
+
+#[tool_router]
+impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> McpServer<T, R> {
     /// Tool 1: check_mcp_status - Health check and diagnostics
-    pub async fn check_mcp_status(&self) -> Result<Json<StatusResponse>, String> {
+    #[tool(description = "Check Telegram connection status and available rate limiter tokens")]
+    pub async fn check_mcp_status(&self) -> Result<Json<StatusResponse>, ErrorResponse> {
         let connected = self.telegram_client.is_connected().await;
         let tokens = self.rate_limiter.available_tokens();
 
+        // Authorization can be cached, so confirm the network path with a live request
+        let telegram_ping_ms = if connected {
+            ping_latency_ms(self.telegram_client.ping(), PING_TIMEOUT).await
+        } else {
+            None
+        };
+
         Ok(Json(StatusResponse {
             telegram_connected: connected,
             rate_limiter_tokens: tokens,
             server_version: env!("CARGO_PKG_VERSION").to_string(),
+            rate_limiter: self.rate_limiter.metrics(),
+            uptime_seconds: self.started_at.elapsed().as_secs(),
+            last_search_at: self.search_stats.last_search_at(),
+            searches_performed: self.search_stats.searches_performed(),
+            telegram_ping_ms,
         }))
     }
 
     /// Tool 2: get_subscribed_channels - List user's Telegram channels with pagination
+    #[tool(
+        description = "List subscribed Telegram channels with pagination, optionally limited to one folder"
+    )]
     pub async fn get_subscribed_channels(
         &self,
-        request: GetChannelsRequest,
-    ) -> Result<Json<ChannelsResponse>, String> {
-        let limit = request.limit.unwrap_or(20);
+        Parameters(request): Parameters<GetChannelsRequest>,
+    ) -> Result<Json<ChannelsResponse>, ErrorResponse> {
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_CHANNELS_LIMIT)
+            .min(MAX_CHANNELS_LIMIT);
         let offset = request.offset.unwrap_or(0);
 
-        let channels = self
-            .telegram_client
-            .get_subscribed_channels(limit, offset)
-            .await
-            .map_err(|e| e.to_string())?;
+        if limit == 0 {
+            return Err(
+                Error::InvalidInput("Channel limit must be greater than 0".to_string()).into(),
+            );
+        }
+
+        let only_public = request.only_public.unwrap_or(false);
+        let only_verified = request.only_verified.unwrap_or(false);
+        let filtered = request.folder.is_some()
+            || request.name_filter.is_some()
+            || only_public
+            || only_verified
+            || !self.channel_blocklist.is_empty();
+
+        // Unfiltered listings page on Telegram's side; filters need every subscription
+        // first, so the page, `total` and `has_more` describe the matching channels
+        let (channels, total, has_more) = if filtered {
+            // Keep only channels from the requested folder
+            let folder = match &request.folder {
+                Some(folder_name) => Some(
+                    self.telegram_client
+                        .get_folders()
+                        .await?
+                        .into_iter()
+                        .find(|folder| &folder.folder_name == folder_name)
+                        .ok_or_else(|| Error::NotFound(format!("folder '{}'", folder_name)))?,
+                ),
+                None => None,
+            };
+
+            self.rate_limiter.acquire(LISTING_TOKEN_COST).await?;
+            let mut matching = self.telegram_client.get_all_subscribed_channels().await?;
+            matching.retain(|channel| {
+                folder
+                    .as_ref()
+                    .is_none_or(|folder| folder.contains(channel.id))
+                    && request
+                        .name_filter
+                        .as_ref()
+                        .is_none_or(|name_filter| channel.matches_name(name_filter))
+                    && (!only_public || channel.is_public)
+                    && (!only_verified || channel.is_verified)
+                    && !self
+                        .channel_blocklist
+                        .is_blocked(channel.id, channel.username.as_ref())
+            });
+
+            // Sort the whole match set so every page follows the same order
+            if let Some(sort_by) = request.sort_by {
+                sort_by.sort(&mut matching, request.descending.unwrap_or(false));
+            }
 
-        let total = channels.len();
-        let has_more = total >= limit as usize;
+            let total = matching.len();
+            let page: Vec<Channel> = matching
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect();
+            let has_more = (offset as usize).saturating_add(page.len()) < total;
+            (page, total, has_more)
+        } else {
+            let (mut channels, has_more) = self
+                .telegram_client
+                .get_subscribed_channels(limit, offset)
+                .await?;
+            if let Some(sort_by) = request.sort_by {
+                sort_by.sort(&mut channels, request.descending.unwrap_or(false));
+            }
+            let total = channels.len();
+            (channels, total, has_more)
+        };
 
         let response = ChannelsResponse {
             channels,
@@ -82,845 +566,5058 @@ impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> McpServer<
     }
 
     /// Tool 3: get_channel_info - Get detailed information about a Telegram channel
+    #[tool(description = "Get detailed information about a channel by @username or numeric ID")]
     pub async fn get_channel_info(
         &self,
-        request: GetChannelInfoRequest,
-    ) -> Result<Json<Channel>, String> {
+        Parameters(request): Parameters<GetChannelInfoRequest>,
+    ) -> Result<Json<Channel>, ErrorResponse> {
+        let identifier: ChannelIdentifier = request.channel_identifier.parse()?;
+
         let channel = self
             .telegram_client
-            .get_channel_info(&request.channel_identifier)
-            .await
-            .map_err(|e| e.to_string())?;
+            .get_channel_info(&identifier.to_string())
+            .await?;
+
+        if self
+            .channel_blocklist
+            .is_blocked(channel.id, channel.username.as_ref())
+        {
+            return Err(Error::InvalidInput("channel blocked".to_string()).into());
+        }
 
         Ok(Json(channel))
     }
 
     /// Tool 4: generate_message_link - Generate deep links for a Telegram message
+    #[tool(description = "Generate https:// and tg:// links for a message in a channel")]
     pub async fn generate_message_link(
         &self,
-        request: GenerateLinkRequest,
-    ) -> Result<Json<MessageLinkResponse>, String> {
-        // Parse channel_id string to i64
-        let channel_id_num: i64 = request.channel_id.parse().map_err(|_| {
-            format!(
-                "Invalid channel_id: '{}' is not a valid number",
-                request.channel_id
-            )
-        })?;
-
+        Parameters(request): Parameters<GenerateLinkRequest>,
+    ) -> Result<Json<MessageLinkResponse>, ErrorResponse> {
         // Create type-safe IDs
-        let channel_id =
-            ChannelId::new(channel_id_num).map_err(|e| format!("Invalid channel_id: {}", e))?;
-        let message_id =
-            MessageId::new(request.message_id).map_err(|e| format!("Invalid message_id: {}", e))?;
-
-        // Generate links using existing MessageLink from link.rs
-        let link = MessageLink::new(channel_id, message_id);
-
-        // Build response based on include_tg_protocol flag (defaults to true)
-        let include_tg = request.include_tg_protocol.unwrap_or(true);
-
-        Ok(Json(MessageLinkResponse {
-            channel_id: request.channel_id,
-            message_id: request.message_id,
-            https_link: link.https_link,
-            tg_protocol_link: if include_tg {
-                Some(link.tg_protocol_link)
-            } else {
-                None
-            },
-        }))
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+        let message_id = MessageId::new(request.message_id)
+            .map_err(|e| Error::InvalidInput(format!("Invalid message_id: {}", e)))?;
+
+        // Build response based on include_tg_protocol flag (defaults to link config)
+        let include_tg = request
+            .include_tg_protocol
+            .unwrap_or(self.default_include_tg);
+
+        Ok(Json(link_response(
+            &request.channel_id,
+            channel_id,
+            message_id,
+            include_tg,
+            request.single.unwrap_or(true),
+        )))
     }
 
     /// Tool 5: open_message_in_telegram - Open message in Telegram Desktop (macOS)
+    #[tool(description = "Open a message in the Telegram Desktop app (macOS only)")]
     pub async fn open_message_in_telegram(
         &self,
-        request: OpenMessageRequest,
-    ) -> Result<Json<OpenMessageResponse>, String> {
-        // Parse channel_id string to i64
-        let channel_id_num: i64 = request.channel_id.parse().map_err(|_| {
-            format!(
-                "Invalid channel_id: '{}' is not a valid number",
-                request.channel_id
-            )
-        })?;
-
+        Parameters(request): Parameters<OpenMessageRequest>,
+    ) -> Result<Json<OpenMessageResponse>, ErrorResponse> {
         // Create type-safe IDs
-        let channel_id =
-            ChannelId::new(channel_id_num).map_err(|e| format!("Invalid channel_id: {}", e))?;
-        let message_id =
-            MessageId::new(request.message_id).map_err(|e| format!("Invalid message_id: {}", e))?;
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+        let message_id = MessageId::new(request.message_id)
+            .map_err(|e| Error::InvalidInput(format!("Invalid message_id: {}", e)))?;
 
         // Generate links
-        let link = MessageLink::new(channel_id, message_id);
+        let link = MessageLink::new(channel_id, message_id, true);
 
         // Choose link type (defaults to tg:// protocol)
         let use_tg = request.use_tg_protocol.unwrap_or(true);
         let link_to_open = if use_tg {
-            &link.tg_protocol_link
+            link.tg_protocol_link
         } else {
-            &link.https_link
+            link.https_link
         };
 
-        // Execute open command (macOS-specific)
-        #[cfg(target_os = "macos")]
-        let result = tokio::process::Command::new("open")
-            .arg(link_to_open)
-            .output()
-            .await;
-
-        #[cfg(not(target_os = "macos"))]
-        let result: Result<std::process::Output, std::io::Error> = Err(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "open_message_in_telegram is only supported on macOS",
-        ));
-
-        match result {
-            Ok(output) => {
-                let success = output.status.success();
-                Ok(Json(OpenMessageResponse {
-                    success,
-                    message: if success {
-                        "Message opened in Telegram".to_string()
-                    } else {
-                        format!("Failed to open: {:?}", output.status)
-                    },
-                    link_used: link_to_open.clone(),
-                    app_opened: success,
-                }))
-            }
-            Err(e) => Ok(Json(OpenMessageResponse {
-                success: false,
-                message: format!("Failed to execute open command: {}", e),
-                link_used: link_to_open.clone(),
+        if request.dry_run.unwrap_or(false) {
+            return Ok(Json(OpenMessageResponse {
+                success: true,
+                message: "Dry run: link built, Telegram was not opened".to_string(),
+                link_used: link_to_open,
                 app_opened: false,
-            })),
+            }));
         }
+
+        Ok(Json(open_in_telegram(link_to_open).await))
     }
 
     /// Tool 6: search_messages - Search messages across Telegram channels
+    #[tool(description = "Search recent messages across subscribed channels or within one channel")]
     pub async fn search_messages(
         &self,
-        request: SearchRequest,
-    ) -> Result<Json<SearchResult>, String> {
-        // Validate query is not empty
-        if request.query.trim().is_empty() {
-            return Err("Search query cannot be empty".to_string());
-        }
-
+        Parameters(request): Parameters<SearchRequest>,
+    ) -> Result<Json<SearchResult>, ErrorResponse> {
         // Parse optional channel_id
-        let channel_id = match &request.channel_id {
+        let channel_id = request
+            .channel_id
+            .as_deref()
+            .map(|value| parse_channel_id("channel_id", value))
+            .transpose()?;
+
+        // Parse optional media type filter
+        let media_types = request
+            .media_types
+            .map(|types| {
+                types
+                    .iter()
+                    .map(|t| t.parse::<MediaType>())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        // Parse optional sender_id
+        let sender_id = match &request.sender_id {
             Some(id_str) => {
                 let id_num: i64 = id_str.parse().map_err(|_| {
-                    format!("Invalid channel_id: '{}' is not a valid number", id_str)
+                    Error::InvalidInput(format!(
+                        "Invalid sender_id: '{}' is not a valid number",
+                        id_str
+                    ))
                 })?;
-                Some(ChannelId::new(id_num).map_err(|e| format!("Invalid channel_id: {}", e))?)
+                Some(
+                    UserId::new(id_num)
+                        .map_err(|e| Error::InvalidInput(format!("Invalid sender_id: {}", e)))?,
+                )
             }
             None => None,
         };
 
-        // Apply defaults and limits
-        let hours_back = request
-            .hours_back
-            .unwrap_or(SearchParams::DEFAULT_HOURS_BACK)
-            .min(SearchParams::MAX_HOURS_BACK);
-
-        let limit = request
-            .limit
-            .unwrap_or(SearchParams::DEFAULT_LIMIT)
-            .min(SearchParams::MAX_LIMIT);
+        // Parse optional explicit time range
+        let after = request
+            .after
+            .as_deref()
+            .map(|value| parse_timestamp("after", value))
+            .transpose()?;
+        let before = request
+            .before
+            .as_deref()
+            .map(|value| parse_timestamp("before", value))
+            .transpose()?;
+
+        if let (Some(after), Some(before)) = (after, before)
+            && after > before
+        {
+            return Err(Error::InvalidInput(format!(
+                "'after' ({}) must not be later than 'before' ({})",
+                after.to_rfc3339(),
+                before.to_rfc3339()
+            ))
+            .into());
+        }
 
-        // Validate limit is greater than 0
-        if limit == 0 {
-            return Err("Search limit must be greater than 0".to_string());
+        let snippet_radius = request.snippet_radius.unwrap_or(DEFAULT_SNIPPET_RADIUS);
+        if snippet_radius > MAX_SNIPPET_RADIUS {
+            return Err(Error::InvalidInput(format!(
+                "snippet_radius must be at most {}, got {}",
+                MAX_SNIPPET_RADIUS, snippet_radius
+            ))
+            .into());
         }
 
-        // Acquire rate limiter tokens (1 token per search)
-        self.rate_limiter
-            .acquire(1)
-            .await
-            .map_err(|e| e.to_string())?;
+        let before_id = request
+            .before_id
+            .map(MessageId::new)
+            .transpose()
+            .map_err(|e| Error::InvalidInput(format!("Invalid before_id: {}", e)))?;
 
-        // Build search params
+        if let Some(id) = channel_id
+            && self.channel_blocklist.is_blocked(id, None)
+        {
+            return Err(Error::InvalidInput("channel blocked".to_string()).into());
+        }
+
+        // Build search params, applying defaults and limits
         let params = SearchParams {
             query: request.query,
+            queries: request.queries.unwrap_or_default(),
             channel_id,
-            hours_back,
-            limit,
+            hours_back: request
+                .hours_back
+                .unwrap_or(SearchParams::DEFAULT_HOURS_BACK),
+            limit: request.limit.unwrap_or(SearchParams::DEFAULT_LIMIT),
+            media_types,
+            sender_id,
+            after,
+            before,
+            match_mode: request.match_mode.unwrap_or_default(),
+            rank: request.rank.unwrap_or_default(),
+            min_views: request.min_views,
+            edited_only: request.edited_only.unwrap_or(false),
+            before_id,
+            blocklist: self.channel_blocklist.clone(),
+        }
+        .sanitized()?;
+
+        // Serve from the local index when it has matches, otherwise search live
+        let mut result = match self.search_index(&params) {
+            Some(result) => result,
+            None => {
+                // Acquire rate limiter tokens (1 token per search)
+                self.rate_limiter.acquire(SEARCH_TOKEN_COST).await?;
+
+                let mut result = self.telegram_client.search_messages(&params).await?;
+                self.search_stats.record(Utc::now());
+
+                // Drop blocklisted channels the client could not identify up front,
+                // so they never reach the index
+                let found = result.messages.len();
+                result.messages.retain(|message| {
+                    !self
+                        .channel_blocklist
+                        .is_blocked(message.channel_id, message.channel_username.as_ref())
+                });
+                let removed = (found - result.messages.len()) as u64;
+                result.total_found = result.total_found.saturating_sub(removed);
+
+                self.store_in_index(&result);
+                result
+            }
         };
 
-        // Execute search
-        let result = self
-            .telegram_client
-            .search_messages(&params)
-            .await
-            .map_err(|e| e.to_string())?;
+        // Highlight matches in the full text, before any snippet replaces it
+        if request.highlight.unwrap_or(false) {
+            for message in &mut result.messages {
+                let term = params.matched_term(&message.text).unwrap_or(&params.query);
+                message.highlighted_text = Some(message.highlight(term));
+            }
+        }
+
+        // Replace full text with a snippet around the match
+        if request.snippet.unwrap_or(false) {
+            for message in &mut result.messages {
+                let term = params.matched_term(&message.text).unwrap_or(&params.query);
+                message.apply_snippet(term, snippet_radius);
+            }
+        }
 
         Ok(Json(result))
     }
-}
 
-// Implement ServerHandler trait - tool registration will be added in Phase 11
-impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> ServerHandler
-    for McpServer<T, R>
-{
-    fn get_info(&self) -> InitializeResult {
-        InitializeResult {
-            protocol_version: ProtocolVersion::default(),
-            capabilities: Default::default(),
-            server_info: Implementation {
-                name: "telegram-mcp".to_string(),
-                version: env!("CARGO_PKG_VERSION").to_string(),
-                title: None,
-                icons: None,
-                website_url: None,
-            },
-            instructions: Some(
-                "Telegram MCP Connector - Search Russian Telegram channels".to_string(),
-            ),
-        }
+    /// Tool 7: get_channel_folders - List dialog folders with their channel IDs
+    #[tool(description = "List Telegram dialog folders with the IDs of the channels they contain")]
+    pub async fn get_channel_folders(&self) -> Result<Json<FoldersResponse>, ErrorResponse> {
+        let folders = self.telegram_client.get_folders().await?;
+
+        Ok(Json(FoldersResponse { folders }))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::rate_limiter::MockRateLimiterTrait;
-    use crate::telegram::client::MockTelegramClientTrait;
+    /// Tool 8: next_search_eta - Estimate when a search can run without being rate limited
+    #[tool(
+        description = "Estimate how many seconds until a search can run without being rate limited"
+    )]
+    pub async fn next_search_eta(&self) -> Result<Json<SearchEtaResponse>, ErrorResponse> {
+        let seconds = self.rate_limiter.time_until_available(SEARCH_TOKEN_COST);
 
-    #[test]
-    fn server_new_creates_instance_with_valid_dependencies() {
-        // Given: Mock client and rate limiter
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
+        Ok(Json(SearchEtaResponse {
+            available_now: seconds == 0,
+            seconds_until_available: seconds,
+        }))
+    }
 
-        let client_arc = Arc::new(mock_client);
-        let limiter_arc = Arc::new(mock_limiter);
+    /// Tool 9: forward_message - Forward a message to Saved Messages or another chat
+    #[tool(
+        description = "Forward a channel message to Saved Messages or another chat (requires allow_writes)"
+    )]
+    pub async fn forward_message(
+        &self,
+        Parameters(request): Parameters<ForwardMessageRequest>,
+    ) -> Result<Json<ForwardMessageResponse>, ErrorResponse> {
+        if !self.allow_writes {
+            return Err(Error::Config(
+                "forward_message is disabled; set telegram.allow_writes = true to enable it"
+                    .to_string(),
+            )
+            .into());
+        }
 
-        // When: Create new server
-        let server = McpServer::new(Arc::clone(&client_arc), Arc::clone(&limiter_arc));
+        // Create type-safe IDs
+        let from_channel = parse_channel_id("from_channel_id", &request.from_channel_id)?;
+        let message_id = MessageId::new(request.message_id)
+            .map_err(|e| Error::InvalidInput(format!("Invalid message_id: {}", e)))?;
+
+        let to = request.to.trim();
+        if to.is_empty() {
+            return Err(
+                Error::InvalidInput("Forward destination cannot be empty".to_string()).into(),
+            );
+        }
 
-        // Then: Server is created successfully
-        // Verify Arc refcounts increased (2 refs each: original + server)
-        assert_eq!(Arc::strong_count(&client_arc), 2);
-        assert_eq!(Arc::strong_count(&limiter_arc), 2);
+        self.ensure_not_blocked(from_channel).await?;
+        if let Ok(destination) = to.parse::<ChannelIdentifier>()
+            && self.channel_blocklist.is_blocked_identifier(&destination)
+        {
+            return Err(Error::InvalidInput("channel blocked".to_string()).into());
+        }
 
-        // Cleanup
-        drop(server);
-        assert_eq!(Arc::strong_count(&client_arc), 1);
-        assert_eq!(Arc::strong_count(&limiter_arc), 1);
+        self.rate_limiter.acquire(FORWARD_TOKEN_COST).await?;
+
+        let new_message_id = self
+            .telegram_client
+            .forward_message(from_channel, message_id, to)
+            .await?;
+
+        Ok(Json(ForwardMessageResponse {
+            success: true,
+            new_message_id: new_message_id.map(|id| id.get()),
+        }))
     }
 
-    #[test]
-    fn server_handler_provides_server_info() {
-        // Given: Server instance with mocks
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
+    /// Tool 10: get_channel_history - Read the latest messages of a channel with paging
+    #[tool(description = "Read the latest messages of a channel, paging back with before_id")]
+    pub async fn get_channel_history(
+        &self,
+        Parameters(request): Parameters<GetHistoryRequest>,
+    ) -> Result<Json<HistoryResponse>, ErrorResponse> {
+        // Create type-safe IDs
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+        let before_id = request
+            .before_id
+            .map(MessageId::new)
+            .transpose()
+            .map_err(|e| Error::InvalidInput(format!("Invalid before_id: {}", e)))?;
 
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+            .min(MAX_HISTORY_LIMIT);
 
-        // When: Get server info via ServerHandler trait
-        use rmcp::ServerHandler;
-        let result = server.get_info();
+        if limit == 0 {
+            return Err(
+                Error::InvalidInput("History limit must be greater than 0".to_string()).into(),
+            );
+        }
 
-        // Then: InitializeResult contains expected metadata
-        assert_eq!(result.protocol_version, ProtocolVersion::default());
-        assert_eq!(result.server_info.name, "telegram-mcp");
-        assert_eq!(result.server_info.version, env!("CARGO_PKG_VERSION"));
-        assert!(result.instructions.is_some());
-        assert!(
-            result
-                .instructions
-                .unwrap()
-                .contains("Telegram MCP Connector")
-        );
-    }
+        if self.channel_blocklist.is_blocked(channel_id, None) {
+            return Err(Error::InvalidInput("channel blocked".to_string()).into());
+        }
 
-    // Manual smoke test for run_stdio() will be done in Phase 12 integration testing
+        self.rate_limiter.acquire(HISTORY_TOKEN_COST).await?;
 
-    // ========================================================================
-    // Tool Tests
-    // ========================================================================
+        let mut messages = self
+            .telegram_client
+            .get_channel_history(channel_id, limit, before_id)
+            .await?;
+
+        // Username entries can only be matched once the channel's messages are known
+        if messages.iter().any(|message| {
+            self.channel_blocklist
+                .is_blocked(message.channel_id, message.channel_username.as_ref())
+        }) {
+            return Err(Error::InvalidInput("channel blocked".to_string()).into());
+        }
 
-    #[tokio::test]
-    async fn check_status_returns_connection_info() {
-        // Given: Server with mock client (connected) and rate limiter (tokens available)
-        let mut mock_client = MockTelegramClientTrait::new();
-        mock_client.expect_is_connected().return_once(|| true);
+        // A full page means older messages may remain
+        let next_before_id = if messages.len() >= limit as usize {
+            messages.iter().map(|message| message.id.get()).min()
+        } else {
+            None
+        };
 
-        let mut mock_limiter = MockRateLimiterTrait::new();
-        mock_limiter.expect_available_tokens().return_once(|| 45.5);
+        messages.sort_by_key(|message| message.timestamp);
 
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        Ok(Json(HistoryResponse {
+            messages,
+            next_before_id,
+        }))
+    }
+    /// Tool 11: send_message - Post a text message to a channel
+    #[tool(description = "Post a text message to a channel you administer (requires allow_writes)")]
+    pub async fn send_message(
+        &self,
+        Parameters(request): Parameters<SendMessageRequest>,
+    ) -> Result<Json<SendMessageResponse>, ErrorResponse> {
+        if !self.allow_writes {
+            return Err(Error::Config(
+                "send_message is disabled; set telegram.allow_writes = true to enable it"
+                    .to_string(),
+            )
+            .into());
+        }
 
-        // When: Call check_mcp_status
-        let result = server.check_mcp_status().await;
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
 
-        // Then: Returns success with connection info
+        if request.text.trim().is_empty() {
+            return Err(Error::InvalidInput("Message text cannot be empty".to_string()).into());
+        }
+        if request.text.chars().count() > MAX_MESSAGE_LENGTH {
+            return Err(Error::InvalidInput(format!(
+                "Message text exceeds {} characters",
+                MAX_MESSAGE_LENGTH
+            ))
+            .into());
+        }
+
+        let message_id = self
+            .telegram_client
+            .send_message(channel_id, &request.text)
+            .await?;
+
+        Ok(Json(SendMessageResponse {
+            message_id: message_id.get(),
+        }))
+    }
+
+    /// Tool 12: download_media - Save a message's media file locally
+    #[tool(description = "Download the photo, video or document attached to a channel message")]
+    pub async fn download_media(
+        &self,
+        Parameters(request): Parameters<DownloadMediaRequest>,
+    ) -> Result<Json<DownloadMediaResponse>, ErrorResponse> {
+        // Create type-safe IDs
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+        let message_id = MessageId::new(request.message_id)
+            .map_err(|e| Error::InvalidInput(format!("Invalid message_id: {}", e)))?;
+
+        tokio::fs::create_dir_all(&self.download_dir)
+            .await
+            .map_err(|e| {
+                Error::Config(format!(
+                    "Cannot create download directory {}: {}",
+                    self.download_dir.display(),
+                    e
+                ))
+            })?;
+
+        self.rate_limiter.acquire(DOWNLOAD_TOKEN_COST).await?;
+
+        let path = self
+            .telegram_client
+            .download_media(channel_id, message_id, &self.download_dir)
+            .await?;
+        let size_bytes = tokio::fs::metadata(&path)
+            .await
+            .map_err(|e| Error::NotFound(format!("downloaded file {}: {}", path.display(), e)))?
+            .len();
+
+        Ok(Json(DownloadMediaResponse {
+            path: path.display().to_string(),
+            size_bytes,
+        }))
+    }
+
+    /// Tool 13: generate_message_links - Generate deep links for many messages at once
+    #[tool(
+        description = "Generate https:// and tg:// links for several messages in a channel; invalid IDs are reported per item"
+    )]
+    pub async fn generate_message_links(
+        &self,
+        Parameters(request): Parameters<GenerateLinksRequest>,
+    ) -> Result<Json<GenerateLinksResponse>, ErrorResponse> {
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+
+        if request.message_ids.len() > MAX_LINKS_PER_REQUEST {
+            return Err(Error::InvalidInput(format!(
+                "Too many message_ids: {} (max {})",
+                request.message_ids.len(),
+                MAX_LINKS_PER_REQUEST
+            ))
+            .into());
+        }
+
+        let include_tg = request
+            .include_tg_protocol
+            .unwrap_or(self.default_include_tg);
+        let mut links = Vec::with_capacity(request.message_ids.len());
+        let mut errors = Vec::new();
+        for raw_id in request.message_ids {
+            match MessageId::new(raw_id) {
+                Ok(message_id) => links.push(link_response(
+                    &request.channel_id,
+                    channel_id,
+                    message_id,
+                    include_tg,
+                    true,
+                )),
+                Err(e) => errors.push(LinkError {
+                    message_id: raw_id,
+                    error: format!("Invalid message_id: {}", e),
+                }),
+            }
+        }
+
+        Ok(Json(GenerateLinksResponse { links, errors }))
+    }
+
+    /// Tool 14: open_link - Open an existing Telegram message link (macOS)
+    ///
+    /// Only message links that `MessageLink::parse` accepts are opened, and they are
+    /// re-rendered from the parsed IDs so no other URL reaches the system handler.
+    #[tool(
+        description = "Open a t.me or tg:// message link in the Telegram Desktop app (macOS only); other URLs are rejected"
+    )]
+    pub async fn open_link(
+        &self,
+        Parameters(request): Parameters<OpenLinkRequest>,
+    ) -> Result<Json<OpenMessageResponse>, ErrorResponse> {
+        let parsed = MessageLink::parse(&request.url)?;
+        let link = match &parsed.channel {
+            LinkChannel::Id(channel_id) => MessageLink::new(*channel_id, parsed.message_id, true),
+            LinkChannel::Username(username) => MessageLink::new_public(username, parsed.message_id),
+        };
+
+        // Keep the scheme the caller chose
+        let link_to_open = if request.url.trim().starts_with("tg://") {
+            link.tg_protocol_link
+        } else {
+            link.https_link
+        };
+
+        Ok(Json(open_in_telegram(link_to_open).await))
+    }
+
+    /// Tool 15: whoami - Show which account the session is signed in as
+    #[tool(
+        description = "Get the Telegram account (ID, username, display name) the session is signed in as"
+    )]
+    pub async fn whoami(&self) -> Result<Json<UserProfile>, ErrorResponse> {
+        let profile = self.telegram_client.get_me().await?;
+
+        Ok(Json(profile))
+    }
+
+    /// Tool 16: channel_stats - Count a channel's recent messages
+    #[tool(
+        description = "Count messages and media posts in a channel over the last hours_back hours, with first/last timestamps"
+    )]
+    pub async fn channel_stats(
+        &self,
+        Parameters(request): Parameters<ChannelStatsRequest>,
+    ) -> Result<Json<ChannelStats>, ErrorResponse> {
+        let channel_id = parse_channel_id("channel_id", &request.channel_id)?;
+        let hours_back = request
+            .hours_back
+            .unwrap_or(SearchParams::DEFAULT_HOURS_BACK)
+            .min(SearchParams::MAX_HOURS_BACK);
+
+        if hours_back == 0 {
+            return Err(
+                Error::InvalidInput("hours_back must be greater than 0".to_string()).into(),
+            );
+        }
+        self.ensure_not_blocked(channel_id).await?;
+
+        self.rate_limiter.acquire(STATS_TOKEN_COST).await?;
+
+        let stats = self
+            .telegram_client
+            .channel_stats(channel_id, hours_back)
+            .await?;
+
+        Ok(Json(stats))
+    }
+}
+
+/// Open a link with the system handler (macOS only)
+async fn open_in_telegram(link_to_open: String) -> OpenMessageResponse {
+    // Execute open command (macOS-specific)
+    #[cfg(target_os = "macos")]
+    let result = tokio::process::Command::new("open")
+        .arg(&link_to_open)
+        .output()
+        .await;
+
+    #[cfg(not(target_os = "macos"))]
+    let result: Result<std::process::Output, std::io::Error> = Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "opening Telegram links is only supported on macOS",
+    ));
+
+    match result {
+        Ok(output) => {
+            let success = output.status.success();
+            OpenMessageResponse {
+                success,
+                message: if success {
+                    "Message opened in Telegram".to_string()
+                } else {
+                    format!("Failed to open: {:?}", output.status)
+                },
+                link_used: link_to_open,
+                app_opened: success,
+            }
+        }
+        Err(e) => OpenMessageResponse {
+            success: false,
+            message: format!("Failed to execute open command: {}", e),
+            link_used: link_to_open,
+            app_opened: false,
+        },
+    }
+}
+
+/// Span for one tool call, named `tool_call`
+///
+/// Only the channel and the argument names are recorded: other argument values
+/// can hold message text. `elapsed_ms` and `outcome` are filled in when the call ends.
+fn tool_span(request: &CallToolRequestParam) -> tracing::Span {
+    let arguments = request.arguments.as_ref();
+    let channel = arguments
+        .and_then(|args| args.get("channel_id"))
+        .map(|value| match value {
+            serde_json::Value::String(channel) => channel.clone(),
+            other => other.to_string(),
+        });
+    let argument_names = arguments
+        .map(|args| args.keys().cloned().collect::<Vec<_>>().join(","))
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "tool_call",
+        tool = %request.name,
+        channel = channel.as_deref(),
+        arguments = %argument_names,
+        elapsed_ms = tracing::field::Empty,
+        outcome = tracing::field::Empty,
+    )
+}
+
+/// `ok`, `error` for a tool error result, or `rejected` when the call never ran
+fn tool_outcome(result: &Result<CallToolResult, ErrorData>) -> &'static str {
+    match result {
+        Ok(result) if result.is_error == Some(true) => "error",
+        Ok(_) => "ok",
+        Err(_) => "rejected",
+    }
+}
+
+/// Ping latency in milliseconds, or `None` if the ping fails or exceeds `timeout`
+async fn ping_latency_ms(
+    ping: impl Future<Output = Result<Duration, Error>>,
+    timeout: Duration,
+) -> Option<u64> {
+    match tokio::time::timeout(timeout, ping).await {
+        Ok(Ok(latency)) => Some(latency.as_millis() as u64),
+        Ok(Err(e)) => {
+            tracing::warn!("Telegram ping failed: {}", e);
+            None
+        }
+        Err(_) => {
+            tracing::warn!("Telegram ping timed out after {:?}", timeout);
+            None
+        }
+    }
+}
+
+/// Link response for one message, optionally without the tg:// link
+fn link_response(
+    raw_channel_id: &str,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    include_tg: bool,
+    single: bool,
+) -> MessageLinkResponse {
+    let link = MessageLink::new(channel_id, message_id, single);
+
+    MessageLinkResponse {
+        channel_id: raw_channel_id.to_string(),
+        message_id: message_id.get(),
+        https_link: link.https_link,
+        tg_protocol_link: include_tg.then_some(link.tg_protocol_link),
+    }
+}
+
+/// Parse a numeric channel ID from a request field
+///
+/// Accepts the same numeric forms as `ChannelIdentifier`; usernames, non-numeric and
+/// non-channel values get distinct messages, all `INVALID_INPUT`.
+fn parse_channel_id(field: &str, value: &str) -> Result<ChannelId, Error> {
+    match value.parse::<ChannelIdentifier>() {
+        Ok(ChannelIdentifier::Id(id)) => Ok(id),
+        Ok(ChannelIdentifier::Username(_)) => Err(Error::InvalidInput(format!(
+            "Invalid {}: '{}' is a username, expected a numeric channel ID",
+            field, value
+        ))),
+        Err(_) if value.trim().parse::<i64>().is_ok() => Err(Error::InvalidInput(format!(
+            "Invalid {}: '{}' must be a positive or -100 prefixed channel ID",
+            field, value
+        ))),
+        Err(_) => Err(Error::InvalidInput(format!(
+            "Invalid {}: '{}' is not a valid number",
+            field, value
+        ))),
+    }
+}
+
+/// Parse an RFC3339 timestamp from a request field
+fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>, Error> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| {
+            Error::InvalidInput(format!(
+                "Invalid {}: '{}' is not an RFC3339 timestamp ({})",
+                field, value, e
+            ))
+        })
+}
+
+/// Tool errors are returned as JSON content so clients can branch on the code
+impl IntoContents for ErrorResponse {
+    fn into_contents(self) -> Vec<Content> {
+        match Content::json(&self) {
+            Ok(content) => vec![content],
+            Err(_) => vec![Content::text(self.to_string())],
+        }
+    }
+}
+
+// Tool dispatch is written out instead of using #[tool_handler] so each call gets a span
+impl<T: TelegramClientTrait + 'static, R: RateLimiterTrait + 'static> ServerHandler
+    for McpServer<T, R>
+{
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let span = tool_span(&request);
+        let start = Instant::now();
+
+        let tcc = ToolCallContext::new(self, request, context);
+        let result = self.tool_router.call(tcc).instrument(span.clone()).await;
+
+        span.record("elapsed_ms", start.elapsed().as_millis() as u64);
+        span.record("outcome", tool_outcome(&result));
+        span.in_scope(|| tracing::info!("Tool call finished"));
+
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult {
+            tools: self.tool_router.list_all(),
+            meta: None,
+            next_cursor: None,
+        })
+    }
+
+    fn get_info(&self) -> InitializeResult {
+        InitializeResult {
+            protocol_version: ProtocolVersion::default(),
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation {
+                name: "telegram-mcp".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                title: None,
+                icons: None,
+                website_url: None,
+            },
+            instructions: Some(
+                "Telegram MCP Connector - Search Russian Telegram channels".to_string(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limiter::{MockRateLimiterTrait, RateLimiterMetrics};
+    use crate::telegram::client::MockTelegramClientTrait;
+
+    /// Test channel `Channel <id>` (`@channel_<id>`): public, unverified, no messages
+    fn test_channel(id: i64) -> TestChannel {
+        use crate::telegram::types::Username;
+        use crate::telegram::{ChannelId, ChannelName};
+
+        TestChannel(Channel {
+            id: ChannelId::new(id).unwrap(),
+            name: ChannelName::new(format!("Channel {}", id)).unwrap(),
+            username: Some(Username::new(format!("channel_{}", id)).unwrap()),
+            description: None,
+            member_count: 1000,
+            is_verified: false,
+            is_public: true,
+            is_subscribed: true,
+            last_message_date: None,
+        })
+    }
+
+    /// Builder returned by `test_channel`
+    struct TestChannel(Channel);
+
+    impl TestChannel {
+        fn name(mut self, name: &str) -> Self {
+            self.0.name = crate::telegram::ChannelName::new(name).unwrap();
+            self
+        }
+
+        fn username(mut self, username: &str) -> Self {
+            self.0.username = Some(crate::telegram::types::Username::new(username).unwrap());
+            self
+        }
+
+        fn public(mut self, is_public: bool) -> Self {
+            self.0.is_public = is_public;
+            self
+        }
+
+        fn verified(mut self, is_verified: bool) -> Self {
+            self.0.is_verified = is_verified;
+            self
+        }
+
+        fn member_count(mut self, member_count: u64) -> Self {
+            self.0.member_count = member_count;
+            self
+        }
+
+        fn last_message_hours_ago(mut self, hours: i64) -> Self {
+            self.0.last_message_date = Some(Utc::now() - chrono::Duration::hours(hours));
+            self
+        }
+
+        fn build(self) -> Channel {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn server_lists_registered_tools_over_mcp() {
+        // Given: Server and client connected through an in-memory transport
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let server_handle = tokio::spawn(async move {
+            let running = server.serve(server_transport).await.unwrap();
+            running.waiting().await.unwrap();
+        });
+        let client = ().serve(client_transport).await.unwrap();
+
+        // When: Client lists the tools after initialization
+        let tools = client.list_all_tools().await.unwrap();
+
+        // Then: Every tool is advertised with an object input schema
+        let mut names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            vec![
+                "channel_stats",
+                "check_mcp_status",
+                "download_media",
+                "forward_message",
+                "generate_message_link",
+                "generate_message_links",
+                "get_channel_folders",
+                "get_channel_history",
+                "get_channel_info",
+                "get_subscribed_channels",
+                "next_search_eta",
+                "open_link",
+                "open_message_in_telegram",
+                "search_messages",
+                "send_message",
+                "whoami",
+            ]
+        );
+        for tool in &tools {
+            assert_eq!(
+                tool.input_schema.get("type").and_then(|t| t.as_str()),
+                Some("object")
+            );
+        }
+
+        client.cancel().await.unwrap();
+        server_handle.await.unwrap();
+    }
+
+    /// Log sink collecting formatted output in memory
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn tool_calls_emit_span_with_tool_name_and_outcome() {
+        use crate::telegram::types::QueryMetadata;
+
+        // Given: JSON logs captured in memory and a server over an in-memory transport
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().returning(|_| {
+            Ok(SearchResult {
+                messages: vec![],
+                total_found: 0,
+                search_time_ms: 5,
+                query_metadata: QueryMetadata {
+                    query: "secret plans".to_string(),
+                    hours_back: 48,
+                    channels_searched: 1,
+                },
+            })
+        });
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let server_handle = tokio::spawn(async move {
+            let running = server.serve(server_transport).await.unwrap();
+            running.waiting().await.unwrap();
+        });
+        let client = ().serve(client_transport).await.unwrap();
+
+        // When: Client calls search_messages
+        let arguments = serde_json::json!({"query": "secret plans", "channel_id": "12345"});
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "search_messages".into(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await
+            .unwrap();
+        client.cancel().await.unwrap();
+        server_handle.await.unwrap();
+
+        // Then: A tool_call span records the tool, channel and outcome, not the query text
+        assert_ne!(result.is_error, Some(true));
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let finished = output
+            .lines()
+            .find(|line| line.contains("Tool call finished"))
+            .expect("no tool call event logged");
+        let event: serde_json::Value = serde_json::from_str(finished).unwrap();
+        let span = &event["span"];
+        assert_eq!(span["name"], "tool_call");
+        assert_eq!(span["tool"], "search_messages");
+        assert_eq!(span["channel"], "12345");
+        assert_eq!(span["outcome"], "ok");
+        assert!(span.get("elapsed_ms").is_some());
+        assert!(!output.contains("secret plans"));
+    }
+
+    #[tokio::test]
+    async fn serve_until_stops_on_shutdown_and_saves_session() {
+        // Given: Server with an initialized client session over an in-memory transport
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_save_session()
+            .times(1)
+            .returning(|| Ok(()));
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let shutdown = CancellationToken::new();
+        let server_handle =
+            tokio::spawn(server.serve_until(server_transport, shutdown.clone().cancelled_owned()));
+        let client = ().serve(client_transport).await.unwrap();
+
+        // When: The shutdown token fires while the client is still connected
+        shutdown.cancel();
+
+        // Then: The server returns cleanly after saving the session
+        tokio::time::timeout(std::time::Duration::from_secs(5), server_handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        drop(client);
+    }
+
+    #[tokio::test]
+    async fn serve_until_saves_session_when_client_disconnects() {
+        // Given: Server whose session save fails
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_save_session()
+            .times(1)
+            .returning(|| Err(Error::Auth("read-only file system".to_string())));
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let (server_transport, client_transport) = tokio::io::duplex(4096);
+        let server_handle =
+            tokio::spawn(server.serve_until(server_transport, std::future::pending::<()>()));
+        let client = ().serve(client_transport).await.unwrap();
+
+        // When: The client goes away
+        client.cancel().await.unwrap();
+
+        // Then: The server still stops without an error
+        tokio::time::timeout(std::time::Duration::from_secs(5), server_handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn serve_http_answers_initialize_and_shuts_down() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Given: Server listening on an ephemeral local port
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_handle = tokio::spawn(server.serve_http(listener, async {
+            let _ = shutdown_rx.await;
+        }));
+
+        // When: A client posts an initialize request
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26","capabilities":{},"clientInfo":{"name":"test","version":"0.1.0"}}}"#;
+        let request = format!(
+            "POST /mcp HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+             Accept: application/json, text/event-stream\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        let mut buf = [0u8; 4096];
+        while !response.contains("telegram-mcp") {
+            let read =
+                tokio::time::timeout(std::time::Duration::from_secs(5), stream.read(&mut buf))
+                    .await
+                    .unwrap()
+                    .unwrap();
+            assert!(read > 0, "connection closed early: {response}");
+            response.push_str(&String::from_utf8_lossy(&buf[..read]));
+        }
+        drop(stream);
+
+        // Then: The server reports its info and stops on shutdown
+        assert!(response.starts_with("HTTP/1.1 200"));
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), server_handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn serve_http_returns_json_error_for_malformed_body() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Given: Server listening on an ephemeral local port
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_handle = tokio::spawn(server.serve_http(listener, async {
+            let _ = shutdown_rx.await;
+        }));
+
+        // When: A client posts a body that is not JSON
+        let body = "{not json";
+        let request = format!(
+            "POST /mcp HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\n\
+             Accept: application/json, text/event-stream\r\nConnection: close\r\n\
+             Content-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            stream.read_to_string(&mut response),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // Then: A 4xx with an ErrorResponse JSON body
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(
+            head.starts_with("HTTP/1.1 4"),
+            "unexpected response: {head}"
+        );
+        assert!(
+            head.to_lowercase()
+                .contains("content-type: application/json")
+        );
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["error"]["code"], "INVALID_INPUT");
+        assert!(json["error"]["message"].is_string());
+
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), server_handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn json_error_body_keeps_json_and_success_responses() {
+        use axum::http::{StatusCode, header};
+        use axum::response::IntoResponse;
+
+        // Given: A JSON-RPC error and a plain-text success
+        let json_error = (
+            StatusCode::BAD_REQUEST,
+            [(header::CONTENT_TYPE, "application/json")],
+            r#"{"jsonrpc":"2.0"}"#,
+        )
+            .into_response();
+        let success = (StatusCode::OK, "ok").into_response();
+
+        // When: Passed through the middleware
+        let json_error = json_error_body(json_error).await;
+        let success = json_error_body(success).await;
+
+        // Then: Both are left untouched
+        let body = axum::body::to_bytes(json_error.into_body(), 1024)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], br#"{"jsonrpc":"2.0"}"#);
+        let body = axum::body::to_bytes(success.into_body(), 1024)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"ok");
+    }
+
+    #[tokio::test]
+    async fn search_messages_tool_schema_includes_query() {
+        // Given: The generated tool attributes
+        let tool =
+            McpServer::<MockTelegramClientTrait, MockRateLimiterTrait>::search_messages_tool_attr();
+
+        // Then: The request schema from schemars is exposed
+        let properties = tool.input_schema.get("properties").unwrap();
+        assert!(properties.get("query").is_some());
+        assert!(tool.description.is_some());
+    }
+
+    #[test]
+    fn error_response_converts_to_json_content() {
+        // Given: A rate limit error
+        let response = ErrorResponse::from(Error::RateLimit {
+            retry_after_seconds: 7,
+        });
+
+        // When: Converted into MCP content
+        let contents = response.into_contents();
+
+        // Then: A single content item carries the code
+        assert_eq!(contents.len(), 1);
+        let text = contents[0].as_text().unwrap();
+        assert!(text.text.contains("RATE_LIMIT"));
+    }
+
+    #[test]
+    fn server_new_creates_instance_with_valid_dependencies() {
+        // Given: Mock client and rate limiter
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+
+        let client_arc = Arc::new(mock_client);
+        let limiter_arc = Arc::new(mock_limiter);
+
+        // When: Create new server
+        let server = McpServer::new(Arc::clone(&client_arc), Arc::clone(&limiter_arc));
+
+        // Then: Server is created successfully
+        // Verify Arc refcounts increased (2 refs each: original + server)
+        assert_eq!(Arc::strong_count(&client_arc), 2);
+        assert_eq!(Arc::strong_count(&limiter_arc), 2);
+
+        // Cleanup
+        drop(server);
+        assert_eq!(Arc::strong_count(&client_arc), 1);
+        assert_eq!(Arc::strong_count(&limiter_arc), 1);
+    }
+
+    #[test]
+    fn server_handler_provides_server_info() {
+        // Given: Server instance with mocks
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Get server info via ServerHandler trait
+        use rmcp::ServerHandler;
+        let result = server.get_info();
+
+        // Then: InitializeResult contains expected metadata
+        assert_eq!(result.protocol_version, ProtocolVersion::default());
+        assert_eq!(result.server_info.name, "telegram-mcp");
+        assert_eq!(result.server_info.version, env!("CARGO_PKG_VERSION"));
+        assert!(result.instructions.is_some());
+        assert!(
+            result
+                .instructions
+                .unwrap()
+                .contains("Telegram MCP Connector")
+        );
+    }
+
+    // Manual smoke test for run_stdio() will be done in Phase 12 integration testing
+
+    // ========================================================================
+    // Tool Tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn check_status_returns_connection_info() {
+        // Given: Server with mock client (connected) and rate limiter (tokens available)
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_is_connected().return_once(|| true);
+        mock_client
+            .expect_ping()
+            .return_once(|| Ok(std::time::Duration::from_millis(35)));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_available_tokens().return_once(|| 45.5);
+        mock_limiter
+            .expect_metrics()
+            .return_once(|| RateLimiterMetrics {
+                total_acquired: 12,
+                total_denied: 3,
+                total_tokens_granted: 12,
+            });
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call check_mcp_status
+        let result = server.check_mcp_status().await;
+
+        // Then: Returns success with connection info
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert!(response.telegram_connected);
+        assert_eq!(response.rate_limiter_tokens, 45.5);
+        assert_eq!(response.server_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(response.rate_limiter.total_acquired, 12);
+        assert_eq!(response.rate_limiter.total_denied, 3);
+        assert_eq!(response.rate_limiter.total_tokens_granted, 12);
+        assert_eq!(response.telegram_ping_ms, Some(35));
+    }
+
+    #[tokio::test]
+    async fn check_status_reports_failed_ping() {
+        // Given: Client that is authorized but cannot reach Telegram
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_is_connected().return_once(|| true);
+        mock_client
+            .expect_ping()
+            .return_once(|| Err(Error::Network("connection reset".to_string())));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_available_tokens().return_once(|| 50.0);
+        mock_limiter
+            .expect_metrics()
+            .return_once(RateLimiterMetrics::default);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call check_mcp_status
+        let result = server.check_mcp_status().await;
+
+        // Then: Status still succeeds, with no ping time
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert!(response.telegram_connected);
+        assert!(response.telegram_ping_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn ping_latency_gives_up_after_timeout() {
+        // Given: A ping that never completes
+        let ping = std::future::pending::<Result<Duration, Error>>();
+
+        // When: Measure it with a short timeout
+        let latency = ping_latency_ms(ping, Duration::from_millis(10)).await;
+
+        // Then: No ping time is reported
+        assert!(latency.is_none());
+    }
+
+    #[tokio::test]
+    async fn ping_latency_reports_completed_ping() {
+        // Given: A ping that completes immediately
+        let ping = async { Ok(Duration::from_millis(35)) };
+
+        // When: Measure it
+        let latency = ping_latency_ms(ping, PING_TIMEOUT).await;
+
+        // Then: Its latency is reported in milliseconds
+        assert_eq!(latency, Some(35));
+    }
+
+    #[tokio::test]
+    async fn check_status_reports_search_activity() {
+        use crate::telegram::types::{QueryMetadata, SearchResult};
+
+        // Given: Server whose client returns an empty search result
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_is_connected().returning(|| true);
+        mock_client
+            .expect_ping()
+            .returning(|| Ok(std::time::Duration::from_millis(10)));
+        mock_client.expect_search_messages().returning(|_| {
+            Ok(SearchResult {
+                messages: vec![],
+                total_found: 0,
+                search_time_ms: 5,
+                query_metadata: QueryMetadata {
+                    query: "AI".to_string(),
+                    hours_back: 48,
+                    channels_searched: 1,
+                },
+            })
+        });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        mock_limiter.expect_available_tokens().returning(|| 49.0);
+        mock_limiter
+            .expect_metrics()
+            .returning(RateLimiterMetrics::default);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let before = server.check_mcp_status().await.unwrap().0;
+
+        // When: Perform a search
+        let request = SearchRequest {
+            query: "AI".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        server.search_messages(Parameters(request)).await.unwrap();
+
+        // Then: The counter increments and the search time is recorded
+        let after = server.check_mcp_status().await.unwrap().0;
+        assert_eq!(before.searches_performed, 0);
+        assert!(before.last_search_at.is_none());
+        assert_eq!(after.searches_performed, 1);
+        assert!(after.last_search_at.is_some());
+        assert!(after.uptime_seconds >= before.uptime_seconds);
+    }
+
+    #[tokio::test]
+    async fn check_status_reports_disconnected() {
+        // Given: Server with disconnected client
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_is_connected().return_once(|| false);
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_available_tokens().return_once(|| 0.0);
+        mock_limiter
+            .expect_metrics()
+            .return_once(RateLimiterMetrics::default);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call check_mcp_status
+        let result = server.check_mcp_status().await;
+
+        // Then: Returns disconnected status
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert!(!response.telegram_connected);
+        assert_eq!(response.rate_limiter_tokens, 0.0);
+        assert!(response.telegram_ping_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_returns_list() {
+        // Given: Mock client returning test channels
+        let mut mock_client = MockTelegramClientTrait::new();
+        let test_channels = vec![
+            test_channel(123).name("Channel 1").build(),
+            test_channel(456).name("Channel 2").build(),
+        ];
+        let expected = test_channels.clone();
+
+        mock_client
+            .expect_get_subscribed_channels()
+            .with(
+                mockall::predicate::eq(20), // default limit
+                mockall::predicate::eq(0),  // default offset
+            )
+            .return_once(move |_, _| Ok((expected, false)));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call get_subscribed_channels with defaults
+        let request = GetChannelsRequest {
+            limit: None,
+            offset: None,
+            folder: None,
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Returns success with channel list
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.channels.len(), 2);
+        assert_eq!(response.total, 2);
+        assert!(!response.has_more); // client reports no further pages
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_respects_pagination() {
+        // Given: Mock client with custom pagination parameters
+        let mut mock_client = MockTelegramClientTrait::new();
+        let test_channels = vec![test_channel(789).name("Channel 3").build()];
+        let expected = test_channels.clone();
+
+        mock_client
+            .expect_get_subscribed_channels()
+            .with(
+                mockall::predicate::eq(10), // custom limit
+                mockall::predicate::eq(5),  // custom offset
+            )
+            .return_once(move |_, _| Ok((expected, false)));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call with custom pagination
+        let request = GetChannelsRequest {
+            limit: Some(10),
+            offset: Some(5),
+            folder: None,
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Returns success with correct pagination values
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.channels.len(), 1);
+        assert_eq!(response.total, 1);
+        assert!(!response.has_more); // client reports no further pages
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_caps_limit() {
+        // Given: Client expecting the capped limit
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_subscribed_channels()
+            .with(
+                mockall::predicate::eq(MAX_CHANNELS_LIMIT),
+                mockall::predicate::eq(0),
+            )
+            .times(1)
+            .return_once(|_, _| Ok((vec![], false)));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request far more than the maximum
+        let request = GetChannelsRequest {
+            limit: Some(u32::MAX),
+            offset: None,
+            folder: None,
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: The client only sees the capped limit
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_zero_limit_fails() {
+        // Given: A client that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_subscribed_channels().never();
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request an empty page
+        let request = GetChannelsRequest {
+            limit: Some(0),
+            offset: None,
+            folder: None,
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Returns INVALID_INPUT instead of an empty page with has_more
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("greater than 0"));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_exact_boundary_has_more_from_client() {
+        // Given: Final page holding exactly `limit` channels, then a full page with more after it
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_subscribed_channels()
+            .with(mockall::predicate::eq(2), mockall::predicate::eq(2))
+            .return_once(|_, _| {
+                Ok((
+                    vec![test_channel(3).build(), test_channel(4).build()],
+                    false,
+                ))
+            });
+        mock_client
+            .expect_get_subscribed_channels()
+            .with(mockall::predicate::eq(2), mockall::predicate::eq(0))
+            .return_once(|_, _| Ok((vec![test_channel(1).build(), test_channel(2).build()], true)));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Fetch both pages
+        let first = server
+            .get_subscribed_channels(Parameters(GetChannelsRequest {
+                limit: Some(2),
+                offset: Some(0),
+                folder: None,
+                name_filter: None,
+                sort_by: None,
+                descending: None,
+                only_public: None,
+                only_verified: None,
+            }))
+            .await
+            .unwrap()
+            .0;
+        let last = server
+            .get_subscribed_channels(Parameters(GetChannelsRequest {
+                limit: Some(2),
+                offset: Some(2),
+                folder: None,
+                name_filter: None,
+                sort_by: None,
+                descending: None,
+                only_public: None,
+                only_verified: None,
+            }))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Only the first page reports more, even though both are full
+        assert_eq!(first.channels.len(), 2);
+        assert!(first.has_more);
+        assert_eq!(last.channels.len(), 2);
+        assert!(!last.has_more);
+    }
+
+    #[tokio::test]
+    async fn get_channel_info_returns_channel_details() {
+        use crate::telegram::types::Username;
+        use crate::telegram::{Channel, ChannelId, ChannelName};
+
+        // Given: Mock client returning channel details
+        let mut mock_client = MockTelegramClientTrait::new();
+        let test_channel = Channel {
+            id: ChannelId::new(12345).unwrap(),
+            name: ChannelName::new("Test Channel").unwrap(),
+            username: Some(Username::new("testchannel").unwrap()),
+            description: Some("A test channel".to_string()),
+            member_count: 5000,
+            is_verified: true,
+            is_public: true,
+            is_subscribed: false,
+            last_message_date: None,
+        };
+        let expected = test_channel.clone();
+
+        mock_client
+            .expect_get_channel_info()
+            .with(mockall::predicate::eq("@testchannel"))
+            .return_once(move |_| Ok(expected));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call get_channel_info
+        let request = GetChannelInfoRequest {
+            channel_identifier: "testchannel".to_string(),
+        };
+
+        let result = server.get_channel_info(Parameters(request)).await;
+
+        // Then: Returns channel details
+        assert!(result.is_ok());
+        let channel = result.unwrap().0;
+        assert_eq!(channel.id, ChannelId::new(12345).unwrap());
+        assert_eq!(channel.name.as_str(), "Test Channel");
+        assert!(channel.is_verified);
+        assert_eq!(channel.member_count, 5000);
+    }
+
+    #[tokio::test]
+    async fn get_channel_info_handles_error() {
+        use crate::error::Error;
+
+        // Given: Mock client returning error
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_info()
+            .with(mockall::predicate::eq("@nonexistent"))
+            .return_once(move |_| Err(Error::TelegramApi("Channel not found".to_string())));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call get_channel_info with nonexistent channel
+        let request = GetChannelInfoRequest {
+            channel_identifier: "nonexistent".to_string(),
+        };
+
+        let result = server.get_channel_info(Parameters(request)).await;
+
+        // Then: Returns error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Channel not found"));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_channel_info_normalizes_supergroup_id() {
+        use crate::telegram::types::Username;
+        use crate::telegram::{Channel, ChannelId, ChannelName};
+
+        // Given: Mock client expecting the plain channel ID
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_info()
+            .with(mockall::predicate::eq("1234567890"))
+            .return_once(|_| {
+                Ok(Channel {
+                    id: ChannelId::new(1234567890).unwrap(),
+                    name: ChannelName::new("Test Channel").unwrap(),
+                    username: Some(Username::new("testchannel").unwrap()),
+                    description: None,
+                    member_count: 5000,
+                    is_verified: false,
+                    is_public: true,
+                    is_subscribed: true,
+                    last_message_date: None,
+                })
+            });
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call get_channel_info with the -100 prefixed form
+        let request = GetChannelInfoRequest {
+            channel_identifier: "-1001234567890".to_string(),
+        };
+        let result = server.get_channel_info(Parameters(request)).await;
+
+        // Then: The client receives the canonical ID
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().0.id.get(), 1234567890);
+    }
+
+    #[tokio::test]
+    async fn get_channel_info_rejects_invalid_identifier() {
+        // Given: Mock client that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_channel_info().times(0);
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call get_channel_info with an identifier that is neither a username nor an ID
+        let request = GetChannelInfoRequest {
+            channel_identifier: "not a channel".to_string(),
+        };
+        let result = server.get_channel_info(Parameters(request)).await;
+
+        // Then: Returns INVALID_INPUT
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("Invalid channel identifier"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 4: generate_message_link
+    // ========================================================================
+
+    #[tokio::test]
+    async fn generate_message_link_returns_both_formats() {
+        // Given: Server and valid request
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinkRequest {
+            channel_id: "123456789".to_string(),
+            message_id: 42,
+            include_tg_protocol: None, // defaults to true
+            single: None,
+        };
+
+        // When: Generate link
+        let result = server.generate_message_link(Parameters(request)).await;
+
+        // Then: Returns both link formats
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.channel_id, "123456789");
+        assert_eq!(response.message_id, 42);
+        assert_eq!(response.https_link, "https://t.me/c/123456789/42?single");
+        assert!(response.tg_protocol_link.is_some());
+        assert_eq!(
+            response.tg_protocol_link.unwrap(),
+            "tg://resolve?channel=123456789&post=42&single"
+        );
+    }
+
+    #[tokio::test]
+    async fn generate_message_link_without_tg_protocol() {
+        // Given: Server and request with include_tg_protocol = false
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinkRequest {
+            channel_id: "999".to_string(),
+            message_id: 111,
+            include_tg_protocol: Some(false),
+            single: None,
+        };
+
+        // When: Generate link
+        let result = server.generate_message_link(Parameters(request)).await;
+
+        // Then: Returns only HTTPS link (tg_protocol_link is None)
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.https_link, "https://t.me/c/999/111?single");
+        assert!(response.tg_protocol_link.is_none());
+    }
+
+    #[tokio::test]
+    async fn generate_message_link_honors_configured_default() {
+        // Given: Server configured for HTTPS-only links
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_default_include_tg_protocol(false);
+
+        // When: Generate links without include_tg_protocol, and with it set explicitly
+        let omitted = GenerateLinkRequest {
+            channel_id: "999".to_string(),
+            message_id: 111,
+            include_tg_protocol: None,
+            single: None,
+        };
+        let explicit = GenerateLinkRequest {
+            include_tg_protocol: Some(true),
+            ..omitted.clone()
+        };
+        let omitted = server
+            .generate_message_link(Parameters(omitted))
+            .await
+            .unwrap()
+            .0;
+        let explicit = server
+            .generate_message_link(Parameters(explicit))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: The config default applies only when the request omits the flag
+        assert!(omitted.tg_protocol_link.is_none());
+        assert!(explicit.tg_protocol_link.is_some());
+    }
+
+    #[tokio::test]
+    async fn generate_message_link_without_single_links_album() {
+        // Given: Server and request with single = false
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinkRequest {
+            channel_id: "999".to_string(),
+            message_id: 111,
+            include_tg_protocol: None,
+            single: Some(false),
+        };
+
+        // When: Generate link
+        let response = server
+            .generate_message_link(Parameters(request))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Neither link carries the single flag
+        assert_eq!(response.https_link, "https://t.me/c/999/111");
+        assert_eq!(
+            response.tg_protocol_link.as_deref(),
+            Some("tg://resolve?channel=999&post=111")
+        );
+    }
+
+    #[tokio::test]
+    async fn generate_message_link_invalid_channel_id() {
+        // Given: Server and request with non-numeric channel_id
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinkRequest {
+            channel_id: "not_a_number".to_string(),
+            message_id: 42,
+            include_tg_protocol: None,
+            single: None,
+        };
+
+        // When: Generate link
+        let result = server.generate_message_link(Parameters(request)).await;
+
+        // Then: Returns error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Invalid channel_id"));
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_message_link_distinguishes_channel_id_errors() {
+        // Given: Server
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        for (channel_id, expected) in [
+            ("abc", "'abc' is not a valid number"),
+            ("@rust_news", "'@rust_news' is a username"),
+            ("-5", "'-5' must be a positive or -100 prefixed channel ID"),
+            ("0", "'0' must be a positive or -100 prefixed channel ID"),
+        ] {
+            // When: Generate a link with a bad channel_id
+            let request = GenerateLinkRequest {
+                channel_id: channel_id.to_string(),
+                message_id: 42,
+                include_tg_protocol: None,
+                single: None,
+            };
+            let result = server.generate_message_link(Parameters(request)).await;
+
+            // Then: Both failure modes share a code but explain the problem
+            assert!(result.is_err());
+            if let Err(error_msg) = result {
+                assert_eq!(error_msg.code, "INVALID_INPUT");
+                assert!(
+                    error_msg.message.contains(expected),
+                    "unexpected message: {}",
+                    error_msg.message
+                );
+            }
+        }
+    }
+
+    // ========================================================================
+    // Tool 13: generate_message_links
+    // ========================================================================
+
+    #[tokio::test]
+    async fn generate_message_links_reports_invalid_ids_per_item() {
+        // Given: Server and a mix of valid and invalid message IDs
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinksRequest {
+            channel_id: "123456789".to_string(),
+            message_ids: vec![42, 0, 43, -7],
+            include_tg_protocol: None,
+        };
+
+        // When: Generate links
+        let result = server.generate_message_links(Parameters(request)).await;
+
+        // Then: Valid IDs get links in order, invalid ones are listed as errors
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        let linked: Vec<i64> = response.links.iter().map(|link| link.message_id).collect();
+        assert_eq!(linked, vec![42, 43]);
+        assert_eq!(
+            response.links[0].https_link,
+            "https://t.me/c/123456789/42?single"
+        );
+        assert!(response.links[1].tg_protocol_link.is_some());
+        let rejected: Vec<i64> = response.errors.iter().map(|e| e.message_id).collect();
+        assert_eq!(rejected, vec![0, -7]);
+        assert!(response.errors[0].error.contains("Invalid message_id"));
+    }
+
+    #[tokio::test]
+    async fn generate_message_links_without_tg_protocol() {
+        // Given: Server and request with include_tg_protocol = false
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinksRequest {
+            channel_id: "999".to_string(),
+            message_ids: vec![1, 2],
+            include_tg_protocol: Some(false),
+        };
+
+        // When: Generate links
+        let result = server.generate_message_links(Parameters(request)).await;
+
+        // Then: Only HTTPS links are returned
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.links.len(), 2);
+        assert!(
+            response
+                .links
+                .iter()
+                .all(|link| link.tg_protocol_link.is_none())
+        );
+        assert!(response.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn generate_message_links_invalid_channel_id_fails_batch() {
+        // Given: Server and request with non-numeric channel_id
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinksRequest {
+            channel_id: "not_a_number".to_string(),
+            message_ids: vec![1, 2],
+            include_tg_protocol: None,
+        };
+
+        // When: Generate links
+        let result = server.generate_message_links(Parameters(request)).await;
+
+        // Then: Returns error because every link needs the channel
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Invalid channel_id"));
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_message_links_rejects_oversized_batch() {
+        // Given: Server and more message IDs than allowed
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = GenerateLinksRequest {
+            channel_id: "999".to_string(),
+            message_ids: (1..=101).collect(),
+            include_tg_protocol: None,
+        };
+
+        // When: Generate links
+        let result = server.generate_message_links(Parameters(request)).await;
+
+        // Then: Returns error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Too many message_ids"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 5: open_message_in_telegram
+    // ========================================================================
+
+    #[tokio::test]
+    async fn open_message_in_telegram_invalid_channel_id() {
+        // Given: Server and request with non-numeric channel_id
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = OpenMessageRequest {
+            channel_id: "invalid".to_string(),
+            message_id: 42,
+            use_tg_protocol: None,
+            dry_run: None,
+        };
+
+        // When: Try to open message
+        let result = server.open_message_in_telegram(Parameters(request)).await;
+
+        // Then: Returns error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Invalid channel_id"));
+        }
+    }
+
+    #[tokio::test]
+    async fn open_message_in_telegram_uses_tg_protocol_by_default() {
+        // Given: Server and request without use_tg_protocol specified
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = OpenMessageRequest {
+            channel_id: "123456".to_string(),
+            message_id: 42,
+            use_tg_protocol: None, // defaults to true
+            dry_run: None,
+        };
+
+        // When: Open message
+        let result = server.open_message_in_telegram(Parameters(request)).await;
+
+        // Then: Returns response with tg:// link
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert!(response.link_used.starts_with("tg://"));
+    }
+
+    #[tokio::test]
+    async fn open_message_in_telegram_uses_https_when_requested() {
+        // Given: Server and request with use_tg_protocol = false
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = OpenMessageRequest {
+            channel_id: "123456".to_string(),
+            message_id: 42,
+            use_tg_protocol: Some(false),
+            dry_run: None,
+        };
+
+        // When: Open message
+        let result = server.open_message_in_telegram(Parameters(request)).await;
+
+        // Then: Returns response with https:// link
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert!(response.link_used.starts_with("https://"));
+    }
+
+    #[tokio::test]
+    async fn open_message_in_telegram_dry_run_does_not_open_app() {
+        // Given: Server and a dry-run request
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = OpenMessageRequest {
+            channel_id: "123456".to_string(),
+            message_id: 42,
+            use_tg_protocol: None,
+            dry_run: Some(true),
+        };
+
+        // When: Open message
+        let response = server
+            .open_message_in_telegram(Parameters(request))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Link is returned, no open command ran (it would fail off macOS)
+        assert!(response.success);
+        assert!(!response.app_opened);
+        assert_eq!(
+            response.link_used,
+            "tg://resolve?channel=123456&post=42&single"
+        );
+        assert!(response.message.contains("Dry run"));
+    }
+
+    // ========================================================================
+    // Tool 14: open_link
+    // ========================================================================
+
+    #[tokio::test]
+    async fn open_link_accepts_telegram_links() {
+        // Given: Server and several valid Telegram message links
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let cases = [
+            (
+                "https://t.me/c/123456/42?single",
+                "https://t.me/c/123456/42?single",
+            ),
+            ("https://t.me/rust_news/7", "https://t.me/rust_news/7"),
+            (
+                "tg://resolve?channel=123456&post=42",
+                "tg://resolve?channel=123456&post=42&single",
+            ),
+            (
+                "tg://resolve?domain=rust_news&post=7",
+                "tg://resolve?domain=rust_news&post=7",
+            ),
+        ];
+
+        for (url, expected) in cases {
+            // When: Open the link
+            let request = OpenLinkRequest {
+                url: url.to_string(),
+            };
+            let result = server.open_link(Parameters(request)).await;
+
+            // Then: The canonical form of the link is opened
+            assert!(result.is_ok(), "{url} should be accepted");
+            let response = result.unwrap().0;
+            assert_eq!(response.link_used, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn open_link_rejects_other_urls() {
+        // Given: Server and URLs that are not Telegram message links
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        for url in [
+            "https://example.com/c/123/42",
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "tg://msg?text=hello",
+            "https://t.me/rust_news",
+            "",
+        ] {
+            // When: Open the URL
+            let request = OpenLinkRequest {
+                url: url.to_string(),
+            };
+            let result = server.open_link(Parameters(request)).await;
+
+            // Then: Returns INVALID_INPUT without opening anything
+            assert!(result.is_err(), "{url} should be rejected");
+            if let Err(error_msg) = result {
+                assert_eq!(error_msg.code, "INVALID_INPUT");
+            }
+        }
+    }
+
+    // ========================================================================
+    // Tool 15: whoami
+    // ========================================================================
+
+    #[tokio::test]
+    async fn whoami_returns_current_account() {
+        use crate::telegram::Username;
+
+        // Given: Mock client signed in as a user with a public username
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_me().times(1).returning(|| {
+            Ok(UserProfile {
+                id: UserId::new(777).unwrap(),
+                username: Some(Username::new("research_bot").unwrap()),
+                display_name: "Research Account".to_string(),
+            })
+        });
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call whoami
+        let result = server.whoami().await;
+
+        // Then: Returns the account profile
+        assert!(result.is_ok());
+        let profile = result.unwrap().0;
+        assert_eq!(profile.id.get(), 777);
+        assert_eq!(profile.username.unwrap().as_str(), "research_bot");
+        assert_eq!(profile.display_name, "Research Account");
+    }
+
+    #[tokio::test]
+    async fn whoami_propagates_client_error() {
+        // Given: Mock client whose session is no longer authorized
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_me()
+            .returning(|| Err(Error::Auth("Session expired".to_string())));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Call whoami
+        let result = server.whoami().await;
+
+        // Then: Returns the auth error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Session expired"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 16: channel_stats
+    // ========================================================================
+
+    #[tokio::test]
+    async fn channel_stats_forwards_aggregates() {
+        let last_seen = Utc::now();
+        let first_seen = last_seen - chrono::Duration::hours(20);
+
+        // Given: Client with 42 posts in the window, 5 of them with media
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_channel_stats()
+            .with(
+                mockall::predicate::eq(ChannelId::new(123).unwrap()),
+                mockall::predicate::eq(24),
+            )
+            .times(1)
+            .return_once(move |_, _| {
+                Ok(ChannelStats {
+                    message_count: 42,
+                    media_count: 5,
+                    first_seen: Some(first_seen),
+                    last_seen: Some(last_seen),
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_acquire()
+            .with(mockall::predicate::eq(STATS_TOKEN_COST))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request stats for the last 24 hours
+        let request = ChannelStatsRequest {
+            channel_id: "123".to_string(),
+            hours_back: Some(24),
+        };
+        let result = server.channel_stats(Parameters(request)).await;
+
+        // Then: The aggregates are returned unchanged
+        assert!(result.is_ok());
+        let stats = result.unwrap().0;
+        assert_eq!(stats.message_count, 42);
+        assert_eq!(stats.media_count, 5);
+        assert_eq!(stats.first_seen, Some(first_seen));
+        assert_eq!(stats.last_seen, Some(last_seen));
+    }
+
+    #[tokio::test]
+    async fn channel_stats_applies_default_and_max_hours_back() {
+        // Given: Client recording the requested window
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_channel_stats()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(SearchParams::DEFAULT_HOURS_BACK),
+            )
+            .times(1)
+            .returning(|_, _| Ok(ChannelStats::default()));
+        mock_client
+            .expect_channel_stats()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(SearchParams::MAX_HOURS_BACK),
+            )
+            .times(1)
+            .returning(|_, _| Ok(ChannelStats::default()));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(2).returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        for hours_back in [None, Some(1000)] {
+            // When: Request stats without a window and with an oversized one
+            let request = ChannelStatsRequest {
+                channel_id: "123".to_string(),
+                hours_back,
+            };
+            let result = server.channel_stats(Parameters(request)).await;
+
+            // Then: Succeeds with an empty window
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().0.message_count, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_stats_rejects_invalid_input_without_a_token() {
+        // Given: Client and limiter that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_channel_stats().times(0);
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["999".to_string()]));
+
+        for (channel_id, hours_back) in [("abc", None), ("123", Some(0)), ("999", None)] {
+            // When: Request stats with a bad ID, an empty window or a blocked channel
+            let request = ChannelStatsRequest {
+                channel_id: channel_id.to_string(),
+                hours_back,
+            };
+            let result = server.channel_stats(Parameters(request)).await;
+
+            // Then: Returns INVALID_INPUT
+            assert!(result.is_err());
+            if let Err(error_msg) = result {
+                assert_eq!(error_msg.code, "INVALID_INPUT");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_stats_rejects_channel_blocked_by_username() {
+        // Given: Channel 555 resolves to a username on the blocklist
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_info()
+            .with(mockall::predicate::eq("555"))
+            .return_once(|_| Ok(test_channel(555).username("SpamChannel").build()));
+        mock_client.expect_channel_stats().times(0);
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["@spamchannel".to_string()]));
+
+        // When: Request its stats by ID
+        let request = ChannelStatsRequest {
+            channel_id: "555".to_string(),
+            hours_back: None,
+        };
+        let result = server.channel_stats(Parameters(request)).await;
+
+        // Then: Rejected before taking a token
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("channel blocked"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 6: search_messages
+    // ========================================================================
+
+    #[tokio::test]
+    async fn search_messages_returns_results() {
+        use crate::telegram::types::{Message, QueryMetadata, SearchResult, Username};
+        use crate::telegram::{ChannelId, ChannelName};
+
+        // Given: Mock client returning search results
+        let mut mock_client = MockTelegramClientTrait::new();
+        let expected_result = SearchResult {
+            messages: vec![Message {
+                id: MessageId::new(1).unwrap(),
+                channel_id: ChannelId::new(123).unwrap(),
+                channel_name: ChannelName::new("Test Channel").unwrap(),
+                channel_username: Some(Username::new("testchannel").unwrap()),
+                text: "Test message about AI".to_string(),
+                timestamp: chrono::Utc::now(),
+                sender_id: None,
+                sender_name: None,
+                has_media: false,
+                media_type: crate::telegram::types::MediaType::None,
+                original_length: None,
+                score: None,
+                highlighted_text: None,
+                detected_language: None,
+                forward_from_channel: None,
+                forward_from_name: None,
+                views: None,
+                forwards: None,
+                reactions: None,
+                reply_to: None,
+                edit_date: None,
+            }],
+            total_found: 1,
+            search_time_ms: 100,
+            query_metadata: QueryMetadata {
+                query: "AI".to_string(),
+                hours_back: 48,
+                channels_searched: 1,
+            },
+        };
+        let expected = expected_result.clone();
+
+        mock_client
+            .expect_search_messages()
+            .returning(move |_| Ok(expected.clone()));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search messages
+        let request = SearchRequest {
+            query: "AI".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns search results
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.total_found, 1);
+        assert_eq!(response.messages.len(), 1);
+        assert!(response.messages[0].text.contains("AI"));
+    }
+
+    #[tokio::test]
+    async fn search_messages_empty_query_fails() {
+        // Given: Server and empty query
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = SearchRequest {
+            query: "   ".to_string(), // whitespace only
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        // When: Search messages
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns invalid input error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("cannot be empty"));
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_rejects_oversized_snippet_radius() {
+        // Given: Server whose client must not be reached
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().times(0);
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = SearchRequest {
+            query: "rust".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: Some(true),
+            snippet_radius: Some(usize::MAX),
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        // When: Search with a radius above the maximum
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Rejected as invalid input
+        let error = result.err().unwrap();
+        assert_eq!(error.code, "INVALID_INPUT");
+        assert!(
+            error
+                .message
+                .contains("snippet_radius must be at most 1000")
+        );
+    }
+
+    #[tokio::test]
+    async fn search_messages_rate_limited() {
+        use crate::error::Error;
+
+        // Given: Rate limiter that denies request
+        let mock_client = MockTelegramClientTrait::new();
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| {
+            Err(Error::RateLimit {
+                retry_after_seconds: 5,
+            })
+        });
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let request = SearchRequest {
+            query: "test".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        // When: Search messages
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns rate limit error with retry hint
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "RATE_LIMIT");
+            assert_eq!(error_msg.retry_after_seconds, Some(5));
+            assert!(error_msg.message.contains("rate limit"));
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_with_channel_filter() {
+        use crate::telegram::types::{QueryMetadata, SearchResult};
+
+        // Given: Mock client with channel filter
+        let mut mock_client = MockTelegramClientTrait::new();
+        let expected_result = SearchResult {
+            messages: vec![],
+            total_found: 0,
+            search_time_ms: 50,
+            query_metadata: QueryMetadata {
+                query: "test".to_string(),
+                hours_back: 24,
+                channels_searched: 1,
+            },
+        };
+        let expected = expected_result.clone();
+
+        mock_client
+            .expect_search_messages()
+            .returning(move |params| {
+                // Verify channel_id is passed correctly
+                assert!(params.channel_id.is_some());
+                assert_eq!(params.channel_id.unwrap().get(), 999);
+                Ok(expected.clone())
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with channel filter
+        let request = SearchRequest {
+            query: "test".to_string(),
+            channel_id: Some("999".to_string()),
+            hours_back: Some(24),
+            limit: Some(50),
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Success
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_applies_limits() {
+        use crate::telegram::types::{QueryMetadata, SearchResult};
+
+        // Given: Mock client that verifies params
+        let mut mock_client = MockTelegramClientTrait::new();
+        let expected_result = SearchResult {
+            messages: vec![],
+            total_found: 0,
+            search_time_ms: 50,
+            query_metadata: QueryMetadata {
+                query: "test".to_string(),
+                hours_back: 72, // should be capped to MAX_HOURS_BACK
+                channels_searched: 0,
+            },
+        };
+        let expected = expected_result.clone();
+
+        mock_client
+            .expect_search_messages()
+            .returning(move |params| {
+                // Verify limits are applied
+                assert_eq!(params.hours_back, 72); // MAX_HOURS_BACK
+                assert_eq!(params.limit, 100); // MAX_LIMIT
+                Ok(expected.clone())
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with values exceeding limits
+        let request = SearchRequest {
+            query: "test".to_string(),
+            channel_id: None,
+            hours_back: Some(1000), // exceeds MAX_HOURS_BACK (72)
+            limit: Some(500),       // exceeds MAX_LIMIT (100)
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Success (limits applied internally)
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_media_type_filter() {
+        // Given: Client expecting a document-only search
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.media_types == Some(vec![MediaType::Document, MediaType::Photo]))
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with media type names in mixed case
+        let request = SearchRequest {
+            query: "report".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: Some(vec!["document".to_string(), "Photo".to_string()]),
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Filter reaches the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_rank_mode() {
+        use crate::telegram::RankMode;
+
+        // Given: Client expecting a relevance-ranked search
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.rank == RankMode::Relevance)
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search ranked by relevance
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: Some(RankMode::Relevance),
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Rank mode reaches the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_min_views() {
+        // Given: Client expecting a view-count filter
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.min_views == Some(1000))
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search for widely seen posts only
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: Some(1000),
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: View threshold reaches the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_queries() {
+        // Given: Client expecting every query term
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.terms() == vec!["bitcoin", "ethereum"])
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search for any of several keywords without a primary query
+        let request = SearchRequest {
+            query: String::new(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: Some(vec!["bitcoin".to_string(), "ethereum".to_string()]),
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: All terms reach the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_rejects_empty_query_and_queries() {
+        // Given: Client that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .times(0)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with a blank query and only blank queries
+        let request = SearchRequest {
+            query: "  ".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: Some(vec![String::new()]),
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns error before any search runs
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Search query cannot be empty"));
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_before_id_cursor() {
+        // Given: Client expecting a paging cursor within one channel
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.before_id == Some(MessageId::new(50).unwrap()))
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Fetch the page older than message 50
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: Some("100".to_string()),
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: Some(50),
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Cursor reaches the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_rejects_cursor_without_channel() {
+        // Given: Client that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .times(0)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Page across all channels
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: Some(50),
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns error because message IDs are per channel
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("before_id requires channel_id"));
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_threads_sender_filter() {
+        // Given: Client expecting a sender-scoped search
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| params.sender_id == Some(UserId::new(424242).unwrap()))
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search scoped to one sender
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: Some("424242".to_string()),
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Sender filter reaches the client
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_invalid_sender_id_fails() {
+        // Given: Server
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with a non-positive sender ID
+        let request = SearchRequest {
+            query: "announcement".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: Some("-5".to_string()),
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("Invalid sender_id"));
+        }
+    }
+
+    #[test]
+    fn parse_timestamp_accepts_rfc3339() {
+        let timestamp = parse_timestamp("after", "2025-01-01T03:00:00+03:00").unwrap();
+        assert_eq!(timestamp.to_rfc3339(), "2025-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn parse_timestamp_rejects_bad_input() {
+        for value in ["", "2025-01-01", "yesterday", "2025-13-01T00:00:00Z"] {
+            let result = parse_timestamp("after", value);
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                value
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_explicit_range_is_not_clamped() {
+        // Given: Client expecting the full week range
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| {
+                let (start, end) = params.time_range(Utc::now());
+                start.to_rfc3339() == "2025-01-01T00:00:00+00:00"
+                    && end.to_rfc3339() == "2025-01-07T23:59:59+00:00"
+            })
+            .times(1)
+            .returning(|params| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 1,
+                    query_metadata: crate::telegram::QueryMetadata {
+                        query: params.query.clone(),
+                        hours_back: params.hours_back,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search a range longer than MAX_HOURS_BACK
+        let request = SearchRequest {
+            query: "report".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: Some("2025-01-01T00:00:00Z".to_string()),
+            before: Some("2025-01-07T23:59:59Z".to_string()),
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Range reaches the client unchanged
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_messages_after_later_than_before_fails() {
+        // Given: Server
+        let mock_client = MockTelegramClientTrait::new();
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Range is inverted
+        let request = SearchRequest {
+            query: "report".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: Some("2025-01-07T00:00:00Z".to_string()),
+            before: Some("2025-01-01T00:00:00Z".to_string()),
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns invalid input error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("must not be later than"));
+        }
+    }
+
+    #[tokio::test]
+    async fn search_messages_invalid_media_type_fails() {
+        // Given: Client and limiter that must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().never();
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().never();
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Search with an unknown media type
+        let request = SearchRequest {
+            query: "report".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: Some(vec!["pdf".to_string()]),
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Returns invalid input error naming the value
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("invalid input"));
+            assert!(error_msg.message.contains("pdf"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 7: get_channel_folders
+    // ========================================================================
+
+    #[tokio::test]
+    async fn get_channel_folders_returns_folders() {
+        use crate::telegram::{ChannelFolder, ChannelId};
+
+        // Given: Mock client returning two folders
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_folders().return_once(|| {
+            Ok(vec![
+                ChannelFolder {
+                    folder_name: "News".to_string(),
+                    channel_ids: vec![ChannelId::new(123).unwrap()],
+                },
+                ChannelFolder {
+                    folder_name: "Crypto".to_string(),
+                    channel_ids: vec![ChannelId::new(456).unwrap(), ChannelId::new(789).unwrap()],
+                },
+            ])
+        });
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: List folders
+        let result = server.get_channel_folders().await;
+
+        // Then: Returns all folders with their channel IDs
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        assert_eq!(response.folders.len(), 2);
+        assert_eq!(response.folders[0].folder_name, "News");
+        assert_eq!(response.folders[1].channel_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_filters_by_folder() {
+        use crate::telegram::{ChannelFolder, ChannelId};
+
+        // Given: Three channels, two of which are in the "Crypto" folder
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .return_once(|| {
+                Ok(vec![
+                    test_channel(123).name("Channel 1").build(),
+                    test_channel(456).name("Channel 2").build(),
+                    test_channel(789).name("Channel 3").build(),
+                ])
+            });
+        mock_client.expect_get_folders().return_once(|| {
+            Ok(vec![ChannelFolder {
+                folder_name: "Crypto".to_string(),
+                channel_ids: vec![ChannelId::new(456).unwrap(), ChannelId::new(789).unwrap()],
+            }])
+        });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request only the "Crypto" folder
+        let request = GetChannelsRequest {
+            limit: None,
+            offset: None,
+            folder: Some("Crypto".to_string()),
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Only folder channels are returned
         assert!(result.is_ok());
         let response = result.unwrap().0;
-        assert!(response.telegram_connected);
-        assert_eq!(response.rate_limiter_tokens, 45.5);
-        assert_eq!(response.server_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(response.total, 2);
+        assert_eq!(response.channels[0].id, ChannelId::new(456).unwrap());
+        assert_eq!(response.channels[1].id, ChannelId::new(789).unwrap());
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_filters_by_name_case_insensitively() {
+        // Given: Channels with Latin and Cyrillic names
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .times(3)
+            .returning(|| {
+                Ok(vec![
+                    test_channel(1)
+                        .name("Crypto Daily")
+                        .username("crypto_daily")
+                        .build(),
+                    test_channel(2)
+                        .name("Криптовалюты")
+                        .username("coins_ru")
+                        .build(),
+                    test_channel(3)
+                        .name("Rust News")
+                        .username("rust_news")
+                        .build(),
+                ])
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        for (name_filter, expected) in
+            [("CRYPTO", vec![1]), ("КРИПТО", vec![2]), ("_news", vec![3])]
+        {
+            // When: Filter channels by part of their name or username
+            let request = GetChannelsRequest {
+                limit: None,
+                offset: None,
+                folder: None,
+                name_filter: Some(name_filter.to_string()),
+                sort_by: None,
+                descending: None,
+                only_public: None,
+                only_verified: None,
+            };
+            let result = server.get_subscribed_channels(Parameters(request)).await;
+
+            // Then: Only matching channels are returned
+            assert!(result.is_ok());
+            let response = result.unwrap().0;
+            let ids: Vec<i64> = response.channels.iter().map(|c| c.id.get()).collect();
+            assert_eq!(ids, expected, "filter {name_filter}");
+            assert_eq!(response.total, expected.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_filters_before_paging() {
+        // Given: 150 subscriptions fetched in one pass, every tenth about crypto
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_subscribed_channels().never();
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .times(1)
+            .returning(|| {
+                Ok((0..150)
+                    .map(|i| {
+                        let name = if i % 10 == 0 { "Crypto" } else { "Other" };
+                        test_channel(i + 1).name(name).build()
+                    })
+                    .collect())
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_acquire()
+            .with(mockall::predicate::eq(LISTING_TOKEN_COST))
+            .times(1)
+            .returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request the second page of two matches
+        let request = GetChannelsRequest {
+            limit: Some(2),
+            offset: Some(2),
+            folder: None,
+            name_filter: Some("crypto".to_string()),
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+        let response = server
+            .get_subscribed_channels(Parameters(request))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Paging and totals describe the 15 matching channels
+        let ids: Vec<i64> = response.channels.iter().map(|c| c.id.get()).collect();
+        assert_eq!(ids, vec![21, 31]);
+        assert_eq!(response.total, 15);
+        assert!(response.has_more);
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_filters_public_and_verified() {
+        // Given: Channels with every public/verified combination
+        let mut mock_client = MockTelegramClientTrait::new();
+        let channels = vec![
+            test_channel(1).verified(true).build(),
+            test_channel(2).build(),
+            test_channel(3).public(false).verified(true).build(),
+            test_channel(4).public(false).build(),
+        ];
+        let unfiltered = channels.clone();
+        mock_client
+            .expect_get_subscribed_channels()
+            .times(1)
+            .return_once(move |_, _| Ok((unfiltered, false)));
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .times(3)
+            .returning(move || Ok(channels.clone()));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        for (only_public, only_verified, expected) in [
+            (None, None, vec![1, 2, 3, 4]),
+            (Some(true), None, vec![1, 2]),
+            (None, Some(true), vec![1, 3]),
+            (Some(true), Some(true), vec![1]),
+        ] {
+            // When: Request channels with the public/verified flags
+            let request = GetChannelsRequest {
+                limit: None,
+                offset: None,
+                folder: None,
+                name_filter: None,
+                sort_by: None,
+                descending: None,
+                only_public,
+                only_verified,
+            };
+            let result = server.get_subscribed_channels(Parameters(request)).await;
+
+            // Then: Only channels with the requested flags are returned
+            assert!(result.is_ok());
+            let response = result.unwrap().0;
+            let ids: Vec<i64> = response.channels.iter().map(|c| c.id.get()).collect();
+            assert_eq!(
+                ids, expected,
+                "only_public={only_public:?} only_verified={only_verified:?}"
+            );
+            assert_eq!(response.total, expected.len());
+        }
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_sorts_most_active_first() {
+        use crate::telegram::ChannelSort;
+
+        // Given: Channels with different activity, one without any messages
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_subscribed_channels()
+            .return_once(|_, _| {
+                Ok((
+                    vec![
+                        test_channel(1).last_message_hours_ago(24).build(),
+                        test_channel(2).build(),
+                        test_channel(3).last_message_hours_ago(1).build(),
+                    ],
+                    false,
+                ))
+            });
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Sort by last message date, newest first
+        let request = GetChannelsRequest {
+            limit: None,
+            offset: None,
+            folder: None,
+            name_filter: None,
+            sort_by: Some(ChannelSort::LastMessageDate),
+            descending: Some(true),
+            only_public: None,
+            only_verified: None,
+        };
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Most recently active channels come first, inactive ones last
+        assert!(result.is_ok());
+        let response = result.unwrap().0;
+        let ids: Vec<i64> = response.channels.iter().map(|c| c.id.get()).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_sorts_filtered_channels_before_paging() {
+        use crate::telegram::ChannelSort;
+
+        // Given: Public channels listed out of member-count order
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .times(2)
+            .returning(|| {
+                Ok(vec![
+                    test_channel(1).member_count(10).build(),
+                    test_channel(2).member_count(400).build(),
+                    test_channel(3).member_count(30).build(),
+                    test_channel(4).member_count(200).build(),
+                ])
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        let mut pages = Vec::new();
+        for offset in [0, 2] {
+            // When: Request two pages sorted by member count, largest first
+            let request = GetChannelsRequest {
+                limit: Some(2),
+                offset: Some(offset),
+                folder: None,
+                name_filter: None,
+                sort_by: Some(ChannelSort::MemberCount),
+                descending: Some(true),
+                only_public: Some(true),
+                only_verified: None,
+            };
+            let response = server
+                .get_subscribed_channels(Parameters(request))
+                .await
+                .unwrap()
+                .0;
+            pages.push(
+                response
+                    .channels
+                    .iter()
+                    .map(|c| c.id.get())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // Then: The pages continue one global order
+        assert_eq!(pages, vec![vec![2, 4], vec![3, 1]]);
+    }
+
+    #[tokio::test]
+    async fn get_subscribed_channels_unknown_folder_fails() {
+        // Given: No folder with the requested name
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_subscribed_channels()
+            .return_once(|_, _| Ok((vec![], false)));
+        mock_client.expect_get_folders().return_once(|| Ok(vec![]));
+
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request a missing folder
+        let request = GetChannelsRequest {
+            limit: None,
+            offset: None,
+            folder: Some("Missing".to_string()),
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+
+        let result = server.get_subscribed_channels(Parameters(request)).await;
+
+        // Then: Returns error naming the folder
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "NOT_FOUND");
+            assert!(error_msg.message.contains("Missing"));
+        }
+    }
+
+    // ========================================================================
+    // Tool 8: next_search_eta
+    // ========================================================================
+
+    #[tokio::test]
+    async fn next_search_eta_available_now() {
+        // Given: Rate limiter with tokens available
+        let mock_client = MockTelegramClientTrait::new();
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_time_until_available()
+            .with(mockall::predicate::eq(SEARCH_TOKEN_COST))
+            .return_once(|_| 0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Ask for the ETA
+        let response = server.next_search_eta().await.unwrap().0;
+
+        // Then: Search is available immediately
+        assert!(response.available_now);
+        assert_eq!(response.seconds_until_available, 0);
+    }
+
+    #[tokio::test]
+    async fn next_search_eta_with_depleted_limiter() {
+        use crate::config::RateLimitConfig;
+        use crate::rate_limiter::RateLimiter;
+
+        // Given: Real rate limiter with all tokens spent
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            max_tokens: 1,
+            refill_rate: 0.5,
+        });
+        limiter.acquire(1).await.unwrap();
+
+        let mock_client = MockTelegramClientTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(limiter));
+
+        // When: Ask for the ETA
+        let response = server.next_search_eta().await.unwrap().0;
+
+        // Then: Positive countdown, not available now
+        assert!(!response.available_now);
+        assert!(response.seconds_until_available > 0);
+    }
+
+    // ========================================================================
+    // Channel Blocklist
+    // ========================================================================
+
+    #[tokio::test]
+    async fn blocklisted_channels_absent_from_listing() {
+        // Given: Blocklist with one ID and one username
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_all_subscribed_channels()
+            .return_once(|| {
+                Ok(vec![
+                    test_channel(111).username("goodchannel").build(),
+                    test_channel(222).username("spamchannel").build(),
+                    test_channel(333).username("otherchannel").build(),
+                ])
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_channel_blocklist(
+                ChannelBlocklist::new(&["@SpamChannel".to_string(), "333".to_string()]),
+            );
+
+        // When: List channels
+        let request = GetChannelsRequest {
+            limit: None,
+            offset: None,
+            folder: None,
+            name_filter: None,
+            sort_by: None,
+            descending: None,
+            only_public: None,
+            only_verified: None,
+        };
+        let response = server
+            .get_subscribed_channels(Parameters(request))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Only the non-blocked channel remains
+        assert_eq!(response.total, 1);
+        assert_eq!(
+            response.channels[0].username.as_ref().unwrap().as_str(),
+            "goodchannel"
+        );
+    }
+
+    #[tokio::test]
+    async fn blocklisted_channels_absent_from_search_results() {
+        use crate::telegram::types::{Message, QueryMetadata, Username};
+        use crate::telegram::{ChannelId, ChannelName};
+
+        fn message_from(channel_id: i64, username: &str) -> Message {
+            Message {
+                id: MessageId::new(1).unwrap(),
+                channel_id: ChannelId::new(channel_id).unwrap(),
+                channel_name: ChannelName::new("Test Channel").unwrap(),
+                channel_username: Some(Username::new(username).unwrap()),
+                text: "crypto news".to_string(),
+                timestamp: chrono::Utc::now(),
+                sender_id: None,
+                sender_name: None,
+                has_media: false,
+                media_type: crate::telegram::types::MediaType::None,
+                original_length: None,
+                score: None,
+                highlighted_text: None,
+                detected_language: None,
+                forward_from_channel: None,
+                forward_from_name: None,
+                views: None,
+                forwards: None,
+                reactions: None,
+                reply_to: None,
+                edit_date: None,
+            }
+        }
+
+        // Given: Search returning messages from a blocked and an allowed channel
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().return_once(|_| {
+            Ok(SearchResult {
+                messages: vec![
+                    message_from(111, "goodchannel"),
+                    message_from(222, "spamchannel"),
+                ],
+                total_found: 2,
+                search_time_ms: 10,
+                query_metadata: QueryMetadata {
+                    query: "crypto".to_string(),
+                    hours_back: 48,
+                    channels_searched: 2,
+                },
+            })
+        });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["222".to_string()]));
+
+        // When: Search
+        let request = SearchRequest {
+            query: "crypto".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let response = server.search_messages(Parameters(request)).await.unwrap().0;
+
+        // Then: Blocked channel's message is removed
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(response.total_found, 1);
+        assert_eq!(
+            response.messages[0]
+                .channel_username
+                .as_ref()
+                .unwrap()
+                .as_str(),
+            "goodchannel"
+        );
+    }
+
+    #[tokio::test]
+    async fn search_passes_blocklist_to_client() {
+        use crate::telegram::types::QueryMetadata;
+
+        // Given: A blocklist and a client that checks it receives the blocklist
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .withf(|params| {
+                params
+                    .blocklist
+                    .is_blocked(crate::telegram::ChannelId::new(222).unwrap(), None)
+            })
+            .return_once(|_| {
+                Ok(SearchResult {
+                    messages: vec![],
+                    total_found: 0,
+                    search_time_ms: 10,
+                    query_metadata: QueryMetadata {
+                        query: "crypto".to_string(),
+                        hours_back: 48,
+                        channels_searched: 1,
+                    },
+                })
+            });
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["222".to_string()]));
+
+        // When: Search across all channels
+        let request = SearchRequest {
+            query: "crypto".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: The client was called with the blocklist
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_in_blocklisted_channel_fails_without_token() {
+        // Given: A blocked channel; neither the client nor the limiter may be used
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().times(0);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["222".to_string()]));
+
+        // When: Search inside the blocked channel
+        let request = SearchRequest {
+            query: "crypto".to_string(),
+            channel_id: Some("222".to_string()),
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        };
+        let result = server.search_messages(Parameters(request)).await;
+
+        // Then: Rejected as invalid input
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("channel blocked"));
+        }
+    }
+
+    #[cfg(feature = "index")]
+    fn index_test_request(query: &str) -> SearchRequest {
+        SearchRequest {
+            query: query.to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: None,
+            snippet_radius: None,
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
+        }
+    }
+
+    #[cfg(feature = "index")]
+    fn index_test_message(id: i64, text: &str) -> crate::telegram::types::Message {
+        use crate::telegram::types::{Message, Username};
+        use crate::telegram::{ChannelId, ChannelName};
+
+        Message {
+            id: MessageId::new(id).unwrap(),
+            channel_id: ChannelId::new(123).unwrap(),
+            channel_name: ChannelName::new("Test Channel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: text.to_string(),
+            timestamp: chrono::Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn search_messages_index_hit_skips_live_search() {
+        // Given: Index already holding a matching message
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
+        index
+            .insert(&[index_test_message(1, "Rust 2024 edition released")])
+            .unwrap();
+
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().times(0);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(0);
+
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_index(index);
+
+        // When: Search for an indexed term
+        let response = server
+            .search_messages(Parameters(index_test_request("edition")))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: Result comes from the index without touching Telegram or the limiter
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(response.messages[0].id.get(), 1);
+    }
+
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn search_messages_index_miss_falls_back_to_live_and_stores() {
+        use crate::telegram::types::QueryMetadata;
+
+        // Given: Empty index and a live search returning one message
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
+
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_search_messages()
+            .times(1)
+            .returning(|_| {
+                Ok(SearchResult {
+                    messages: vec![index_test_message(7, "Tokio 2.0 announced")],
+                    total_found: 1,
+                    search_time_ms: 50,
+                    query_metadata: QueryMetadata {
+                        query: "tokio".to_string(),
+                        hours_back: 48,
+                        channels_searched: 3,
+                    },
+                })
+            });
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().times(1).returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_index(Arc::clone(&index));
+
+        // When: Search twice for the same term
+        let first = server
+            .search_messages(Parameters(index_test_request("tokio")))
+            .await
+            .unwrap()
+            .0;
+        let second = server
+            .search_messages(Parameters(index_test_request("tokio")))
+            .await
+            .unwrap()
+            .0;
+
+        // Then: First search goes live and fills the index, second is served locally
+        assert_eq!(first.query_metadata.channels_searched, 3);
+        assert_eq!(index.len().unwrap(), 1);
+        assert_eq!(second.messages.len(), 1);
+        assert_eq!(second.messages[0].id.get(), 7);
+        assert_eq!(second.query_metadata.channels_searched, 0);
+    }
+
+    /// History pages of `total` messages, newest first, one minute apart
+    #[cfg(feature = "index")]
+    fn backfill_pages(
+        mock_client: &mut MockTelegramClientTrait,
+        total: i64,
+    ) -> Arc<std::sync::Mutex<Vec<Option<i64>>>> {
+        let requested = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&requested);
+        let now = Utc::now();
+        mock_client
+            .expect_get_channel_history()
+            .returning(move |_, limit, before_id| {
+                seen.lock().unwrap().push(before_id.map(|id| id.get()));
+                let newest = before_id.map_or(total, |id| id.get() - 1);
+                Ok((1..=newest)
+                    .rev()
+                    .take(limit as usize)
+                    .map(|id| {
+                        let mut message = index_test_message(id, "backfilled post");
+                        message.timestamp = now - chrono::Duration::minutes(total - id);
+                        message
+                    })
+                    .collect())
+            });
+        requested
     }
 
+    #[cfg(feature = "index")]
     #[tokio::test]
-    async fn check_status_reports_disconnected() {
-        // Given: Server with disconnected client
+    async fn backfill_channel_pages_history_into_index() {
+        // Given: Channel with 250 messages served in pages of 100
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
         let mut mock_client = MockTelegramClientTrait::new();
-        mock_client.expect_is_connected().return_once(|| false);
+        let requested = backfill_pages(&mut mock_client, 250);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_acquire_wait()
+            .times(3)
+            .returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_index(Arc::clone(&index));
+
+        // When: Backfill the whole day
+        let ingested = server
+            .backfill_channel(
+                ChannelId::new(123).unwrap(),
+                Utc::now() - chrono::Duration::days(1),
+            )
+            .await
+            .unwrap();
 
+        // Then: Every page is requested once, paging back by the oldest ID
+        assert_eq!(ingested, 250);
+        assert_eq!(index.len().unwrap(), 250);
+        assert_eq!(*requested.lock().unwrap(), vec![None, Some(151), Some(51)]);
+    }
+
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn backfill_channel_stops_at_since() {
+        // Given: 250 messages, one minute apart, and a cutoff 150 minutes ago
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
+        let mut mock_client = MockTelegramClientTrait::new();
+        let requested = backfill_pages(&mut mock_client, 250);
         let mut mock_limiter = MockRateLimiterTrait::new();
-        mock_limiter.expect_available_tokens().return_once(|| 0.0);
+        mock_limiter.expect_acquire_wait().returning(|_| Ok(()));
 
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_index(Arc::clone(&index));
 
-        // When: Call check_mcp_status
-        let result = server.check_mcp_status().await;
+        // When: Backfill since the cutoff
+        let since = Utc::now() - chrono::Duration::minutes(150) + chrono::Duration::seconds(30);
+        let ingested = server
+            .backfill_channel(ChannelId::new(123).unwrap(), since)
+            .await
+            .unwrap();
 
-        // Then: Returns disconnected status
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert!(!response.telegram_connected);
-        assert_eq!(response.rate_limiter_tokens, 0.0);
+        // Then: Only messages newer than the cutoff are stored, and paging stops there
+        assert_eq!(ingested, 150);
+        assert_eq!(requested.lock().unwrap().len(), 2);
     }
 
+    #[cfg(feature = "index")]
     #[tokio::test]
-    async fn get_subscribed_channels_returns_list() {
-        use crate::telegram::types::Username;
-        use crate::telegram::{Channel, ChannelId, ChannelName};
+    async fn backfill_channel_reingest_is_idempotent() {
+        // Given: A channel backfilled once
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
+        let mut mock_client = MockTelegramClientTrait::new();
+        backfill_pages(&mut mock_client, 120);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire_wait().returning(|_| Ok(()));
 
-        // Helper to create test channel
-        fn create_test_channel(id: i64, name: &str) -> Channel {
-            Channel {
-                id: ChannelId::new(id).unwrap(),
-                name: ChannelName::new(name).unwrap(),
-                username: Username::new("testchannel").unwrap(),
-                description: Some("Test channel".to_string()),
-                member_count: 1000,
-                is_verified: false,
-                is_public: true,
-                is_subscribed: true,
-                last_message_date: None,
-            }
-        }
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_index(Arc::clone(&index));
+        let channel = ChannelId::new(123).unwrap();
+        let since = Utc::now() - chrono::Duration::days(1);
+        server.backfill_channel(channel, since).await.unwrap();
 
-        // Given: Mock client returning test channels
+        // When: Backfill the same range again
+        let ingested = server.backfill_channel(channel, since).await.unwrap();
+
+        // Then: Messages are replaced, not duplicated
+        assert_eq!(ingested, 120);
+        assert_eq!(index.len().unwrap(), 120);
+    }
+
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn backfill_channel_waits_for_throttled_tokens() {
+        use crate::config::RateLimitConfig;
+        use crate::rate_limiter::RateLimiter;
+
+        // Given: Limiter with a single token refilling every 50ms
+        let index = Arc::new(MessageIndex::open_in_memory().unwrap());
         let mut mock_client = MockTelegramClientTrait::new();
-        let test_channels = vec![
-            create_test_channel(123, "Channel 1"),
-            create_test_channel(456, "Channel 2"),
-        ];
-        let expected = test_channels.clone();
+        let requested = backfill_pages(&mut mock_client, 250);
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            max_tokens: 1,
+            refill_rate: 20.0,
+        });
 
-        mock_client
-            .expect_get_subscribed_channels()
-            .with(
-                mockall::predicate::eq(20), // default limit
-                mockall::predicate::eq(0),  // default offset
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(limiter)).with_index(Arc::clone(&index));
+
+        // When: Backfill three pages
+        let start = std::time::Instant::now();
+        let ingested = server
+            .backfill_channel(
+                ChannelId::new(123).unwrap(),
+                Utc::now() - chrono::Duration::days(1),
             )
-            .return_once(move |_, _| Ok(expected));
+            .await
+            .unwrap();
 
-        let mock_limiter = MockRateLimiterTrait::new();
+        // Then: Later pages wait for a refill instead of failing
+        assert_eq!(ingested, 250);
+        assert_eq!(requested.lock().unwrap().len(), 3);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(80));
+    }
+
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn backfill_channel_without_index_fails() {
+        // Given: Server without a local index
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_channel_history().times(0);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire_wait().times(0);
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        // When: Call get_subscribed_channels with defaults
-        let request = GetChannelsRequest {
-            limit: None,
-            offset: None,
-        };
+        // When: Backfill
+        let result = server
+            .backfill_channel(ChannelId::new(123).unwrap(), Utc::now())
+            .await;
 
-        let result = server.get_subscribed_channels(request).await;
+        // Then: Configuration error, nothing fetched
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
 
-        // Then: Returns success with channel list
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert_eq!(response.channels.len(), 2);
-        assert_eq!(response.total, 2);
-        assert!(!response.has_more); // 2 channels < 20 limit
+    #[cfg(feature = "index")]
+    #[tokio::test]
+    async fn backfill_channel_rejects_channel_blocked_by_username() {
+        // Given: Channel 123 resolves to a username on the blocklist
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_info()
+            .return_once(|_| Ok(test_channel(123).username("spamchannel").build()));
+        mock_client.expect_get_channel_history().times(0);
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire_wait().times(0);
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_index(Arc::new(MessageIndex::open_in_memory().unwrap()))
+            .with_channel_blocklist(ChannelBlocklist::new(&["@SpamChannel".to_string()]));
+
+        // When: Backfill it by ID
+        let result = server
+            .backfill_channel(ChannelId::new(123).unwrap(), Utc::now())
+            .await;
+
+        // Then: Rejected before any page is fetched
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
     }
 
     #[tokio::test]
-    async fn get_subscribed_channels_respects_pagination() {
-        use crate::telegram::types::Username;
-        use crate::telegram::{Channel, ChannelId, ChannelName};
+    async fn get_channel_info_on_blocklisted_channel_fails() {
+        // Given: Channel info resolves to a blocklisted channel
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_info()
+            .return_once(|_| Ok(test_channel(222).username("spamchannel").build()));
 
-        // Helper to create test channel
-        fn create_test_channel(id: i64, name: &str) -> Channel {
-            Channel {
-                id: ChannelId::new(id).unwrap(),
-                name: ChannelName::new(name).unwrap(),
-                username: Username::new("testchannel").unwrap(),
-                description: Some("Test channel".to_string()),
-                member_count: 1000,
-                is_verified: false,
-                is_public: true,
-                is_subscribed: true,
-                last_message_date: None,
-            }
+        let mock_limiter = MockRateLimiterTrait::new();
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["spamchannel".to_string()]));
+
+        // When: Request channel info
+        let request = GetChannelInfoRequest {
+            channel_identifier: "@spamchannel".to_string(),
+        };
+        let result = server.get_channel_info(Parameters(request)).await;
+
+        // Then: Returns channel blocked error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("channel blocked"));
         }
+    }
 
-        // Given: Mock client with custom pagination parameters
-        let mut mock_client = MockTelegramClientTrait::new();
-        let test_channels = vec![create_test_channel(789, "Channel 3")];
-        let expected = test_channels.clone();
+    // ========================================================================
+    // Tool 9: forward_message
+    // ========================================================================
 
+    #[tokio::test]
+    async fn forward_message_to_saved_succeeds() {
+        // Given: Mock client that forwards successfully
+        let mut mock_client = MockTelegramClientTrait::new();
         mock_client
-            .expect_get_subscribed_channels()
+            .expect_forward_message()
             .with(
-                mockall::predicate::eq(10), // custom limit
-                mockall::predicate::eq(5),  // custom offset
+                mockall::predicate::eq(ChannelId::new(123456).unwrap()),
+                mockall::predicate::eq(MessageId::new(42).unwrap()),
+                mockall::predicate::eq("saved"),
             )
-            .return_once(move |_, _| Ok(expected));
-
-        let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+            .return_once(|_, _, _| Ok(Some(MessageId::new(900).unwrap())));
 
-        // When: Call with custom pagination
-        let request = GetChannelsRequest {
-            limit: Some(10),
-            offset: Some(5),
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_acquire()
+            .with(mockall::predicate::eq(FORWARD_TOKEN_COST))
+            .times(1)
+            .returning(|_| Ok(()));
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_allow_writes(true);
+
+        // When: Forward to Saved Messages
+        let request = ForwardMessageRequest {
+            from_channel_id: "123456".to_string(),
+            message_id: 42,
+            to: "saved".to_string(),
         };
+        let result = server.forward_message(Parameters(request)).await;
 
-        let result = server.get_subscribed_channels(request).await;
-
-        // Then: Returns success with correct pagination values
+        // Then: Returns success with the new message ID
         assert!(result.is_ok());
         let response = result.unwrap().0;
-        assert_eq!(response.channels.len(), 1);
-        assert_eq!(response.total, 1);
-        assert!(!response.has_more); // 1 channel < 10 limit
+        assert!(response.success);
+        assert_eq!(response.new_message_id, Some(900));
     }
 
     #[tokio::test]
-    async fn get_channel_info_returns_channel_details() {
-        use crate::telegram::types::Username;
-        use crate::telegram::{Channel, ChannelId, ChannelName};
+    async fn forward_message_restricted_channel_fails() {
+        use crate::error::Error;
 
-        // Given: Mock client returning channel details
+        // Given: Source channel forbids forwarding
         let mut mock_client = MockTelegramClientTrait::new();
-        let test_channel = Channel {
-            id: ChannelId::new(12345).unwrap(),
-            name: ChannelName::new("Test Channel").unwrap(),
-            username: Username::new("testchannel").unwrap(),
-            description: Some("A test channel".to_string()),
-            member_count: 5000,
-            is_verified: true,
-            is_public: true,
-            is_subscribed: false,
-            last_message_date: None,
-        };
-        let expected = test_channel.clone();
-
-        mock_client
-            .expect_get_channel_info()
-            .with(mockall::predicate::eq("testchannel"))
-            .return_once(move |_| Ok(expected));
+        mock_client.expect_forward_message().return_once(|_, _, _| {
+            Err(Error::TelegramApi(
+                "forwarding is restricted in this channel".to_string(),
+            ))
+        });
 
-        let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_allow_writes(true);
 
-        // When: Call get_channel_info
-        let request = GetChannelInfoRequest {
-            channel_identifier: "testchannel".to_string(),
+        // When: Forward
+        let request = ForwardMessageRequest {
+            from_channel_id: "123456".to_string(),
+            message_id: 42,
+            to: "saved".to_string(),
         };
+        let result = server.forward_message(Parameters(request)).await;
 
-        let result = server.get_channel_info(request).await;
-
-        // Then: Returns channel details
-        assert!(result.is_ok());
-        let channel = result.unwrap().0;
-        assert_eq!(channel.id, ChannelId::new(12345).unwrap());
-        assert_eq!(channel.name.as_str(), "Test Channel");
-        assert!(channel.is_verified);
-        assert_eq!(channel.member_count, 5000);
+        // Then: Returns the restriction error
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("forwarding is restricted"));
+        }
     }
 
     #[tokio::test]
-    async fn get_channel_info_handles_error() {
-        use crate::error::Error;
-
-        // Given: Mock client returning error
+    async fn forward_message_disabled_by_default() {
+        // Given: Server without allow_writes; neither client nor limiter may be used
         let mut mock_client = MockTelegramClientTrait::new();
-        mock_client
-            .expect_get_channel_info()
-            .with(mockall::predicate::eq("nonexistent"))
-            .return_once(move |_| Err(Error::TelegramApi("Channel not found".to_string())));
+        mock_client.expect_forward_message().never();
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().never();
 
-        let mock_limiter = MockRateLimiterTrait::new();
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        // When: Call get_channel_info with nonexistent channel
-        let request = GetChannelInfoRequest {
-            channel_identifier: "nonexistent".to_string(),
+        // When: Forward
+        let request = ForwardMessageRequest {
+            from_channel_id: "123456".to_string(),
+            message_id: 42,
+            to: "saved".to_string(),
         };
+        let result = server.forward_message(Parameters(request)).await;
 
-        let result = server.get_channel_info(request).await;
-
-        // Then: Returns error
+        // Then: Rejected with a config error pointing at allow_writes
         assert!(result.is_err());
         if let Err(error_msg) = result {
-            assert!(error_msg.contains("Channel not found"));
+            assert_eq!(error_msg.code, "CONFIG");
+            assert!(error_msg.message.contains("allow_writes"));
+        }
+    }
+
+    #[tokio::test]
+    async fn forward_message_blocklisted_channels_fail_without_token() {
+        // Given: One blocked source and one blocked destination
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_forward_message().never();
+        // Username entries mean an allowed source is resolved before forwarding
+        mock_client
+            .expect_get_channel_info()
+            .returning(|_| Ok(test_channel(654321).username("goodchannel").build()));
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().never();
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_allow_writes(true)
+            .with_channel_blocklist(ChannelBlocklist::new(&[
+                "123456".to_string(),
+                "@spamchannel".to_string(),
+            ]));
+
+        for (from, to) in [("123456", "saved"), ("654321", "@SpamChannel")] {
+            // When: Forward from or to a blocked channel
+            let request = ForwardMessageRequest {
+                from_channel_id: from.to_string(),
+                message_id: 42,
+                to: to.to_string(),
+            };
+            let result = server.forward_message(Parameters(request)).await;
+
+            // Then: Rejected before taking a token
+            assert!(result.is_err());
+            if let Err(error_msg) = result {
+                assert!(error_msg.message.contains("channel blocked"));
+            }
         }
     }
 
     // ========================================================================
-    // Tool 4: generate_message_link
+    // Tool 10: get_channel_history
     // ========================================================================
 
+    fn history_message(id: i64, minutes_ago: i64) -> crate::telegram::Message {
+        use crate::telegram::types::{MediaType, Username};
+        use crate::telegram::{ChannelName, Message};
+
+        Message {
+            id: MessageId::new(id).unwrap(),
+            channel_id: ChannelId::new(123).unwrap(),
+            channel_name: ChannelName::new("Test Channel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: format!("Message {}", id),
+            timestamp: chrono::Utc::now() - chrono::Duration::minutes(minutes_ago),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
     #[tokio::test]
-    async fn generate_message_link_returns_both_formats() {
-        // Given: Server and valid request
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
+    async fn get_channel_history_forwards_cursor_and_orders_chronologically() {
+        // Given: Client returning a full page, newest first
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_history()
+            .with(
+                mockall::predicate::eq(ChannelId::new(123).unwrap()),
+                mockall::predicate::eq(2),
+                mockall::predicate::eq(Some(MessageId::new(50).unwrap())),
+            )
+            .times(1)
+            .return_once(|_, _, _| Ok(vec![history_message(49, 1), history_message(48, 2)]));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter
+            .expect_acquire()
+            .with(mockall::predicate::eq(HISTORY_TOKEN_COST))
+            .times(1)
+            .returning(|_| Ok(()));
+
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        let request = GenerateLinkRequest {
-            channel_id: "123456789".to_string(),
-            message_id: 42,
-            include_tg_protocol: None, // defaults to true
+        // When: Request the page before message 50
+        let request = GetHistoryRequest {
+            channel_id: "123".to_string(),
+            limit: Some(2),
+            before_id: Some(50),
         };
+        let result = server.get_channel_history(Parameters(request)).await;
 
-        // When: Generate link
-        let result = server.generate_message_link(request).await;
+        // Then: Messages are oldest first, with a cursor to the next page
+        let response = result.unwrap().0;
+        assert_eq!(response.messages[0].id.get(), 48);
+        assert_eq!(response.messages[1].id.get(), 49);
+        assert_eq!(response.next_before_id, Some(48));
+    }
 
-        // Then: Returns both link formats
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn get_channel_history_caps_limit() {
+        // Given: Client expecting the capped limit
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_history()
+            .with(
+                mockall::predicate::always(),
+                mockall::predicate::eq(MAX_HISTORY_LIMIT),
+                mockall::predicate::eq(None),
+            )
+            .times(1)
+            .return_once(|_, _, _| Ok(vec![history_message(1, 1)]));
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+
+        // When: Request more than the maximum
+        let request = GetHistoryRequest {
+            channel_id: "123".to_string(),
+            limit: Some(10_000),
+            before_id: None,
+        };
+        let result = server.get_channel_history(Parameters(request)).await;
+
+        // Then: Limit was capped and history is exhausted
         let response = result.unwrap().0;
-        assert_eq!(response.channel_id, "123456789");
-        assert_eq!(response.message_id, 42);
-        assert_eq!(response.https_link, "https://t.me/c/123456789/42?single");
-        assert!(response.tg_protocol_link.is_some());
-        assert_eq!(
-            response.tg_protocol_link.unwrap(),
-            "tg://resolve?channel=123456789&post=42&single"
-        );
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(response.next_before_id, None);
     }
 
     #[tokio::test]
-    async fn generate_message_link_without_tg_protocol() {
-        // Given: Server and request with include_tg_protocol = false
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
+    async fn get_channel_history_rate_limited() {
+        use crate::error::Error;
+
+        // Given: Rate limiter with no tokens
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_channel_history().never();
+
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| {
+            Err(Error::RateLimit {
+                retry_after_seconds: 3,
+            })
+        });
+
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        let request = GenerateLinkRequest {
-            channel_id: "999".to_string(),
-            message_id: 111,
-            include_tg_protocol: Some(false),
+        // When: Request history
+        let request = GetHistoryRequest {
+            channel_id: "123".to_string(),
+            limit: None,
+            before_id: None,
         };
+        let result = server.get_channel_history(Parameters(request)).await;
+
+        // Then: Returns rate limit error without calling the client
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert!(error_msg.message.contains("rate limit"));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_channel_history_blocklisted_channel_fails_without_token() {
+        // Given: Channel 123 is blocked by ID
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_get_channel_history().never();
 
-        // When: Generate link
-        let result = server.generate_message_link(request).await;
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().never();
 
-        // Then: Returns only HTTPS link (tg_protocol_link is None)
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert_eq!(response.https_link, "https://t.me/c/999/111?single");
-        assert!(response.tg_protocol_link.is_none());
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["123".to_string()]));
+
+        // When: Request its history
+        let request = GetHistoryRequest {
+            channel_id: "123".to_string(),
+            limit: None,
+            before_id: None,
+        };
+        let result = server.get_channel_history(Parameters(request)).await;
+
+        // Then: Rejected before taking a token
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("channel blocked"));
+        }
     }
 
     #[tokio::test]
-    async fn generate_message_link_invalid_channel_id() {
-        // Given: Server and request with non-numeric channel_id
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+    async fn get_channel_history_blocklisted_username_fails_without_cursor() {
+        // Given: Channel 123 is blocked by username and returns a full page
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_get_channel_history()
+            .return_once(|_, _, _| Ok(vec![history_message(49, 1), history_message(48, 2)]));
 
-        let request = GenerateLinkRequest {
-            channel_id: "not_a_number".to_string(),
-            message_id: 42,
-            include_tg_protocol: None,
-        };
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
 
-        // When: Generate link
-        let result = server.generate_message_link(request).await;
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_channel_blocklist(ChannelBlocklist::new(&["@testchannel".to_string()]));
 
-        // Then: Returns error
+        // When: Request its history
+        let request = GetHistoryRequest {
+            channel_id: "123".to_string(),
+            limit: Some(2),
+            before_id: None,
+        };
+        let result = server.get_channel_history(Parameters(request)).await;
+
+        // Then: Rejected instead of an empty page with a cursor
         assert!(result.is_err());
         if let Err(error_msg) = result {
-            assert!(error_msg.contains("Invalid channel_id"));
+            assert!(error_msg.message.contains("channel blocked"));
         }
     }
 
     // ========================================================================
-    // Tool 5: open_message_in_telegram
+    // Tool 11: send_message
     // ========================================================================
 
     #[tokio::test]
-    async fn open_message_in_telegram_invalid_channel_id() {
-        // Given: Server and request with non-numeric channel_id
-        let mock_client = MockTelegramClientTrait::new();
+    async fn send_message_disabled_by_default() {
+        // Given: Server without allow_writes; the client must not be called
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_send_message().never();
+
         let mock_limiter = MockRateLimiterTrait::new();
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        let request = OpenMessageRequest {
-            channel_id: "invalid".to_string(),
-            message_id: 42,
-            use_tg_protocol: None,
+        // When: Send a message
+        let request = SendMessageRequest {
+            channel_id: "123456".to_string(),
+            text: "hello".to_string(),
         };
+        let result = server.send_message(Parameters(request)).await;
 
-        // When: Try to open message
-        let result = server.open_message_in_telegram(request).await;
-
-        // Then: Returns error
+        // Then: Rejected with a config error pointing at allow_writes
         assert!(result.is_err());
         if let Err(error_msg) = result {
-            assert!(error_msg.contains("Invalid channel_id"));
+            assert_eq!(error_msg.code, "CONFIG");
+            assert!(error_msg.message.contains("allow_writes"));
         }
     }
 
     #[tokio::test]
-    async fn open_message_in_telegram_uses_tg_protocol_by_default() {
-        // Given: Server and request without use_tg_protocol specified
-        let mock_client = MockTelegramClientTrait::new();
+    async fn send_message_posts_when_writes_allowed() {
+        // Given: Server with writes enabled and a client that posts successfully
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client
+            .expect_send_message()
+            .with(
+                mockall::predicate::eq(ChannelId::new(123456).unwrap()),
+                mockall::predicate::eq("hello"),
+            )
+            .return_once(|_, _| Ok(MessageId::new(77).unwrap()));
+
         let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_allow_writes(true);
 
-        let request = OpenMessageRequest {
+        // When: Send a message
+        let request = SendMessageRequest {
             channel_id: "123456".to_string(),
-            message_id: 42,
-            use_tg_protocol: None, // defaults to true
+            text: "hello".to_string(),
         };
+        let result = server.send_message(Parameters(request)).await;
 
-        // When: Open message
-        let result = server.open_message_in_telegram(request).await;
-
-        // Then: Returns response with tg:// link
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert!(response.link_used.starts_with("tg://"));
+        // Then: Returns the new message ID
+        assert_eq!(result.unwrap().0.message_id, 77);
     }
 
     #[tokio::test]
-    async fn open_message_in_telegram_uses_https_when_requested() {
-        // Given: Server and request with use_tg_protocol = false
+    async fn send_message_rejects_empty_text() {
+        // Given: Server with writes enabled
         let mock_client = MockTelegramClientTrait::new();
         let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let server =
+            McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter)).with_allow_writes(true);
 
-        let request = OpenMessageRequest {
+        // When: Send blank text
+        let request = SendMessageRequest {
             channel_id: "123456".to_string(),
-            message_id: 42,
-            use_tg_protocol: Some(false),
+            text: "   ".to_string(),
         };
+        let result = server.send_message(Parameters(request)).await;
 
-        // When: Open message
-        let result = server.open_message_in_telegram(request).await;
-
-        // Then: Returns response with https:// link
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert!(response.link_used.starts_with("https://"));
+        // Then: Invalid input
+        assert!(result.is_err());
+        if let Err(error_msg) = result {
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+        }
     }
 
     // ========================================================================
-    // Tool 6: search_messages
+    // Tool 12: download_media
     // ========================================================================
 
     #[tokio::test]
-    async fn search_messages_returns_results() {
-        use crate::telegram::types::{Message, QueryMetadata, SearchResult, Username};
-        use crate::telegram::{ChannelId, ChannelName};
-
-        // Given: Mock client returning search results
+    async fn download_media_returns_path_and_size() {
+        // Given: Client that saves a 5-byte file into the download directory
+        let dir = tempfile::tempdir().unwrap();
         let mut mock_client = MockTelegramClientTrait::new();
-        let expected_result = SearchResult {
-            messages: vec![Message {
-                id: MessageId::new(1).unwrap(),
-                channel_id: ChannelId::new(123).unwrap(),
-                channel_name: ChannelName::new("Test Channel").unwrap(),
-                channel_username: Username::new("testchannel").unwrap(),
-                text: "Test message about AI".to_string(),
-                timestamp: chrono::Utc::now(),
-                sender_id: None,
-                sender_name: None,
-                has_media: false,
-                media_type: crate::telegram::types::MediaType::None,
-            }],
-            total_found: 1,
-            search_time_ms: 100,
-            query_metadata: QueryMetadata {
-                query: "AI".to_string(),
-                hours_back: 48,
-                channels_searched: 1,
-            },
-        };
-        let expected = expected_result.clone();
-
         mock_client
-            .expect_search_messages()
-            .returning(move |_| Ok(expected.clone()));
+            .expect_download_media()
+            .with(
+                mockall::predicate::eq(ChannelId::new(123456).unwrap()),
+                mockall::predicate::eq(MessageId::new(42).unwrap()),
+                mockall::predicate::eq(dir.path().to_path_buf()),
+            )
+            .return_once(|_, _, dest_dir| {
+                let path = dest_dir.join("42_report.pdf");
+                std::fs::write(&path, b"hello").unwrap();
+                Ok(path)
+            });
 
         let mut mock_limiter = MockRateLimiterTrait::new();
         mock_limiter.expect_acquire().returning(|_| Ok(()));
 
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_download_dir(dir.path());
 
-        // When: Search messages
-        let request = SearchRequest {
-            query: "AI".to_string(),
-            channel_id: None,
-            hours_back: None,
-            limit: None,
+        // When: Download the message media
+        let request = DownloadMediaRequest {
+            channel_id: "123456".to_string(),
+            message_id: 42,
         };
+        let response = server.download_media(Parameters(request)).await.unwrap().0;
 
-        let result = server.search_messages(request).await;
-
-        // Then: Returns search results
-        assert!(result.is_ok());
-        let response = result.unwrap().0;
-        assert_eq!(response.total_found, 1);
-        assert_eq!(response.messages.len(), 1);
-        assert!(response.messages[0].text.contains("AI"));
+        // Then: Returns the saved path and its size
+        assert!(response.path.ends_with("42_report.pdf"));
+        assert_eq!(response.size_bytes, 5);
     }
 
     #[tokio::test]
-    async fn search_messages_empty_query_fails() {
-        // Given: Server and empty query
-        let mock_client = MockTelegramClientTrait::new();
-        let mock_limiter = MockRateLimiterTrait::new();
-        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
+    async fn download_media_without_media_fails() {
+        use crate::error::Error;
 
-        let request = SearchRequest {
-            query: "   ".to_string(), // whitespace only
-            channel_id: None,
-            hours_back: None,
-            limit: None,
-        };
+        // Given: Client reporting a text-only message
+        let dir = tempfile::tempdir().unwrap();
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_download_media().return_once(|_, _, _| {
+            Err(Error::InvalidInput(
+                "Message 42 has no media to download".to_string(),
+            ))
+        });
 
-        // When: Search messages
-        let result = server.search_messages(request).await;
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
 
-        // Then: Returns error
+        let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter))
+            .with_download_dir(dir.path());
+
+        // When: Download the message media
+        let request = DownloadMediaRequest {
+            channel_id: "123456".to_string(),
+            message_id: 42,
+        };
+        let result = server.download_media(Parameters(request)).await;
+
+        // Then: Returns the no-media error
         assert!(result.is_err());
         if let Err(error_msg) = result {
-            assert!(error_msg.contains("cannot be empty"));
+            assert_eq!(error_msg.code, "INVALID_INPUT");
+            assert!(error_msg.message.contains("no media"));
         }
     }
 
-    #[tokio::test]
-    async fn search_messages_rate_limited() {
-        use crate::error::Error;
+    // ========================================================================
+    // Search Snippets
+    // ========================================================================
 
-        // Given: Rate limiter that denies request
-        let mock_client = MockTelegramClientTrait::new();
+    #[tokio::test]
+    async fn search_messages_returns_snippets_when_requested() {
+        use crate::telegram::types::{Message, QueryMetadata, SearchResult, Username};
+        use crate::telegram::{ChannelId, ChannelName};
 
-        let mut mock_limiter = MockRateLimiterTrait::new();
-        mock_limiter.expect_acquire().returning(|_| {
-            Err(Error::RateLimit {
-                retry_after_seconds: 5,
+        // Given: Search result with a long message
+        let mut mock_client = MockTelegramClientTrait::new();
+        mock_client.expect_search_messages().return_once(|_| {
+            Ok(SearchResult {
+                messages: vec![Message {
+                    id: MessageId::new(1).unwrap(),
+                    channel_id: ChannelId::new(123).unwrap(),
+                    channel_name: ChannelName::new("Test Channel").unwrap(),
+                    channel_username: Some(Username::new("testchannel").unwrap()),
+                    text: "Long intro text before the AI announcement and more text after it"
+                        .to_string(),
+                    timestamp: chrono::Utc::now(),
+                    sender_id: None,
+                    sender_name: None,
+                    has_media: false,
+                    media_type: crate::telegram::types::MediaType::None,
+                    original_length: None,
+                    score: None,
+                    highlighted_text: None,
+                    detected_language: None,
+                    forward_from_channel: None,
+                    forward_from_name: None,
+                    views: None,
+                    forwards: None,
+                    reactions: None,
+                    reply_to: None,
+                    edit_date: None,
+                }],
+                total_found: 1,
+                search_time_ms: 10,
+                query_metadata: QueryMetadata {
+                    query: "AI".to_string(),
+                    hours_back: 48,
+                    channels_searched: 1,
+                },
             })
         });
 
+        let mut mock_limiter = MockRateLimiterTrait::new();
+        mock_limiter.expect_acquire().returning(|_| Ok(()));
+
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
+        // When: Search with snippets enabled
         let request = SearchRequest {
-            query: "test".to_string(),
+            query: "AI".to_string(),
             channel_id: None,
             hours_back: None,
             limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: Some(true),
+            snippet_radius: Some(4),
+            rank: None,
+            highlight: None,
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
         };
+        let response = server.search_messages(Parameters(request)).await.unwrap().0;
 
-        // When: Search messages
-        let result = server.search_messages(request).await;
-
-        // Then: Returns rate limit error
-        assert!(result.is_err());
-        if let Err(error_msg) = result {
-            assert!(error_msg.contains("rate limit"));
-        }
+        // Then: Text is replaced with a marked snippet
+        assert_eq!(response.messages[0].text, "…the **AI** ann…");
     }
 
     #[tokio::test]
-    async fn search_messages_with_channel_filter() {
-        use crate::telegram::types::{QueryMetadata, SearchResult};
+    async fn search_messages_adds_highlighted_text_when_requested() {
+        use crate::telegram::types::{Message, QueryMetadata, SearchResult, Username};
+        use crate::telegram::{ChannelId, ChannelName};
 
-        // Given: Mock client with channel filter
+        // Given: Search result mentioning the query twice
         let mut mock_client = MockTelegramClientTrait::new();
-        let expected_result = SearchResult {
-            messages: vec![],
-            total_found: 0,
-            search_time_ms: 50,
-            query_metadata: QueryMetadata {
-                query: "test".to_string(),
-                hours_back: 24,
-                channels_searched: 1,
-            },
-        };
-        let expected = expected_result.clone();
-
-        mock_client
-            .expect_search_messages()
-            .returning(move |params| {
-                // Verify channel_id is passed correctly
-                assert!(params.channel_id.is_some());
-                assert_eq!(params.channel_id.unwrap().get(), 999);
-                Ok(expected.clone())
-            });
+        mock_client.expect_search_messages().return_once(|_| {
+            Ok(SearchResult {
+                messages: vec![Message {
+                    id: MessageId::new(1).unwrap(),
+                    channel_id: ChannelId::new(123).unwrap(),
+                    channel_name: ChannelName::new("Test Channel").unwrap(),
+                    channel_username: Some(Username::new("testchannel").unwrap()),
+                    text: "AI news: the ai team shipped".to_string(),
+                    timestamp: chrono::Utc::now(),
+                    sender_id: None,
+                    sender_name: None,
+                    has_media: false,
+                    media_type: crate::telegram::types::MediaType::None,
+                    original_length: None,
+                    score: None,
+                    highlighted_text: None,
+                    detected_language: None,
+                    forward_from_channel: None,
+                    forward_from_name: None,
+                    views: None,
+                    forwards: None,
+                    reactions: None,
+                    reply_to: None,
+                    edit_date: None,
+                }],
+                total_found: 1,
+                search_time_ms: 10,
+                query_metadata: QueryMetadata {
+                    query: "AI".to_string(),
+                    hours_back: 48,
+                    channels_searched: 1,
+                },
+            })
+        });
 
         let mut mock_limiter = MockRateLimiterTrait::new();
         mock_limiter.expect_acquire().returning(|_| Ok(()));
 
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        // When: Search with channel filter
+        // When: Search with highlighting and snippets enabled
         let request = SearchRequest {
-            query: "test".to_string(),
-            channel_id: Some("999".to_string()),
-            hours_back: Some(24),
-            limit: Some(50),
+            query: "AI".to_string(),
+            channel_id: None,
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: Some(true),
+            snippet_radius: Some(2),
+            rank: None,
+            highlight: Some(true),
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
         };
+        let response = server.search_messages(Parameters(request)).await.unwrap().0;
 
-        let result = server.search_messages(request).await;
-
-        // Then: Success
-        assert!(result.is_ok());
+        // Then: Highlighting covers the full text, independent of the snippet
+        let message = &response.messages[0];
+        assert_eq!(
+            message.highlighted_text.as_deref(),
+            Some("«AI» news: the «ai» team shipped")
+        );
+        assert_eq!(message.text, "**AI** n…");
     }
 
     #[tokio::test]
-    async fn search_messages_applies_limits() {
-        use crate::telegram::types::{QueryMetadata, SearchResult};
+    async fn search_messages_highlights_live_results_on_normalized_text() {
+        use crate::telegram::types::{Message, QueryMetadata, SearchResult, Username};
+        use crate::telegram::{ChannelId, ChannelName};
 
-        // Given: Mock client that verifies params
+        // Given: A live result spelling the query with Latin look-alikes
         let mut mock_client = MockTelegramClientTrait::new();
-        let expected_result = SearchResult {
-            messages: vec![],
-            total_found: 0,
-            search_time_ms: 50,
-            query_metadata: QueryMetadata {
-                query: "test".to_string(),
-                hours_back: 72, // should be capped to MAX_HOURS_BACK
-                channels_searched: 0,
-            },
-        };
-        let expected = expected_result.clone();
-
-        mock_client
-            .expect_search_messages()
-            .returning(move |params| {
-                // Verify limits are applied
-                assert_eq!(params.hours_back, 72); // MAX_HOURS_BACK
-                assert_eq!(params.limit, 100); // MAX_LIMIT
-                Ok(expected.clone())
-            });
+        mock_client.expect_search_messages().return_once(|_| {
+            Ok(SearchResult {
+                messages: vec![Message {
+                    id: MessageId::new(1).unwrap(),
+                    channel_id: ChannelId::new(123).unwrap(),
+                    channel_name: ChannelName::new("Test Channel").unwrap(),
+                    channel_username: Some(Username::new("testchannel").unwrap()),
+                    text: "Курс pyбля вырос".to_string(),
+                    timestamp: chrono::Utc::now(),
+                    sender_id: None,
+                    sender_name: None,
+                    has_media: false,
+                    media_type: crate::telegram::types::MediaType::None,
+                    original_length: None,
+                    score: None,
+                    highlighted_text: None,
+                    detected_language: None,
+                    forward_from_channel: None,
+                    forward_from_name: None,
+                    views: None,
+                    forwards: None,
+                    reactions: None,
+                    reply_to: None,
+                    edit_date: None,
+                }],
+                total_found: 1,
+                search_time_ms: 10,
+                query_metadata: QueryMetadata {
+                    query: "рубля".to_string(),
+                    hours_back: 48,
+                    channels_searched: 1,
+                },
+            })
+        });
 
         let mut mock_limiter = MockRateLimiterTrait::new();
-        mock_limiter.expect_acquire().returning(|_| Ok(()));
+        mock_limiter.expect_acquire().times(1).returning(|_| Ok(()));
 
         let server = McpServer::new(Arc::new(mock_client), Arc::new(mock_limiter));
 
-        // When: Search with values exceeding limits
+        // When: Search live with highlighting and snippets enabled
         let request = SearchRequest {
-            query: "test".to_string(),
+            query: "рубля".to_string(),
             channel_id: None,
-            hours_back: Some(1000), // exceeds MAX_HOURS_BACK (72)
-            limit: Some(500),       // exceeds MAX_LIMIT (100)
+            hours_back: None,
+            limit: None,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: None,
+            snippet: Some(true),
+            snippet_radius: Some(2),
+            rank: None,
+            highlight: Some(true),
+            min_views: None,
+            edited_only: None,
+            queries: None,
+            before_id: None,
         };
+        let response = server.search_messages(Parameters(request)).await.unwrap().0;
 
-        let result = server.search_messages(request).await;
-
-        // Then: Success (limits applied internally)
-        assert!(result.is_ok());
+        // Then: The look-alike spelling is marked in the original text
+        let message = &response.messages[0];
+        assert_eq!(
+            message.highlighted_text.as_deref(),
+            Some("Курс «pyбля» вырос")
+        );
+        assert_eq!(message.text, "…с **pyбля** в…");
     }
 }
@@ -1,6 +1,8 @@
 //! MCP tool request and response types with JSON schemas
 
-use crate::telegram::types::Channel;
+use crate::rate_limiter::RateLimiterMetrics;
+use crate::telegram::types::{Channel, ChannelFolder, ChannelSort, MatchMode, Message, RankMode};
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +21,23 @@ pub struct StatusResponse {
 
     #[schemars(description = "Server version")]
     pub server_version: String,
+
+    #[schemars(description = "Granted and denied rate limiter requests since startup")]
+    pub rate_limiter: RateLimiterMetrics,
+
+    #[schemars(description = "Seconds since the server started")]
+    pub uptime_seconds: u64,
+
+    #[schemars(description = "Completion time of the latest successful search (null if none yet)")]
+    pub last_search_at: Option<DateTime<Utc>>,
+
+    #[schemars(description = "Successful searches since startup")]
+    pub searches_performed: u64,
+
+    #[schemars(
+        description = "Round-trip time of a live request to Telegram (null if disconnected or the request failed)"
+    )]
+    pub telegram_ping_ms: Option<u64>,
 }
 
 // ============================================================================
@@ -28,11 +47,33 @@ pub struct StatusResponse {
 /// Request for get_subscribed_channels tool
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct GetChannelsRequest {
-    #[schemars(description = "Maximum number of channels to return (default: 50, max: 500)")]
+    #[schemars(description = "Maximum number of channels to return (default: 20, max: 500)")]
     pub limit: Option<u32>,
 
     #[schemars(description = "Offset for pagination (default: 0)")]
     pub offset: Option<u32>,
+
+    #[schemars(description = "Optional: Only return channels in this folder (exact name)")]
+    pub folder: Option<String>,
+
+    #[schemars(
+        description = "Optional: Only return channels whose name or username contains this text (case-insensitive)"
+    )]
+    pub name_filter: Option<String>,
+
+    #[schemars(
+        description = "Optional: Sort the page by name, member_count or last_message_date (channels without messages go last)"
+    )]
+    pub sort_by: Option<ChannelSort>,
+
+    #[schemars(description = "Sort in descending order (default: false)")]
+    pub descending: Option<bool>,
+
+    #[schemars(description = "Only return public channels (default: false)")]
+    pub only_public: Option<bool>,
+
+    #[schemars(description = "Only return verified channels (default: false)")]
+    pub only_verified: Option<bool>,
 }
 
 /// Response for get_subscribed_channels tool
@@ -41,7 +82,9 @@ pub struct ChannelsResponse {
     #[schemars(description = "List of subscribed channels")]
     pub channels: Vec<Channel>,
 
-    #[schemars(description = "Total number of channels (for pagination)")]
+    #[schemars(
+        description = "Total number of channels (for pagination); with filters, the number of matching channels"
+    )]
     pub total: usize,
 
     #[schemars(description = "Whether there are more channels available")]
@@ -74,8 +117,15 @@ pub struct GenerateLinkRequest {
     #[schemars(description = "Message ID within the channel")]
     pub message_id: i64,
 
-    #[schemars(description = "Also return tg:// protocol link (default: true)")]
+    #[schemars(
+        description = "Also return tg:// protocol link (default: true unless changed in server config)"
+    )]
     pub include_tg_protocol: Option<bool>,
+
+    #[schemars(
+        description = "Link to just this message (default: true). Set false to open the whole album"
+    )]
+    pub single: Option<bool>,
 }
 
 /// Response for generate_message_link tool
@@ -109,6 +159,11 @@ pub struct OpenMessageRequest {
 
     #[schemars(description = "Use tg:// protocol (default: true). If false, uses https")]
     pub use_tg_protocol: Option<bool>,
+
+    #[schemars(
+        description = "Only build and return the link without launching Telegram (default: false)"
+    )]
+    pub dry_run: Option<bool>,
 }
 
 /// Response for open_message_in_telegram tool
@@ -134,9 +189,15 @@ pub struct OpenMessageResponse {
 /// Request for search_messages tool
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 pub struct SearchRequest {
-    #[schemars(description = "Search query (required, minimum length: 1)")]
+    #[schemars(description = "Search query (required unless queries is given)")]
+    #[serde(default)]
     pub query: String,
 
+    #[schemars(
+        description = "Optional: Additional queries; messages matching any query are returned, deduplicated"
+    )]
+    pub queries: Option<Vec<String>>,
+
     #[schemars(description = "Optional: Filter by specific channel ID")]
     pub channel_id: Option<String>,
 
@@ -145,10 +206,250 @@ pub struct SearchRequest {
 
     #[schemars(description = "Maximum results to return (default: 20, max: 100)")]
     pub limit: Option<u32>,
+
+    #[schemars(
+        description = "Optional: Only return messages with these media types (e.g. [\"photo\", \"document\"])"
+    )]
+    pub media_types: Option<Vec<String>>,
+
+    #[schemars(description = "Optional: Only return messages from this sender (numeric user ID)")]
+    pub sender_id: Option<String>,
+
+    #[schemars(
+        description = "Optional: Only messages at or after this RFC3339 time (overrides hours_back)"
+    )]
+    pub after: Option<String>,
+
+    #[schemars(description = "Optional: Only messages at or before this RFC3339 time")]
+    pub before: Option<String>,
+
+    #[schemars(description = "Matching mode: substring (default), whole_word or case_sensitive")]
+    pub match_mode: Option<MatchMode>,
+
+    #[schemars(description = "Return a snippet around the first match instead of full text")]
+    pub snippet: Option<bool>,
+
+    #[schemars(
+        description = "Characters of context on each side of the match (default: 80, max: 1000)"
+    )]
+    pub snippet_radius: Option<usize>,
+
+    #[schemars(
+        description = "Result order: recency (newest first, default) or relevance (most query term hits first, with a score)"
+    )]
+    pub rank: Option<RankMode>,
+
+    #[schemars(
+        description = "Add highlighted_text with every match of the query wrapped in « and »"
+    )]
+    pub highlight: Option<bool>,
+
+    #[schemars(description = "Optional: Only return posts with at least this many views")]
+    pub min_views: Option<u32>,
+
+    #[schemars(description = "Optional: Only return messages edited after posting")]
+    pub edited_only: Option<bool>,
+
+    #[schemars(
+        description = "Optional: Only messages older than this message ID, to page past the limit (requires channel_id)"
+    )]
+    pub before_id: Option<i64>,
 }
 
 // Response: SearchResult (from telegram/types.rs) which contains Vec<Message>
 
+// ============================================================================
+// Tool 7: get_channel_folders
+// ============================================================================
+
+/// Response for get_channel_folders tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FoldersResponse {
+    #[schemars(description = "Dialog folders with the IDs of the channels they contain")]
+    pub folders: Vec<ChannelFolder>,
+}
+
+// ============================================================================
+// Tool 8: next_search_eta
+// ============================================================================
+
+/// Response for next_search_eta tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SearchEtaResponse {
+    #[schemars(description = "Whether a search can run right now without being rate limited")]
+    pub available_now: bool,
+
+    #[schemars(description = "Seconds until enough rate limiter tokens are available (0 if now)")]
+    pub seconds_until_available: u64,
+}
+
+// ============================================================================
+// Tool 9: forward_message
+// ============================================================================
+
+/// Request for forward_message tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ForwardMessageRequest {
+    #[schemars(description = "Numeric ID of the channel the message is in")]
+    pub from_channel_id: String,
+
+    #[schemars(description = "Message ID within the channel")]
+    pub message_id: i64,
+
+    #[schemars(description = "Destination: \"saved\" for Saved Messages, or a channel identifier")]
+    pub to: String,
+}
+
+/// Response for forward_message tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ForwardMessageResponse {
+    #[schemars(description = "Whether the message was forwarded")]
+    pub success: bool,
+
+    #[schemars(description = "ID of the forwarded copy in the destination chat, if known")]
+    pub new_message_id: Option<i64>,
+}
+
+// ============================================================================
+// Tool 10: get_channel_history
+// ============================================================================
+
+/// Request for get_channel_history tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetHistoryRequest {
+    #[schemars(description = "Numeric channel ID")]
+    pub channel_id: String,
+
+    #[schemars(description = "Maximum messages to return (default: 20, max: 100)")]
+    pub limit: Option<u32>,
+
+    #[schemars(description = "Only return messages older than this message ID (for paging back)")]
+    pub before_id: Option<i64>,
+}
+
+/// Response for get_channel_history tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HistoryResponse {
+    #[schemars(description = "Messages in chronological order (oldest first)")]
+    pub messages: Vec<Message>,
+
+    #[schemars(description = "Pass as before_id to fetch the previous page (null when exhausted)")]
+    pub next_before_id: Option<i64>,
+}
+
+// ============================================================================
+// Tool 11: send_message
+// ============================================================================
+
+/// Request for send_message tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SendMessageRequest {
+    #[schemars(description = "Numeric ID of a channel you can post to")]
+    pub channel_id: String,
+
+    #[schemars(description = "Message text (max 4096 characters)")]
+    pub text: String,
+}
+
+/// Response for send_message tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SendMessageResponse {
+    #[schemars(description = "ID of the posted message")]
+    pub message_id: i64,
+}
+
+// ============================================================================
+// Tool 12: download_media
+// ============================================================================
+
+/// Request for download_media tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DownloadMediaRequest {
+    #[schemars(description = "Numeric ID of the channel the message is in")]
+    pub channel_id: String,
+
+    #[schemars(description = "ID of the message whose media to download")]
+    pub message_id: i64,
+}
+
+/// Response for download_media tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DownloadMediaResponse {
+    #[schemars(description = "Local path of the saved file")]
+    pub path: String,
+
+    #[schemars(description = "Size of the saved file in bytes")]
+    pub size_bytes: u64,
+}
+
+// ============================================================================
+// Tool 13: generate_message_links
+// ============================================================================
+
+/// Request for generate_message_links tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GenerateLinksRequest {
+    #[schemars(description = "Numeric channel ID")]
+    pub channel_id: String,
+
+    #[schemars(description = "Message IDs within the channel (max 100)")]
+    pub message_ids: Vec<i64>,
+
+    #[schemars(
+        description = "Also return tg:// protocol links (default: true unless changed in server config)"
+    )]
+    pub include_tg_protocol: Option<bool>,
+}
+
+/// Response for generate_message_links tool
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GenerateLinksResponse {
+    #[schemars(description = "Links for every valid message ID, in request order")]
+    pub links: Vec<MessageLinkResponse>,
+
+    #[schemars(description = "Message IDs that could not be linked, with the reason")]
+    pub errors: Vec<LinkError>,
+}
+
+/// A message ID rejected by generate_message_links
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LinkError {
+    #[schemars(description = "Message ID as given in the request")]
+    pub message_id: i64,
+
+    #[schemars(description = "Why no link was generated")]
+    pub error: String,
+}
+
+// ============================================================================
+// Tool 14: open_link
+// ============================================================================
+
+/// Request for open_link tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct OpenLinkRequest {
+    #[schemars(description = "Telegram message link: https://t.me/... or tg://resolve?...")]
+    pub url: String,
+}
+
+// Response: OpenMessageResponse (shared with open_message_in_telegram)
+
+// ============================================================================
+// Tool 16: channel_stats
+// ============================================================================
+
+/// Request for channel_stats tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct ChannelStatsRequest {
+    #[schemars(description = "Numeric channel ID")]
+    pub channel_id: String,
+
+    #[schemars(description = "Hours to look back (default: 48, max: 72)")]
+    pub hours_back: Option<u32>,
+}
+
+// Response: ChannelStats
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +460,11 @@ mod tests {
             telegram_connected: true,
             rate_limiter_tokens: 45.5,
             server_version: "0.1.0".to_string(),
+            rate_limiter: RateLimiterMetrics::default(),
+            uptime_seconds: 42,
+            last_search_at: None,
+            searches_performed: 0,
+            telegram_ping_ms: Some(35),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -182,6 +488,15 @@ mod tests {
 
         assert_eq!(request.limit, None);
         assert_eq!(request.offset, None);
+        assert_eq!(request.folder, None);
+    }
+
+    #[test]
+    fn get_channels_request_with_folder() {
+        let json = r#"{"folder": "Crypto"}"#;
+        let request: GetChannelsRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.folder.as_deref(), Some("Crypto"));
     }
 
     #[test]
@@ -191,5 +506,30 @@ mod tests {
 
         assert_eq!(request.query, "test");
         assert!(request.channel_id.is_none());
+        assert!(request.snippet.is_none());
+        assert!(request.snippet_radius.is_none());
+        assert!(request.media_types.is_none());
+        assert!(request.sender_id.is_none());
+        assert!(request.after.is_none());
+        assert!(request.before.is_none());
+        assert!(request.match_mode.is_none());
+    }
+
+    #[test]
+    fn search_request_parses_match_mode() {
+        let json = r#"{"query": "рубль", "match_mode": "whole_word"}"#;
+        let request: SearchRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.match_mode, Some(MatchMode::WholeWord));
+    }
+
+    #[test]
+    fn get_history_request_defaults() {
+        let json = r#"{"channel_id": "123"}"#;
+        let request: GetHistoryRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.channel_id, "123");
+        assert_eq!(request.limit, None);
+        assert_eq!(request.before_id, None);
     }
 }
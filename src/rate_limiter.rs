@@ -1,7 +1,10 @@
 use crate::config::RateLimitConfig;
 use crate::error::Error;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Token bucket for rate limiting
 struct TokenBucket {
@@ -46,14 +49,61 @@ impl TokenBucket {
         }
     }
 
+    /// Refill the bucket to full capacity
+    fn reset(&mut self) {
+        self.available_tokens = self.max_tokens;
+        self.last_refill = Instant::now();
+    }
+
     fn available(&self) -> f64 {
         self.available_tokens
     }
+
+    /// Seconds until the given tokens are available (0 if available now)
+    fn seconds_until_available(&self, tokens: u32) -> u64 {
+        let tokens_needed = tokens as f64 - self.available_tokens;
+        if tokens_needed <= 0.0 {
+            return 0;
+        }
+        (tokens_needed / self.refill_rate).ceil() as u64
+    }
+
+    /// Exact time until the given tokens are available (zero if available now)
+    fn wait_time(&self, tokens: u32) -> Duration {
+        let tokens_needed = tokens as f64 - self.available_tokens;
+        if tokens_needed <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(tokens_needed / self.refill_rate)
+    }
+
+    /// Whether the request can ever succeed by waiting for refill
+    fn can_satisfy(&self, tokens: u32) -> bool {
+        let tokens_f64 = tokens as f64;
+        tokens_f64 <= self.max_tokens
+            && (self.refill_rate > 0.0 || tokens_f64 <= self.available_tokens)
+    }
+}
+
+/// Snapshot of rate limiter activity since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct RateLimiterMetrics {
+    /// Successful acquire calls
+    pub total_acquired: u64,
+    /// Acquire calls rejected by the rate limit
+    pub total_denied: u64,
+    /// Tokens handed out across all successful acquires
+    pub total_tokens_granted: u64,
 }
 
 /// Rate limiter using token bucket algorithm
 pub struct RateLimiter {
     bucket: Arc<Mutex<TokenBucket>>,
+    /// Serializes `acquire_wait` callers (tokio's Mutex is FIFO, so waiters are served in order)
+    waiters: tokio::sync::Mutex<()>,
+    total_acquired: AtomicU64,
+    total_denied: AtomicU64,
+    total_tokens_granted: AtomicU64,
 }
 
 impl RateLimiter {
@@ -62,15 +112,42 @@ impl RateLimiter {
         let bucket = TokenBucket::new(config.max_tokens, config.refill_rate);
         Self {
             bucket: Arc::new(Mutex::new(bucket)),
+            waiters: tokio::sync::Mutex::new(()),
+            total_acquired: AtomicU64::new(0),
+            total_denied: AtomicU64::new(0),
+            total_tokens_granted: AtomicU64::new(0),
         }
     }
 
+    fn record_granted(&self, tokens: u32) {
+        self.total_acquired.fetch_add(1, Ordering::Relaxed);
+        self.total_tokens_granted
+            .fetch_add(u64::from(tokens), Ordering::Relaxed);
+    }
+
+    fn record_denied(&self) {
+        self.total_denied.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Get the number of available tokens (after refill)
     pub fn available_tokens(&self) -> f64 {
         let mut bucket = self.bucket.lock().unwrap();
         bucket.refill();
         bucket.available()
     }
+
+    /// Refill the bucket to full (e.g. after reconnecting from a long idle period)
+    pub fn reset(&self) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.reset();
+    }
+
+    /// Seconds until `tokens` would be available, or `None` if available now
+    ///
+    /// Side-effect free: refills but never consumes tokens.
+    pub fn retry_after(&self, tokens: u32) -> Option<u64> {
+        Some(self.time_until_available(tokens)).filter(|seconds| *seconds > 0)
+    }
 }
 
 /// Trait for rate limiting (allows mocking in tests)
@@ -80,19 +157,83 @@ pub trait RateLimiterTrait: Send + Sync {
     /// Acquire tokens, returning error if rate limit exceeded
     async fn acquire(&self, tokens: u32) -> Result<(), Error>;
 
+    /// Acquire tokens, waiting for refill instead of returning a rate limit error
+    ///
+    /// Fails immediately if the request can never be satisfied (e.g. `tokens > max_tokens`).
+    async fn acquire_wait(&self, tokens: u32) -> Result<(), Error>;
+
+    /// Acquire tokens, waiting for refill for at most `timeout`
+    ///
+    /// Returns `Error::RateLimit` with the remaining wait if the deadline passes first.
+    async fn acquire_timeout(&self, tokens: u32, timeout: Duration) -> Result<(), Error>;
+
     /// Get available tokens
     fn available_tokens(&self) -> f64;
+
+    /// Seconds until `tokens` can be acquired (0 if available now), without consuming any
+    fn time_until_available(&self, tokens: u32) -> u64;
+
+    /// Refill the bucket to full capacity
+    fn reset(&self);
+
+    /// Counters of granted and denied acquires since startup
+    fn metrics(&self) -> RateLimiterMetrics;
 }
 
 #[async_trait::async_trait]
 impl RateLimiterTrait for RateLimiter {
     async fn acquire(&self, tokens: u32) -> Result<(), Error> {
-        let mut bucket = self.bucket.lock().unwrap();
-        bucket
-            .try_acquire(tokens)
-            .map_err(|retry_after_seconds| Error::RateLimit {
-                retry_after_seconds,
-            })
+        let result = self.bucket.lock().unwrap().try_acquire(tokens);
+        match result {
+            Ok(()) => {
+                self.record_granted(tokens);
+                Ok(())
+            }
+            Err(retry_after_seconds) => {
+                self.record_denied();
+                Err(Error::RateLimit {
+                    retry_after_seconds,
+                })
+            }
+        }
+    }
+
+    async fn acquire_wait(&self, tokens: u32) -> Result<(), Error> {
+        // Only the waiter at the head of the queue polls the bucket, so a refill
+        // is never raced for by several waiters
+        let _turn = self.waiters.lock().await;
+
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                if bucket.try_acquire(tokens).is_ok() {
+                    self.record_granted(tokens);
+                    return Ok(());
+                }
+                if !bucket.can_satisfy(tokens) {
+                    self.record_denied();
+                    return Err(Error::InvalidInput(format!(
+                        "Cannot acquire {} tokens: exceeds what the rate limiter can ever provide",
+                        tokens
+                    )));
+                }
+                bucket.wait_time(tokens)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    async fn acquire_timeout(&self, tokens: u32, timeout: Duration) -> Result<(), Error> {
+        match tokio::time::timeout(timeout, self.acquire_wait(tokens)).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.record_denied();
+                Err(Error::RateLimit {
+                    retry_after_seconds: self.time_until_available(tokens),
+                })
+            }
+        }
     }
 
     fn available_tokens(&self) -> f64 {
@@ -100,12 +241,30 @@ impl RateLimiterTrait for RateLimiter {
         bucket.refill();
         bucket.available()
     }
+
+    fn time_until_available(&self, tokens: u32) -> u64 {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill();
+        bucket.seconds_until_available(tokens)
+    }
+
+    fn reset(&self) {
+        RateLimiter::reset(self);
+    }
+
+    fn metrics(&self) -> RateLimiterMetrics {
+        RateLimiterMetrics {
+            total_acquired: self.total_acquired.load(Ordering::Relaxed),
+            total_denied: self.total_denied.load(Ordering::Relaxed),
+            total_tokens_granted: self.total_tokens_granted.load(Ordering::Relaxed),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::time::{Duration, sleep};
+    use tokio::time::sleep;
 
     fn test_config(max_tokens: u32, refill_rate: f64) -> RateLimitConfig {
         RateLimitConfig {
@@ -239,6 +398,233 @@ mod tests {
         }
     }
 
+    // ========================================
+    // Metrics Tests
+    // ========================================
+
+    #[test]
+    fn new_limiter_has_zero_metrics() {
+        let limiter = RateLimiter::new(&test_config(50, 2.0));
+        assert_eq!(limiter.metrics(), RateLimiterMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn metrics_count_granted_and_denied_acquires() {
+        let limiter = RateLimiter::new(&test_config(10, 0.0));
+
+        limiter.acquire(4).await.unwrap();
+        limiter.acquire(5).await.unwrap();
+        assert!(limiter.acquire(5).await.is_err());
+        assert!(limiter.acquire(2).await.is_err());
+        limiter.acquire(1).await.unwrap();
+
+        assert_eq!(
+            limiter.metrics(),
+            RateLimiterMetrics {
+                total_acquired: 3,
+                total_denied: 2,
+                total_tokens_granted: 10,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_count_wait_and_timeout_outcomes() {
+        let limiter = RateLimiter::new(&test_config(5, 0.01));
+
+        limiter.acquire_wait(5).await.unwrap();
+        assert!(limiter.acquire_wait(10).await.is_err());
+        assert!(
+            limiter
+                .acquire_timeout(1, Duration::from_millis(10))
+                .await
+                .is_err()
+        );
+
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.total_acquired, 1);
+        assert_eq!(metrics.total_tokens_granted, 5);
+        // The timed-out wait is denied once, not once per poll
+        assert_eq!(metrics.total_denied, 2);
+    }
+
+    // ========================================
+    // Time Until Available Tests
+    // ========================================
+
+    #[test]
+    fn time_until_available_is_zero_when_tokens_available() {
+        let config = test_config(50, 2.0);
+        let limiter = RateLimiter::new(&config);
+
+        assert_eq!(limiter.time_until_available(10), 0);
+    }
+
+    #[tokio::test]
+    async fn time_until_available_when_depleted() {
+        let config = test_config(10, 2.0);
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(10).await.unwrap();
+
+        // Need 4 tokens at 2/sec = 2 seconds
+        assert_eq!(limiter.time_until_available(4), 2);
+        // Checking does not consume tokens
+        assert!(limiter.available_tokens() < 1.0);
+    }
+
+    #[test]
+    fn retry_after_is_none_when_tokens_available() {
+        let config = test_config(50, 2.0);
+        let limiter = RateLimiter::new(&config);
+
+        assert_eq!(limiter.retry_after(10), None);
+    }
+
+    #[tokio::test]
+    async fn retry_after_repeated_calls_do_not_drain_bucket() {
+        let config = test_config(10, 2.0);
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(6).await.unwrap();
+
+        for _ in 0..5 {
+            assert_eq!(limiter.retry_after(4), None);
+        }
+        // Need 2 more tokens at 2/sec = 1 second
+        assert_eq!(limiter.retry_after(6), Some(1));
+
+        let available = limiter.available_tokens();
+        assert!((3.9..=4.1).contains(&available)); // Allow for timing variance
+    }
+
+    // ========================================
+    // Reset Tests
+    // ========================================
+
+    #[tokio::test]
+    async fn reset_refills_depleted_bucket() {
+        let config = test_config(50, 0.0); // No refill
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(50).await.unwrap();
+        assert_eq!(limiter.available_tokens(), 0.0);
+
+        limiter.reset();
+
+        assert_eq!(limiter.available_tokens(), 50.0);
+    }
+
+    #[test]
+    fn mock_reset_can_be_asserted() {
+        let mut mock = MockRateLimiterTrait::new();
+        mock.expect_reset().times(1).return_const(());
+
+        mock.reset();
+    }
+
+    // ========================================
+    // Acquire Wait Tests
+    // ========================================
+
+    #[tokio::test]
+    async fn acquire_wait_succeeds_immediately_when_available() {
+        let config = test_config(50, 2.0);
+        let limiter = RateLimiter::new(&config);
+
+        limiter.acquire_wait(10).await.unwrap();
+
+        let available = limiter.available_tokens();
+        assert!((39.9..=40.1).contains(&available)); // Allow for timing variance
+    }
+
+    #[tokio::test]
+    async fn acquire_wait_waits_for_refill() {
+        let config = test_config(10, 20.0); // 20 tokens/sec
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(10).await.unwrap();
+
+        let start = std::time::Instant::now();
+        limiter.acquire_wait(5).await.unwrap();
+
+        // 5 tokens at 20/sec = 250ms
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn acquire_wait_more_than_max_fails_immediately() {
+        let config = test_config(10, 2.0);
+        let limiter = RateLimiter::new(&config);
+
+        let result = limiter.acquire_wait(11).await;
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn acquire_wait_without_refill_fails_when_depleted() {
+        let config = test_config(10, 0.0); // No refill
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(10).await.unwrap();
+
+        let result = limiter.acquire_wait(1).await;
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn concurrent_acquire_wait_waiters_all_succeed() {
+        let config = test_config(4, 20.0); // 20 tokens/sec
+        let limiter = Arc::new(RateLimiter::new(&config));
+        limiter.acquire(4).await.unwrap();
+
+        let mut handles = vec![];
+        for _ in 0..2 {
+            let limiter_clone = Arc::clone(&limiter);
+            handles.push(tokio::spawn(
+                async move { limiter_clone.acquire_wait(4).await },
+            ));
+        }
+
+        for handle in handles {
+            assert!(handle.await.unwrap().is_ok());
+        }
+
+        // Each waiter consumed its own refill: nothing left over
+        assert!(limiter.available_tokens() < 1.0);
+    }
+
+    // ========================================
+    // Acquire Timeout Tests
+    // ========================================
+
+    #[tokio::test]
+    async fn acquire_timeout_succeeds_when_tokens_arrive_before_deadline() {
+        let config = test_config(10, 10.0); // 10 tokens/sec
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(10).await.unwrap();
+
+        // 4 tokens arrive after ~400ms, just inside the deadline
+        let result = limiter.acquire_timeout(4, Duration::from_millis(500)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn acquire_timeout_expires_with_retry_after() {
+        let config = test_config(10, 1.0); // 1 token/sec
+        let limiter = RateLimiter::new(&config);
+        limiter.acquire(10).await.unwrap();
+
+        let start = std::time::Instant::now();
+        let result = limiter.acquire_timeout(5, Duration::from_millis(200)).await;
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        match result {
+            Err(Error::RateLimit {
+                retry_after_seconds,
+            }) => {
+                // ~4.8 tokens still missing at 1/sec
+                assert_eq!(retry_after_seconds, 5);
+            }
+            _ => panic!("Expected RateLimit error"),
+        }
+    }
+
     // ========================================
     // Refill Over Time Tests
     // ========================================
@@ -1,9 +1,14 @@
 pub mod auth;
+pub mod cache;
 pub mod client;
 pub mod types;
+pub mod watch;
 
-pub use client::TelegramClient;
+pub use cache::{ChannelCache, PeerCache};
+pub use client::{ConnectionParams, TelegramClient, resolve_channels, subscribed_channels_stream};
 pub use types::{
-    Channel, ChannelId, ChannelName, MediaType, Message, MessageId, QueryMetadata, SearchParams,
-    SearchResult, UserId, Username,
+    Channel, ChannelBlocklist, ChannelFolder, ChannelId, ChannelIdentifier, ChannelName,
+    ChannelSort, ChannelStats, MatchMode, MediaType, Message, MessageId, PhoneNumber,
+    QueryMetadata, RankMode, SearchParams, SearchResult, UserId, UserProfile, Username,
 };
+pub use watch::{UpdateSource, watch_updates};
@@ -1,8 +1,22 @@
+use crate::config::TelegramConfig;
 use crate::error::Error;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use dialoguer::{Input, Password};
-use grammers_client::{Client, SignInError};
+use grammers_client::grammers_tl_types as tl;
+use grammers_client::{Client, InvocationError, LoginToken, PasswordToken, SignInError};
+use qrcode::QrCode;
+use qrcode::render::unicode;
+use secrecy::{ExposeSecret, SecretString};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+
+/// How often to poll Telegram while waiting for the login QR code to be scanned
+const QR_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How long to wait for the login QR code to be scanned before giving up
+const QR_LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
 
 /// Save a Telegram session to a file with secure permissions (0600)
 ///
@@ -47,16 +61,16 @@ pub fn load_session(path: &Path) -> Result<Vec<u8>, Error> {
         )));
     }
 
-    // Check permissions on Unix (must be 0600)
+    // Check permissions on Unix (no group or other access, e.g. 0600 or 0400)
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let metadata = fs::metadata(path)
             .map_err(|e| Error::Auth(format!("Failed to read session file metadata: {}", e)))?;
         let mode = metadata.permissions().mode() & 0o777;
-        if mode != 0o600 {
+        if mode & 0o077 != 0 {
             return Err(Error::Auth(format!(
-                "Session file has insecure permissions: {:o} (expected 0600)",
+                "Session file has insecure permissions: {:o} (expected no group/other access, e.g. 0600)",
                 mode
             )));
         }
@@ -71,50 +85,386 @@ pub async fn is_session_valid(client: &Client) -> bool {
     client.is_authorized().await.unwrap_or(false)
 }
 
-/// Interactive authentication flow for Telegram
+/// Source of login codes and 2FA passwords (allows headless authentication)
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait CodeProvider: Send + Sync {
+    /// Return the login code sent to the user's Telegram app
+    async fn request_code(&self) -> Result<String, Error>;
+
+    /// Return the 2FA password (only called when the account requires one)
+    async fn request_password(&self) -> Result<String, Error>;
+}
+
+/// Code provider prompting on the terminal via dialoguer
+pub struct TerminalCodeProvider;
+
+#[async_trait::async_trait]
+impl CodeProvider for TerminalCodeProvider {
+    async fn request_code(&self) -> Result<String, Error> {
+        Input::new()
+            .with_prompt("Enter the code you received in Telegram")
+            .interact_text()
+            .map_err(|e| Error::Auth(format!("Failed to read input: {}", e)))
+    }
+
+    async fn request_password(&self) -> Result<String, Error> {
+        Password::new()
+            .with_prompt("Enter your 2FA password")
+            .interact()
+            .map_err(|e| Error::Auth(format!("Failed to read password: {}", e)))
+    }
+}
+
+/// Sign-in steps performed after a login code was requested
+#[cfg_attr(test, mockall::automock(type PasswordToken = ();))]
+#[async_trait::async_trait]
+trait SignIn: Send + Sync {
+    type PasswordToken: Send;
+
+    /// Sign in with the code, returning a password token if 2FA is required
+    async fn sign_in(&self, code: &str) -> Result<Option<Self::PasswordToken>, Error>;
+
+    /// Complete 2FA with the password
+    async fn check_password(&self, token: Self::PasswordToken, password: &str)
+    -> Result<(), Error>;
+}
+
+/// Sign-in backed by a grammers client and login token
+struct GrammersSignIn<'a> {
+    client: &'a Client,
+    token: LoginToken,
+}
+
+#[async_trait::async_trait]
+impl SignIn for GrammersSignIn<'_> {
+    type PasswordToken = PasswordToken;
+
+    async fn sign_in(&self, code: &str) -> Result<Option<PasswordToken>, Error> {
+        match self.client.sign_in(&self.token, code).await {
+            Ok(_) => Ok(None),
+            Err(SignInError::PasswordRequired(password_token)) => Ok(Some(password_token)),
+            Err(e) => Err(Error::Auth(format!("Sign in failed: {}", e))),
+        }
+    }
+
+    async fn check_password(&self, token: PasswordToken, password: &str) -> Result<(), Error> {
+        self.client
+            .check_password(token, password)
+            .await
+            .map(|_| ())
+            .map_err(|e| Error::Auth(format!("2FA authentication failed: {}", e)))
+    }
+}
+
+/// QR-code login flow for Telegram (no phone code needed)
+///
+/// Shows a QR code on stderr to scan from Telegram on another device
+/// (Settings → Devices → Link Desktop Device), then polls until it is approved
+/// or `QR_LOGIN_TIMEOUT` passes. Asks the provider for the 2FA password if the
+/// account has one.
+pub async fn authenticate_qr(
+    client: &Client,
+    api_id: i32,
+    api_hash: &str,
+    provider: &impl CodeProvider,
+) -> Result<(), Error> {
+    let login = GrammersQrLogin {
+        client,
+        request: tl::functions::auth::ExportLoginToken {
+            api_id,
+            api_hash: api_hash.to_string(),
+            except_ids: Vec::new(),
+        },
+    };
+
+    match wait_for_qr_login(&login, QR_POLL_INTERVAL, QR_LOGIN_TIMEOUT).await? {
+        QrPoll::PasswordRequired => {
+            let password_token = fetch_password_token(client).await?;
+            let password = provider.request_password().await?;
+            client
+                .check_password(password_token, password.trim())
+                .await
+                .map_err(|e| Error::Auth(format!("2FA authentication failed: {}", e)))?;
+
+            tracing::info!("Successfully authenticated with QR code and 2FA");
+        }
+        _ => tracing::info!("Successfully authenticated with QR code"),
+    }
+    Ok(())
+}
+
+/// State of a QR login after one poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum QrPoll {
+    /// Not scanned yet; the token to show as a QR code
+    Pending(Vec<u8>),
+    Success,
+    /// Scanned, but the account needs its 2FA password
+    PasswordRequired,
+}
+
+/// QR login polling (allows mocking the network call)
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+trait QrLogin: Send + Sync {
+    async fn poll(&self) -> Result<QrPoll, Error>;
+}
+
+/// QR login backed by a grammers client
+struct GrammersQrLogin<'a> {
+    client: &'a Client,
+    request: tl::functions::auth::ExportLoginToken,
+}
+
+#[async_trait::async_trait]
+impl QrLogin for GrammersQrLogin<'_> {
+    async fn poll(&self) -> Result<QrPoll, Error> {
+        match self.client.invoke(&self.request).await {
+            Ok(tl::enums::auth::LoginToken::Token(token)) => Ok(QrPoll::Pending(token.token)),
+            Ok(tl::enums::auth::LoginToken::Success(_)) => Ok(QrPoll::Success),
+            Ok(tl::enums::auth::LoginToken::MigrateTo(migrate)) => Err(Error::Auth(format!(
+                "QR login requires switching to DC {}, which is not supported; use phone login",
+                migrate.dc_id
+            ))),
+            Err(InvocationError::Rpc(rpc)) if rpc.is("SESSION_PASSWORD_NEEDED") => {
+                Ok(QrPoll::PasswordRequired)
+            }
+            Err(e) => Err(Error::Auth(format!("QR login failed: {}", e))),
+        }
+    }
+}
+
+/// Poll until the QR code is scanned, showing it again whenever a new token is issued
 ///
-/// This prompts the user for:
+/// Returns `Success` or `PasswordRequired`; gives up after `timeout`.
+async fn wait_for_qr_login(
+    login: &impl QrLogin,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<QrPoll, Error> {
+    let polling = async {
+        let mut shown_token: Option<Vec<u8>> = None;
+        loop {
+            match login.poll().await? {
+                QrPoll::Pending(token) => {
+                    // Tokens expire; show the QR code again whenever a new one is issued
+                    if shown_token.as_ref() != Some(&token) {
+                        let qr = render_qr(&qr_login_url(&token))?;
+                        eprintln!(
+                            "Scan this QR code in Telegram (Settings → Devices):\n{}",
+                            qr
+                        );
+                        shown_token = Some(token);
+                    }
+                    tokio::time::sleep(poll_interval).await;
+                }
+                done => return Ok(done),
+            }
+        }
+    };
+
+    tokio::time::timeout(timeout, polling)
+        .await
+        .map_err(|_| Error::Auth("QR login timed out".to_string()))?
+}
+
+/// Fetch the account's 2FA settings needed to check the password
+async fn fetch_password_token(client: &Client) -> Result<PasswordToken, Error> {
+    let tl::enums::account::Password::Password(password) = client
+        .invoke(&tl::functions::account::GetPassword {})
+        .await
+        .map_err(|e| Error::Auth(format!("Failed to fetch 2FA settings: {}", e)))?;
+
+    Ok(PasswordToken::new(password))
+}
+
+/// Build the `tg://login` URL encoded in the login QR code
+fn qr_login_url(token: &[u8]) -> String {
+    format!("tg://login?token={}", URL_SAFE_NO_PAD.encode(token))
+}
+
+/// Render a URL as a QR code made of Unicode half blocks
+fn render_qr(url: &str) -> Result<String, Error> {
+    let code = QrCode::new(url.as_bytes())
+        .map_err(|e| Error::Auth(format!("Failed to render QR code: {}", e)))?;
+
+    Ok(code.render::<unicode::Dense1x2>().quiet_zone(true).build())
+}
+
+/// Server-side session revocation (allows mocking the network call)
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+trait SignOut: Send + Sync {
+    async fn sign_out(&self) -> Result<(), Error>;
+}
+
+#[async_trait::async_trait]
+impl SignOut for Client {
+    async fn sign_out(&self) -> Result<(), Error> {
+        Client::sign_out(self)
+            .await
+            .map(|_| ())
+            .map_err(|e| Error::Auth(format!("Failed to sign out: {}", e)))
+    }
+}
+
+/// Log out of Telegram and delete the local session file
+///
+/// The session file is removed even if the server-side sign out fails, so
+/// local credentials never outlive a logout; the sign out error is still returned.
+pub async fn logout(client: &Client, session_path: &Path) -> Result<(), Error> {
+    complete_logout(client, session_path).await
+}
+
+async fn complete_logout(sign_out: &impl SignOut, session_path: &Path) -> Result<(), Error> {
+    let signed_out = sign_out.sign_out().await;
+    if let Err(e) = &signed_out {
+        tracing::warn!(
+            "Server sign out failed, removing local session anyway: {}",
+            e
+        );
+    }
+
+    remove_session(session_path)?;
+    signed_out?;
+
+    tracing::info!("Logged out and removed session file");
+    Ok(())
+}
+
+/// Overwrite the session file with zeros (best effort) and delete it
+fn remove_session(path: &Path) -> Result<(), Error> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    // Best effort: on copy-on-write or journaling filesystems old blocks may survive
+    if let Ok(metadata) = fs::metadata(path) {
+        let zeros = vec![0u8; metadata.len() as usize];
+        if let Err(e) = fs::write(path, &zeros) {
+            tracing::warn!("Failed to overwrite session file before removal: {}", e);
+        }
+    }
+
+    fs::remove_file(path).map_err(|e| Error::Auth(format!("Failed to remove session file: {}", e)))
+}
+
+/// Sign in as a bot with a token from @BotFather
+///
+/// Bots skip the phone code and 2FA steps but can only read public channels.
+pub async fn authenticate_bot(client: &Client, token: &SecretString) -> Result<(), Error> {
+    client
+        .bot_sign_in(token.expose_secret(), "")
+        .await
+        .map_err(|e| Error::Auth(format!("Bot sign in failed: {}", e)))?;
+
+    tracing::info!("Successfully authenticated as bot");
+    Ok(())
+}
+
+/// Sign in with the configured credentials: the bot token when set, otherwise the phone flow
+pub async fn authenticate_configured(
+    client: &Client,
+    config: &TelegramConfig,
+    provider: &impl CodeProvider,
+) -> Result<(), Error> {
+    match (&config.bot_token, &config.phone_number) {
+        (Some(token), _) => authenticate_bot(client, token).await,
+        (None, Some(phone)) => authenticate(client, phone.expose_secret(), provider).await,
+        (None, None) => Err(Error::Auth(
+            "No phone_number or bot_token configured".to_string(),
+        )),
+    }
+}
+
+/// Authentication flow for Telegram
+///
+/// Asks the code provider for:
 /// - Authentication code (sent to Telegram app)
 /// - 2FA password (if enabled on account)
 ///
-/// The phone number should already be used when requesting the login code.
+/// Use `TerminalCodeProvider` for interactive prompts.
 ///
 /// Returns Ok(()) if authentication succeeds.
-pub async fn authenticate(client: &Client, phone: &str) -> Result<(), Error> {
+pub async fn authenticate(
+    client: &Client,
+    phone: &str,
+    provider: &impl CodeProvider,
+) -> Result<(), Error> {
+    let sign_in = start_sign_in(client, phone).await?;
+    complete_sign_in(&sign_in, provider).await
+}
+
+/// Authenticate and save the session to `session_path` on success
+pub async fn authenticate_and_persist(
+    client: &Client,
+    phone: &str,
+    session_path: &Path,
+    provider: &impl CodeProvider,
+) -> Result<(), Error> {
+    let sign_in = start_sign_in(client, phone).await?;
+    sign_in_and_persist(&sign_in, provider, client, session_path).await
+}
+
+/// Request a login code and prepare the sign-in steps
+async fn start_sign_in<'a>(client: &'a Client, phone: &str) -> Result<GrammersSignIn<'a>, Error> {
     // Request login code (grammers requires phone and code settings)
     let token = client
         .request_login_code(phone, "")
         .await
         .map_err(|e| Error::Auth(format!("Failed to request login code: {}", e)))?;
 
-    // Prompt for code
-    let code: String = Input::new()
-        .with_prompt("Enter the code you received in Telegram")
-        .interact_text()
-        .map_err(|e| Error::Auth(format!("Failed to read input: {}", e)))?;
+    Ok(GrammersSignIn { client, token })
+}
+
+/// Source of serialized session bytes (allows mocking the client session)
+#[cfg_attr(test, mockall::automock)]
+trait SessionSource {
+    fn session_bytes(&self) -> Vec<u8>;
+}
+
+impl SessionSource for Client {
+    fn session_bytes(&self) -> Vec<u8> {
+        self.session().save()
+    }
+}
+
+async fn sign_in_and_persist<S: SignIn>(
+    sign_in: &S,
+    provider: &impl CodeProvider,
+    session: &impl SessionSource,
+    session_path: &Path,
+) -> Result<(), Error> {
+    complete_sign_in(sign_in, provider).await?;
 
-    // Sign in with code
-    match client.sign_in(&token, &code).await {
-        Ok(_) => {
+    save_session(session_path, &session.session_bytes())?;
+    tracing::info!("Session saved to {}", session_path.display());
+    Ok(())
+}
+
+/// Drive the code and optional 2FA password steps
+async fn complete_sign_in<S: SignIn>(
+    sign_in: &S,
+    provider: &impl CodeProvider,
+) -> Result<(), Error> {
+    let code = provider.request_code().await?;
+
+    match sign_in.sign_in(code.trim()).await? {
+        None => {
             tracing::info!("Successfully authenticated");
             Ok(())
         }
-        Err(SignInError::PasswordRequired(password_token)) => {
-            // 2FA is enabled, prompt for password
-            let password = Password::new()
-                .with_prompt("Enter your 2FA password")
-                .interact()
-                .map_err(|e| Error::Auth(format!("Failed to read password: {}", e)))?;
-
-            client
+        Some(password_token) => {
+            // 2FA is enabled, ask for password
+            let password = provider.request_password().await?;
+            sign_in
                 .check_password(password_token, password.trim())
-                .await
-                .map_err(|e| Error::Auth(format!("2FA authentication failed: {}", e)))?;
+                .await?;
 
             tracing::info!("Successfully authenticated with 2FA");
             Ok(())
         }
-        Err(e) => Err(Error::Auth(format!("Sign in failed: {}", e))),
     }
 }
 
@@ -214,6 +564,194 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn qr_login_times_out_when_never_scanned() {
+        let mut login = MockQrLogin::new();
+        login
+            .expect_poll()
+            .returning(|| Ok(QrPoll::Pending(b"token".to_vec())));
+
+        let result =
+            wait_for_qr_login(&login, Duration::from_millis(5), Duration::from_millis(30)).await;
+
+        match result {
+            Err(Error::Auth(message)) => assert_eq!(message, "QR login timed out"),
+            other => panic!("expected timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn qr_login_returns_once_scanned() {
+        let mut login = MockQrLogin::new();
+        let mut polls = 0;
+        login.expect_poll().times(2).returning(move || {
+            polls += 1;
+            Ok(if polls == 1 {
+                QrPoll::Pending(b"token".to_vec())
+            } else {
+                QrPoll::PasswordRequired
+            })
+        });
+
+        let result =
+            wait_for_qr_login(&login, Duration::from_millis(1), Duration::from_secs(5)).await;
+
+        assert_eq!(result.unwrap(), QrPoll::PasswordRequired);
+    }
+
+    #[test]
+    fn qr_login_url_uses_unpadded_base64url_token() {
+        // 0xfb 0xff encodes to "+/8=" in standard base64
+        let url = qr_login_url(&[0xfb, 0xff]);
+        assert_eq!(url, "tg://login?token=-_8");
+    }
+
+    #[test]
+    fn qr_login_url_encodes_full_token() {
+        let url = qr_login_url(b"login-token");
+        assert_eq!(url, "tg://login?token=bG9naW4tdG9rZW4");
+    }
+
+    #[test]
+    fn render_qr_produces_multiline_block() {
+        let qr = render_qr(&qr_login_url(b"login-token")).unwrap();
+        assert!(qr.lines().count() > 10);
+    }
+
+    #[tokio::test]
+    async fn sign_in_and_persist_writes_session_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("test.session");
+
+        let mut provider = MockCodeProvider::new();
+        provider
+            .expect_request_code()
+            .returning(|| Ok("12345".to_string()));
+        let mut sign_in = MockSignIn::new();
+        sign_in.expect_sign_in().times(1).returning(|_| Ok(None));
+        let mut session = MockSessionSource::new();
+        session
+            .expect_session_bytes()
+            .times(1)
+            .returning(|| b"authorized session".to_vec());
+
+        let result = sign_in_and_persist(&sign_in, &provider, &session, &session_path).await;
+
+        assert!(result.is_ok());
+        assert_eq!(load_session(&session_path).unwrap(), b"authorized session");
+    }
+
+    #[tokio::test]
+    async fn sign_in_and_persist_skips_save_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("test.session");
+
+        let mut provider = MockCodeProvider::new();
+        provider
+            .expect_request_code()
+            .returning(|| Ok("00000".to_string()));
+        let mut sign_in = MockSignIn::new();
+        sign_in.expect_sign_in().returning(|_| {
+            Err(Error::Auth(
+                "Sign in failed: PHONE_CODE_INVALID".to_string(),
+            ))
+        });
+        let mut session = MockSessionSource::new();
+        session.expect_session_bytes().never();
+
+        let result = sign_in_and_persist(&sign_in, &provider, &session, &session_path).await;
+
+        assert!(result.is_err());
+        assert!(!session_path.exists());
+    }
+
+    #[tokio::test]
+    async fn logout_signs_out_and_removes_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("test.session");
+        save_session(&session_path, b"test session data").unwrap();
+
+        let mut sign_out = MockSignOut::new();
+        sign_out.expect_sign_out().times(1).returning(|| Ok(()));
+
+        let result = complete_logout(&sign_out, &session_path).await;
+
+        assert!(result.is_ok());
+        assert!(!session_path.exists());
+    }
+
+    #[tokio::test]
+    async fn logout_removes_session_even_if_sign_out_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("test.session");
+        save_session(&session_path, b"test session data").unwrap();
+
+        let mut sign_out = MockSignOut::new();
+        sign_out
+            .expect_sign_out()
+            .returning(|| Err(Error::Network("connection reset".to_string())));
+
+        let result = complete_logout(&sign_out, &session_path).await;
+
+        assert!(result.is_err());
+        assert!(!session_path.exists());
+    }
+
+    #[tokio::test]
+    async fn logout_without_session_file_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("missing.session");
+
+        let mut sign_out = MockSignOut::new();
+        sign_out.expect_sign_out().returning(|| Ok(()));
+
+        let result = complete_logout(&sign_out, &session_path).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    fn load_with_mode(mode: u32) -> Result<Vec<u8>, Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let session_path = temp_dir.path().join("test.session");
+        save_session(&session_path, b"test session data").unwrap();
+        fs::set_permissions(&session_path, fs::Permissions::from_mode(mode)).unwrap();
+
+        load_session(&session_path)
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn load_session_accepts_owner_read_only() {
+        assert!(load_with_mode(0o400).is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn load_session_accepts_owner_read_write() {
+        assert!(load_with_mode(0o600).is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn load_session_rejects_group_readable() {
+        assert!(load_with_mode(0o640).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn load_session_rejects_world_readable() {
+        assert!(load_with_mode(0o644).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn load_session_rejects_other_readable_only() {
+        assert!(load_with_mode(0o604).is_err());
+    }
+
     #[test]
     fn save_and_load_round_trip() {
         let temp_dir = TempDir::new().unwrap();
@@ -246,6 +784,76 @@ mod tests {
         assert_eq!(loaded_data, b"version 2");
     }
 
-    // Note: is_session_valid and authenticate tests require a real Telegram client
+    #[tokio::test]
+    async fn complete_sign_in_with_code_only() {
+        let mut provider = MockCodeProvider::new();
+        provider
+            .expect_request_code()
+            .times(1)
+            .returning(|| Ok("12345".to_string()));
+        provider.expect_request_password().never();
+
+        let mut sign_in = MockSignIn::new();
+        sign_in
+            .expect_sign_in()
+            .with(mockall::predicate::eq("12345"))
+            .times(1)
+            .returning(|_| Ok(None));
+        sign_in.expect_check_password().never();
+
+        assert!(complete_sign_in(&sign_in, &provider).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn complete_sign_in_with_code_and_password() {
+        let mut provider = MockCodeProvider::new();
+        provider
+            .expect_request_code()
+            .times(1)
+            .returning(|| Ok(" 12345\n".to_string()));
+        provider
+            .expect_request_password()
+            .times(1)
+            .returning(|| Ok("hunter2\n".to_string()));
+
+        let mut sign_in = MockSignIn::new();
+        sign_in
+            .expect_sign_in()
+            .with(mockall::predicate::eq("12345"))
+            .times(1)
+            .returning(|_| Ok(Some(())));
+        sign_in
+            .expect_check_password()
+            .with(
+                mockall::predicate::eq(()),
+                mockall::predicate::eq("hunter2"),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        assert!(complete_sign_in(&sign_in, &provider).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn complete_sign_in_propagates_provider_error() {
+        let mut provider = MockCodeProvider::new();
+        provider
+            .expect_request_code()
+            .times(1)
+            .returning(|| Err(Error::Auth("no code available".to_string())));
+
+        let mut sign_in = MockSignIn::new();
+        sign_in.expect_sign_in().never();
+
+        let result = complete_sign_in(&sign_in, &provider).await;
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("no code available")
+        );
+    }
+
+    // Note: is_session_valid and the grammers sign-in steps require a real Telegram client
     // and are tested manually or via integration tests
 }
@@ -0,0 +1,240 @@
+use crate::telegram::types::{Channel, ChannelId, Username};
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+
+/// Resolved channels keyed by ID and by public username, expiring after a TTL
+///
+/// Callers pass the current `Instant`, so expiry can be tested without sleeping.
+/// A zero TTL disables caching.
+pub struct ChannelCache {
+    ttl: Duration,
+    by_id: DashMap<ChannelId, CachedChannel>,
+    by_username: DashMap<String, ChannelId>,
+}
+
+struct CachedChannel {
+    channel: Channel,
+    cached_at: Instant,
+}
+
+impl ChannelCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            by_id: DashMap::new(),
+            by_username: DashMap::new(),
+        }
+    }
+
+    /// Cached channel with the given ID, unless missing or expired
+    pub fn get_by_id(&self, id: ChannelId, now: Instant) -> Option<Channel> {
+        if let Some(entry) = self.by_id.get(&id)
+            && self.is_fresh(entry.cached_at, now)
+        {
+            return Some(entry.channel.clone());
+        }
+
+        // Drop the expired entry so the next lookup goes to the network
+        self.by_id
+            .remove_if(&id, |_, entry| !self.is_fresh(entry.cached_at, now));
+        None
+    }
+
+    /// Cached channel with the given public username (case-insensitive)
+    pub fn get_by_username(&self, username: &Username, now: Instant) -> Option<Channel> {
+        let key = username_key(username);
+        let id = *self.by_username.get(&key)?;
+
+        let channel = self.get_by_id(id, now);
+        if channel.is_none() {
+            self.by_username.remove(&key);
+        }
+        channel
+    }
+
+    /// Store a resolved channel; private channels are only keyed by ID
+    pub fn insert(&self, channel: Channel, now: Instant) {
+        if let Some(username) = &channel.username {
+            self.by_username.insert(username_key(username), channel.id);
+        }
+        self.by_id.insert(
+            channel.id,
+            CachedChannel {
+                channel,
+                cached_at: now,
+            },
+        );
+    }
+
+    /// Number of cached channels, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    fn is_fresh(&self, cached_at: Instant, now: Instant) -> bool {
+        now.saturating_duration_since(cached_at) < self.ttl
+    }
+}
+
+/// Resolved Telegram peers keyed by channel ID, expiring after a TTL
+///
+/// Lets ID lookups skip walking the dialog list. Like `ChannelCache`, callers
+/// pass the current `Instant` and a zero TTL disables caching.
+pub struct PeerCache<P> {
+    ttl: Duration,
+    entries: DashMap<ChannelId, (P, Instant)>,
+}
+
+impl<P: Clone> PeerCache<P> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Cached peer for the channel, unless missing or expired
+    pub fn get(&self, id: ChannelId, now: Instant) -> Option<P> {
+        let is_fresh = |cached_at: Instant| now.saturating_duration_since(cached_at) < self.ttl;
+
+        if let Some(entry) = self.entries.get(&id)
+            && is_fresh(entry.1)
+        {
+            return Some(entry.0.clone());
+        }
+
+        self.entries.remove_if(&id, |_, entry| !is_fresh(entry.1));
+        None
+    }
+
+    pub fn insert(&self, id: ChannelId, peer: P, now: Instant) {
+        self.entries.insert(id, (peer, now));
+    }
+}
+
+/// Telegram usernames are case-insensitive
+fn username_key(username: &Username) -> String {
+    username.as_str().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram::types::ChannelName;
+
+    const TTL: Duration = Duration::from_secs(300);
+
+    fn channel(id: i64, username: &str, is_public: bool) -> Channel {
+        Channel {
+            id: ChannelId::new(id).unwrap(),
+            name: ChannelName::new("Test Channel").unwrap(),
+            username: is_public.then(|| Username::new(username).unwrap()),
+            description: None,
+            member_count: 10,
+            is_verified: false,
+            is_public,
+            is_subscribed: true,
+            last_message_date: None,
+        }
+    }
+
+    #[test]
+    fn get_by_id_hits_within_ttl() {
+        let cache = ChannelCache::new(TTL);
+        let start = Instant::now();
+        cache.insert(channel(1, "rust_news", true), start);
+
+        let cached = cache.get_by_id(ChannelId::new(1).unwrap(), start + Duration::from_secs(299));
+
+        assert_eq!(cached.unwrap().id, ChannelId::new(1).unwrap());
+    }
+
+    #[test]
+    fn get_by_id_misses_unknown_channel() {
+        let cache = ChannelCache::new(TTL);
+        cache.insert(channel(1, "rust_news", true), Instant::now());
+
+        assert!(
+            cache
+                .get_by_id(ChannelId::new(2).unwrap(), Instant::now())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn get_by_id_expires_after_ttl() {
+        let cache = ChannelCache::new(TTL);
+        let start = Instant::now();
+        cache.insert(channel(1, "rust_news", true), start);
+
+        let cached = cache.get_by_id(ChannelId::new(1).unwrap(), start + TTL);
+
+        assert!(cached.is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn get_by_username_is_case_insensitive() {
+        let cache = ChannelCache::new(TTL);
+        let start = Instant::now();
+        cache.insert(channel(1, "Rust_News", true), start);
+
+        let cached = cache.get_by_username(&Username::new("@rust_news").unwrap(), start);
+
+        assert_eq!(cached.unwrap().id, ChannelId::new(1).unwrap());
+    }
+
+    #[test]
+    fn get_by_username_expires_after_ttl() {
+        let cache = ChannelCache::new(TTL);
+        let start = Instant::now();
+        cache.insert(channel(1, "rust_news", true), start);
+
+        let username = Username::new("rust_news").unwrap();
+        assert!(cache.get_by_username(&username, start + TTL).is_none());
+        assert!(cache.get_by_username(&username, start).is_none());
+    }
+
+    #[test]
+    fn private_channels_are_not_keyed_by_username() {
+        let cache = ChannelCache::new(TTL);
+        let start = Instant::now();
+        cache.insert(channel(1, "channel_1", false), start);
+
+        assert!(
+            cache
+                .get_by_username(&Username::new("channel_1").unwrap(), start)
+                .is_none()
+        );
+        assert!(cache.get_by_id(ChannelId::new(1).unwrap(), start).is_some());
+    }
+
+    #[test]
+    fn peer_cache_hits_within_ttl_and_expires_after() {
+        let cache = PeerCache::new(TTL);
+        let start = Instant::now();
+        let id = ChannelId::new(1).unwrap();
+        cache.insert(id, "peer", start);
+
+        assert_eq!(
+            cache.get(id, start + Duration::from_secs(299)),
+            Some("peer")
+        );
+        assert_eq!(cache.get(ChannelId::new(2).unwrap(), start), None);
+        assert_eq!(cache.get(id, start + TTL), None);
+        assert_eq!(cache.get(id, start), None);
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let cache = ChannelCache::new(Duration::ZERO);
+        let start = Instant::now();
+        cache.insert(channel(1, "rust_news", true), start);
+
+        assert!(cache.get_by_id(ChannelId::new(1).unwrap(), start).is_none());
+    }
+}
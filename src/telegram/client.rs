@@ -1,9 +1,27 @@
-use crate::config::TelegramConfig;
+use crate::config::{Config, ConnectionConfig};
 use crate::error::Error;
-use crate::telegram::auth::is_session_valid;
-use crate::telegram::types::{Channel, SearchParams, SearchResult};
-use grammers_client::Client;
+use crate::telegram::auth::{is_session_valid, load_session, save_session};
+use crate::telegram::cache::{ChannelCache, PeerCache};
+use crate::telegram::types::{
+    Channel, ChannelBlocklist, ChannelFolder, ChannelId, ChannelIdentifier, ChannelName,
+    ChannelStats, MediaType, Message, MessageId, QueryMetadata, RankMode, SearchParams,
+    SearchResult, UserId, UserProfile, Username, detect_language, normalize_text,
+};
+use crate::telegram::watch::UpdateSource;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use grammers_client::grammers_tl_types as tl;
+use grammers_client::types::{Channel as TgChannel, Chat, Media, Message as TgMessage, PackedChat};
+use grammers_client::{
+    Client, Config as ClientConfig, InitParams, InvocationError, ReconnectionPolicy, RpcError,
+    Update,
+};
+use grammers_session::Session;
+use secrecy::ExposeSecret;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Trait for Telegram client operations (allows mocking in tests)
 #[cfg_attr(test, mockall::automock)]
@@ -15,17 +33,361 @@ pub trait TelegramClientTrait: Send + Sync {
     /// Get information about a specific channel by username or ID
     async fn get_channel_info(&self, identifier: &str) -> Result<Channel, Error>;
 
-    /// Get list of subscribed channels with pagination
-    async fn get_subscribed_channels(&self, limit: u32, offset: u32)
-    -> Result<Vec<Channel>, Error>;
+    /// Resolve several channels concurrently; results follow the input order
+    ///
+    /// A failed identifier yields an error in its slot without aborting the batch.
+    async fn get_channels_info(&self, identifiers: &[String]) -> Vec<Result<Channel, Error>>;
+
+    /// Get a page of subscribed channels
+    ///
+    /// Returns the page and whether more channels exist after it.
+    async fn get_subscribed_channels(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<Channel>, bool), Error>;
+
+    /// Get every subscribed channel in a single pass over the dialog list
+    async fn get_all_subscribed_channels(&self) -> Result<Vec<Channel>, Error>;
+
+    /// Get recent messages from a channel, newest first
+    ///
+    /// `before_id` restricts results to messages older than that ID (for paging back).
+    async fn get_channel_history(
+        &self,
+        channel: ChannelId,
+        limit: u32,
+        before_id: Option<MessageId>,
+    ) -> Result<Vec<Message>, Error>;
+
+    /// Get messages in a channel that reply to the given message, oldest first
+    async fn get_replies(
+        &self,
+        channel: ChannelId,
+        message: MessageId,
+    ) -> Result<Vec<Message>, Error>;
+
+    /// Count a channel's messages from the last `hours_back` hours
+    async fn channel_stats(
+        &self,
+        channel: ChannelId,
+        hours_back: u32,
+    ) -> Result<ChannelStats, Error>;
+
+    /// Get the user's dialog folders (dialog filters) with their channels
+    async fn get_folders(&self) -> Result<Vec<ChannelFolder>, Error>;
+
+    /// Forward a message to Saved Messages (`to = "saved"`) or another chat
+    ///
+    /// Returns the ID of the forwarded copy when Telegram reports it.
+    async fn forward_message(
+        &self,
+        from_channel: ChannelId,
+        message_id: MessageId,
+        to: &str,
+    ) -> Result<Option<MessageId>, Error>;
+
+    /// Post a text message to a channel the user can write to
+    ///
+    /// Returns the ID of the new message.
+    async fn send_message(&self, channel: ChannelId, text: &str) -> Result<MessageId, Error>;
+
+    /// Download the media attached to a message into `dest_dir`
+    ///
+    /// Returns the path of the saved file.
+    async fn download_media(
+        &self,
+        channel: ChannelId,
+        message: MessageId,
+        dest_dir: &Path,
+    ) -> Result<PathBuf, Error>;
 
     /// Check if client is connected and authorized
     async fn is_connected(&self) -> bool;
+
+    /// Round-trip a cheap request to Telegram and return its latency
+    async fn ping(&self) -> Result<Duration, Error>;
+
+    /// Get the account the session is signed in as
+    async fn get_me(&self) -> Result<UserProfile, Error>;
+
+    /// Re-establish the Telegram connection using the existing session
+    async fn reconnect(&self) -> Result<(), Error>;
+
+    /// Persist the current session to the configured session file
+    async fn save_session(&self) -> Result<(), Error>;
+}
+
+/// Run `operation`, reconnecting once and retrying if it fails with a network error
+pub async fn with_reconnect<C, T, F, Fut>(client: &C, mut operation: F) -> Result<T, Error>
+where
+    C: TelegramClientTrait + ?Sized,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    match operation().await {
+        Err(Error::Network(reason)) => {
+            tracing::warn!("Network error, reconnecting to Telegram: {}", reason);
+            client.reconnect().await?;
+            operation().await
+        }
+        result => result,
+    }
+}
+
+/// Settings used to open the grammers connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionParams {
+    pub api_id: i32,
+    /// SOCKS5 proxy URL passed to grammers, if configured
+    pub proxy_url: Option<String>,
+    /// Retry policy for the connect step
+    pub retry: ConnectionConfig,
+    /// Session labels shown in Telegram's active sessions list
+    pub device_model: Option<String>,
+    pub system_version: Option<String>,
+    pub app_version: Option<String>,
+    /// Policy grammers uses to redial dropped connections (the `retry` policy,
+    /// leaked once because grammers needs a `'static` reference)
+    pub reconnection_policy: &'static ConnectionConfig,
+}
+
+impl ConnectionParams {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            api_id: config.telegram.api_id,
+            proxy_url: config.telegram.proxy.clone(),
+            retry: config.connection.clone(),
+            device_model: config.telegram.device_model.clone(),
+            system_version: config.telegram.system_version.clone(),
+            app_version: config.telegram.app_version.clone(),
+            reconnection_policy: Box::leak(Box::new(config.connection.clone())),
+        }
+    }
+
+    /// grammers init params, keeping its defaults for labels that aren't configured
+    pub fn init_params(&self) -> InitParams {
+        let mut params = InitParams {
+            proxy_url: self.proxy_url.clone(),
+            reconnection_policy: self.reconnection_policy,
+            ..InitParams::default()
+        };
+        if let Some(device_model) = &self.device_model {
+            params.device_model = device_model.clone();
+        }
+        if let Some(system_version) = &self.system_version {
+            params.system_version = system_version.clone();
+        }
+        if let Some(app_version) = &self.app_version {
+            params.app_version = app_version.clone();
+        }
+        params
+    }
+
+    /// grammers connection config for `session`, carrying `init_params`
+    pub fn client_config(&self, session: Session, api_hash: String) -> ClientConfig {
+        ClientConfig {
+            session,
+            api_id: self.api_id,
+            api_hash,
+            params: self.init_params(),
+        }
+    }
+}
+
+/// Redial dropped connections with the same backoff as the initial connect
+impl ReconnectionPolicy for ConnectionConfig {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        let attempts = u32::try_from(attempts).unwrap_or(u32::MAX);
+        if attempts >= self.max_attempts {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(self.backoff_delay(attempts.saturating_add(1)))
+        }
+    }
+}
+
+/// Run `connect`, retrying retryable failures with exponential backoff
+///
+/// Non-retryable errors are returned immediately; once all attempts are used
+/// up the last failure is reported as `Error::Network`.
+pub async fn connect_with_retry<T, F, Fut>(
+    policy: &ConnectionConfig,
+    mut connect: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        match connect().await {
+            Ok(value) => return Ok(value),
+            Err(e) if !e.is_retryable() => return Err(e),
+            Err(e) if attempt >= max_attempts => {
+                return Err(Error::Network(format!(
+                    "failed to connect after {} attempts: {}",
+                    max_attempts, e
+                )));
+            }
+            Err(e) => {
+                let delay = e
+                    .retry_after()
+                    .unwrap_or_else(|| policy.backoff_delay(attempt));
+                tracing::warn!(
+                    "Connection attempt {}/{} failed: {}; retrying in {:?}",
+                    attempt,
+                    max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Channels requested per page by `subscribed_channels_stream`
+pub const CHANNEL_STREAM_PAGE_SIZE: u32 = 100;
+
+/// All subscribed channels, fetched page by page as the stream is polled
+///
+/// A failed page is yielded as an error and ends the stream. Each page is a
+/// separate `get_subscribed_channels` call; `TelegramClient` callers should use
+/// its inherent stream, which walks the dialog list once.
+pub fn subscribed_channels_stream<C>(
+    client: &C,
+    page_size: u32,
+) -> impl Stream<Item = Result<Channel, Error>> + '_
+where
+    C: TelegramClientTrait + ?Sized,
+{
+    stream::unfold(Some(0u32), move |offset| async move {
+        let offset = offset?;
+        match client.get_subscribed_channels(page_size, offset).await {
+            Ok((channels, has_more)) => {
+                // An empty page would repeat the same offset forever
+                let next = (has_more && !channels.is_empty())
+                    .then(|| offset.saturating_add(channels.len() as u32));
+                let items: Vec<_> = channels.into_iter().map(Ok).collect();
+                Some((stream::iter(items), next))
+            }
+            Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+        }
+    })
+    .flatten()
+}
+
+/// Channels resolved at once by `get_channels_info`
+const CHANNEL_INFO_CONCURRENCY: usize = 4;
+
+/// Resolve channels with at most `concurrency` lookups in flight, keeping input order
+pub async fn resolve_channels<C>(
+    client: &C,
+    identifiers: &[String],
+    concurrency: usize,
+) -> Vec<Result<Channel, Error>>
+where
+    C: TelegramClientTrait + ?Sized,
+{
+    // Futures are lazy, so building them up front starts no lookups yet
+    let lookups: Vec<_> = identifiers
+        .iter()
+        .map(|identifier| client.get_channel_info(identifier))
+        .collect();
+
+    stream::iter(lookups)
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Run `search` over every channel with at most `concurrency` searches in flight
+///
+/// Flood waits are account-wide, so one stops the remaining channels but keeps the
+/// results gathered so far; any other error fails the whole search. Returns the
+/// messages and the number of channels fully searched.
+async fn search_channels_bounded<'a, T, F, Fut>(
+    channels: &'a [T],
+    concurrency: usize,
+    search: F,
+) -> Result<(Vec<Message>, u32), Error>
+where
+    F: Fn(&'a T) -> Fut,
+    Fut: Future<Output = Result<Vec<Message>, Error>>,
+{
+    // Futures are lazy, so building them up front starts no searches yet
+    let searches: Vec<Fut> = channels.iter().map(search).collect();
+    let mut pending = stream::iter(searches).buffer_unordered(concurrency.max(1));
+
+    let mut messages = Vec::new();
+    let mut channels_searched = 0;
+    while let Some(result) = pending.next().await {
+        match result {
+            Ok(found) => {
+                messages.extend(found);
+                channels_searched += 1;
+            }
+            Err(e @ Error::RateLimit { .. }) => {
+                tracing::warn!("Search stopped early due to flood wait: {}", e);
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok((messages, channels_searched))
+}
+
+/// Search hits scanned per channel and term before giving up on filling the limit
+const MAX_SEARCH_SCAN: usize = 2000;
+
+/// Take search hits (newest first) until `params.limit` of them pass the filters
+///
+/// Telegram caps each page, so filtering only the first page could return fewer
+/// matches than exist. Stops at the first hit older than `cutoff`, on the first
+/// error, or after `MAX_SEARCH_SCAN` hits.
+async fn collect_search_hits<S>(
+    hits: S,
+    params: &SearchParams,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<Message>, Error>
+where
+    S: Stream<Item = Result<Message, Error>>,
+{
+    let limit = params.limit as usize;
+    let mut hits = std::pin::pin!(hits.take(MAX_SEARCH_SCAN));
+    let mut messages = Vec::new();
+
+    while messages.len() < limit
+        && let Some(message) = hits.next().await.transpose()?
+    {
+        if message.timestamp < cutoff {
+            break;
+        }
+        if params.accepts(&message) {
+            messages.push(message);
+        }
+    }
+
+    Ok(messages)
 }
 
 /// Telegram client wrapping grammers-client
 pub struct TelegramClient {
     client: Arc<Client>,
+    session_file: PathBuf,
+    max_stored_text_chars: usize,
+    /// Channels searched at once when a search spans all subscriptions
+    search_concurrency: usize,
+    /// Retry policy for reconnecting a dropped update stream
+    reconnect_policy: ConnectionConfig,
+    channel_cache: ChannelCache,
+    /// Dialog channels seen while walking the dialog list, with their latest message date
+    dialog_cache: PeerCache<(TgChannel, Option<DateTime<Utc>>)>,
 }
 
 impl TelegramClient {
@@ -33,30 +395,333 @@ impl TelegramClient {
     ///
     /// This handles both first-time setup (no session) and returning users (with session).
     /// If session exists, it will be loaded and used. Otherwise, a new session is created.
+    /// The connect step is retried following `config.connection` (see `connect_with_retry`).
     ///
     /// After creation, check `is_connected()` to determine if authentication is needed.
+    pub async fn new(config: &Config) -> Result<Self, Error> {
+        let params = ConnectionParams::from_config(config);
+        let session_file = config.telegram.session_file.clone();
+        let session_bytes = if session_file.exists() {
+            Some(load_session(&session_file)?)
+        } else {
+            None
+        };
+
+        tracing::debug!(
+            api_id = params.api_id,
+            proxy = params.proxy_url.is_some(),
+            session = session_bytes.is_some(),
+            channel_cache_ttl_secs = config.telegram.channel_cache_ttl_secs,
+            "Connecting to Telegram"
+        );
+
+        let client = connect_with_retry(&params.retry, || async {
+            let session = match &session_bytes {
+                Some(bytes) => Session::load(bytes)
+                    .map_err(|e| Error::Auth(format!("Failed to load session: {}", e)))?,
+                None => Session::new(),
+            };
+            let api_hash = config.telegram.api_hash.expose_secret().to_string();
+            Client::connect(params.client_config(session, api_hash))
+                .await
+                .map_err(|e| Error::Network(format!("Failed to connect to Telegram: {}", e)))
+        })
+        .await?;
+
+        Ok(Self {
+            client: Arc::new(client),
+            session_file,
+            max_stored_text_chars: config.search.max_stored_text_chars,
+            search_concurrency: config.search.search_concurrency,
+            reconnect_policy: params.retry,
+            channel_cache: ChannelCache::new(config.telegram.channel_cache_ttl()),
+            dialog_cache: PeerCache::new(config.telegram.channel_cache_ttl()),
+        })
+    }
+
+    /// All subscribed channels as a stream, from a single pass over the dialog list
     ///
-    /// # Implementation Note
-    ///
-    /// Full grammers integration requires:
-    /// 1. Loading/creating session
-    /// 2. Connecting to Telegram with api_id and api_hash
-    /// 3. Checking authorization status
-    ///
-    /// This will be fully implemented during integration testing (Phase 12)
-    /// when we have actual Telegram API credentials.
-    pub async fn new(_config: &TelegramConfig) -> Result<Self, Error> {
-        // Stub implementation - full grammers integration pending
-        // TODO: Implement full grammers client connection in Phase 12
-        Err(Error::TelegramApi(
-            "TelegramClient::new() requires real Telegram API credentials - will be implemented in Phase 12 integration testing".to_string()
-        ))
+    /// A failed dialog page is yielded as an error and ends the stream.
+    pub fn subscribed_channels_stream(&self) -> impl Stream<Item = Result<Channel, Error>> + '_ {
+        stream::unfold(Some(self.client.iter_dialogs()), |dialogs| async move {
+            let mut dialogs = dialogs?;
+            loop {
+                match dialogs.next().await {
+                    Ok(Some(dialog)) => {
+                        let Chat::Channel(channel) = dialog.chat() else {
+                            continue;
+                        };
+                        let last_message_date =
+                            dialog.last_message.as_ref().map(|message| message.date());
+                        let channel = convert_channel(channel, last_message_date);
+                        return Some((channel, Some(dialogs)));
+                    }
+                    Ok(None) => return None,
+                    Err(e) => return Some((Err(dialogs_error(e)), None)),
+                }
+            }
+        })
     }
 
     /// Get access to the underlying grammers client (for session saving)
     pub fn client(&self) -> &Client {
         &self.client
     }
+
+    /// Retry policy used when the update stream has to reconnect
+    pub fn reconnect_policy(&self) -> &ConnectionConfig {
+        &self.reconnect_policy
+    }
+
+    /// Find a channel among the user's dialogs by ID
+    ///
+    /// Returns the channel together with the date of its latest message.
+    async fn find_dialog_channel(
+        &self,
+        id: ChannelId,
+    ) -> Result<Option<(TgChannel, Option<DateTime<Utc>>)>, Error> {
+        if let Some(cached) = self.dialog_cache.get(id, Instant::now()) {
+            return Ok(Some(cached));
+        }
+
+        // Remember every channel passed on the way, so later lookups skip the walk
+        let mut dialogs = self.client.iter_dialogs();
+        while let Some(dialog) = dialogs.next().await.map_err(dialogs_error)? {
+            let Chat::Channel(channel) = dialog.chat() else {
+                continue;
+            };
+            let Ok(channel_id) = ChannelId::from_raw(channel.id()) else {
+                continue;
+            };
+
+            let last_message_date = dialog.last_message.as_ref().map(|message| message.date());
+            let found = (channel.clone(), last_message_date);
+            self.dialog_cache
+                .insert(channel_id, found.clone(), Instant::now());
+            if channel_id == id {
+                return Ok(Some(found));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Resolve a forward destination: `saved` (Saved Messages) or a channel identifier
+    async fn forward_destination(&self, to: &str) -> Result<PackedChat, Error> {
+        if to.eq_ignore_ascii_case("saved") {
+            let me = self.client.get_me().await?;
+            return Ok(Chat::User(me).pack());
+        }
+
+        match to.parse::<ChannelIdentifier>()? {
+            ChannelIdentifier::Id(id) => {
+                let (channel, _) = self
+                    .find_dialog_channel(id)
+                    .await?
+                    .ok_or_else(|| Error::NotFound(format!("channel {}", id)))?;
+                Ok(Chat::Channel(channel).pack())
+            }
+            ChannelIdentifier::Username(username) => self
+                .client
+                .resolve_username(username.as_str())
+                .await?
+                .map(|chat| chat.pack())
+                .ok_or_else(|| Error::NotFound(format!("chat @{}", username))),
+        }
+    }
+
+    /// Collect all broadcast channels from the user's dialogs
+    async fn dialog_channels(&self) -> Result<Vec<TgChannel>, Error> {
+        let mut channels = Vec::new();
+        let mut dialogs = self.client.iter_dialogs();
+        while let Some(dialog) = dialogs.next().await.map_err(dialogs_error)? {
+            if let Chat::Channel(channel) = dialog.chat() {
+                channels.push(channel.clone());
+            }
+        }
+
+        Ok(channels)
+    }
+
+    /// Search a single channel within `range` (oldest, newest)
+    ///
+    /// Telegram pages are fetched until `params.limit` messages pass the filters
+    /// or the scan reaches the start of the range (see `collect_search_hits`).
+    async fn search_channel(
+        &self,
+        channel: &TgChannel,
+        query: &str,
+        params: &SearchParams,
+        range: (DateTime<Utc>, DateTime<Utc>),
+    ) -> Result<Vec<Message>, Error> {
+        let Ok(info) = convert_channel(channel, None) else {
+            return Ok(Vec::new());
+        };
+        let (cutoff, until) = range;
+
+        let chat = Chat::Channel(channel.clone());
+        let mut results = self
+            .client
+            .search_messages(chat.pack())
+            .query(&telegram_query(query));
+        if let Some(before_id) = params.before_id {
+            let offset_id = i32::try_from(before_id.get()).map_err(|_| {
+                Error::InvalidInput(format!("Message ID out of range: {}", before_id))
+            })?;
+            results = results.offset_id(offset_id);
+        }
+        if params.before.is_some() {
+            // offset_date is exclusive, while `before` itself is still in range
+            let offset_date = i32::try_from(until.timestamp().saturating_add(1))
+                .map_err(|_| Error::InvalidInput(format!("Date out of range: {}", until)))?;
+            results = results.offset_date(offset_date);
+        }
+
+        let max_chars = self.max_stored_text_chars;
+        let hits = stream::unfold(results, move |mut results| {
+            let info = info.clone();
+            async move {
+                loop {
+                    match results.next().await {
+                        Ok(Some(raw)) => {
+                            if let Ok(message) = convert_message(&raw, &info, max_chars) {
+                                return Some((Ok(message), results));
+                            }
+                        }
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(Error::from(e)), results)),
+                    }
+                }
+            }
+        });
+
+        collect_search_hits(hits, params, cutoff).await
+    }
+
+    /// Fetch the channel description (`about`) from the full channel info
+    async fn fetch_channel_about(&self, channel: &TgChannel) -> Option<String> {
+        let request = tl::functions::channels::GetFullChannel {
+            channel: tl::enums::InputChannel::Channel(tl::types::InputChannel {
+                channel_id: channel.raw.id,
+                access_hash: channel.raw.access_hash?,
+            }),
+        };
+
+        let tl::enums::messages::ChatFull::Full(full) = self.client.invoke(&request).await.ok()?;
+        match full.full_chat {
+            tl::enums::ChatFull::ChannelFull(full_channel) if !full_channel.about.is_empty() => {
+                Some(full_channel.about)
+            }
+            _ => None,
+        }
+    }
+
+    /// Single get_channel_info attempt (see `with_reconnect`)
+    async fn get_channel_info_once(&self, identifier: &str) -> Result<Channel, Error> {
+        // Validate identifier
+        if identifier.is_empty() {
+            return Err(Error::InvalidInput(
+                "Channel identifier cannot be empty".to_string(),
+            ));
+        }
+
+        let lookup: ChannelIdentifier = identifier.parse()?;
+        let cached = match &lookup {
+            ChannelIdentifier::Username(username) => {
+                self.channel_cache.get_by_username(username, Instant::now())
+            }
+            ChannelIdentifier::Id(id) => self.channel_cache.get_by_id(*id, Instant::now()),
+        };
+        if let Some(channel) = cached {
+            return Ok(channel);
+        }
+
+        let (channel, last_message_date) = match lookup {
+            ChannelIdentifier::Username(username) => {
+                match self.client.resolve_username(username.as_str()).await {
+                    Ok(Some(Chat::Channel(channel))) => (channel, None),
+                    // Connection problems must surface so the caller can reconnect
+                    Err(e) => match Error::from(e) {
+                        error @ (Error::Network(_) | Error::RateLimit { .. }) => return Err(error),
+                        _ => return Err(Error::NotFound(format!("channel @{}", username))),
+                    },
+                    Ok(_) => {
+                        return Err(Error::NotFound(format!("channel @{}", username)));
+                    }
+                }
+            }
+            ChannelIdentifier::Id(id) => self
+                .find_dialog_channel(id)
+                .await?
+                .ok_or_else(|| Error::NotFound(format!("channel {}", id)))?,
+        };
+
+        let mut result = convert_channel(&channel, last_message_date)?;
+        result.description = self.fetch_channel_about(&channel).await;
+        self.channel_cache.insert(result.clone(), Instant::now());
+
+        Ok(result)
+    }
+
+    /// Single search attempt (see `with_reconnect`)
+    async fn search_messages_once(&self, params: &SearchParams) -> Result<SearchResult, Error> {
+        // Library callers may pass raw values, so apply the same limits as the server
+        let params = &params.clone().sanitized()?;
+
+        let start = Instant::now();
+        let range = params.time_range(Utc::now());
+
+        let channels = match params.channel_id {
+            Some(id) => {
+                let (channel, _) = self
+                    .find_dialog_channel(id)
+                    .await?
+                    .ok_or_else(|| Error::NotFound(format!("channel {}", id)))?;
+                vec![channel]
+            }
+            None => self.dialog_channels().await?,
+        };
+
+        // Blocklisted channels are never searched
+        let channels: Vec<TgChannel> = channels
+            .into_iter()
+            .filter(|channel| !is_blocked_channel(&params.blocklist, channel))
+            .collect();
+
+        // Each term is a separate Telegram search; overlaps are removed below
+        let terms = params.terms();
+        let terms = &terms;
+        let (messages, channels_searched) =
+            search_channels_bounded(&channels, self.search_concurrency, |channel| async move {
+                let mut found = Vec::new();
+                for term in terms {
+                    found.extend(self.search_channel(channel, term, params, range).await?);
+                }
+                Ok(found)
+            })
+            .await?;
+
+        let mut result = SearchResult {
+            total_found: messages.len() as u64,
+            messages,
+            search_time_ms: 0,
+            query_metadata: QueryMetadata {
+                query: terms.join(" OR "),
+                hours_back: params.hours_back,
+                channels_searched,
+            },
+        };
+        // Cross-posted announcements can come back more than once
+        result.dedup_by_link();
+        result.sort_by_timestamp_desc();
+        if params.rank == RankMode::Relevance {
+            result.rank_by_relevance(&terms.join(" "));
+        }
+        result.messages.truncate(params.limit as usize);
+        result.search_time_ms = start.elapsed().as_millis() as u64;
+
+        Ok(result)
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,101 +730,999 @@ impl TelegramClientTrait for TelegramClient {
         is_session_valid(&self.client).await
     }
 
+    async fn ping(&self) -> Result<Duration, Error> {
+        let start = Instant::now();
+        self.client.get_me().await?;
+        Ok(start.elapsed())
+    }
+
+    async fn get_me(&self) -> Result<UserProfile, Error> {
+        let me = self.client.get_me().await?;
+
+        Ok(UserProfile {
+            id: UserId::new(me.id())?,
+            username: public_username(me.username()),
+            display_name: me.full_name(),
+        })
+    }
+
+    async fn get_channels_info(&self, identifiers: &[String]) -> Vec<Result<Channel, Error>> {
+        resolve_channels(self, identifiers, CHANNEL_INFO_CONCURRENCY).await
+    }
+
     async fn get_subscribed_channels(
         &self,
-        _limit: u32,
-        _offset: u32,
-    ) -> Result<Vec<Channel>, Error> {
-        // Implementation note: This requires iterating grammers dialogs
-        // and filtering for channels only
-        //
-        // Pseudocode:
-        // 1. Get dialog iterator from client
-        // 2. Filter for channel types
-        // 3. Apply offset/limit pagination
-        // 4. Convert grammers Chat to our Channel type
-        //
-        // For now, return error indicating not yet implemented
-        Err(Error::TelegramApi(
-            "get_subscribed_channels not yet fully implemented - Phase 9 TODO".to_string(),
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<Channel>, bool), Error> {
+        let limit = limit as usize;
+        let offset = offset as usize;
+        // Library callers may pass any limit, so don't preallocate from it
+        let mut channels = Vec::with_capacity(limit.min(CHANNEL_STREAM_PAGE_SIZE as usize));
+        let mut skipped = 0;
+        let mut has_more = false;
+
+        // Dialogs are fetched lazily in chunks, so stop as soon as the page is full
+        let mut dialogs = self.client.iter_dialogs();
+        while let Some(dialog) = dialogs.next().await.map_err(dialogs_error)? {
+            let Chat::Channel(channel) = dialog.chat() else {
+                continue;
+            };
+
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+
+            // One channel past the page is enough to know another page exists
+            if channels.len() == limit {
+                has_more = true;
+                break;
+            }
+
+            let last_message_date = dialog.last_message.as_ref().map(|message| message.date());
+            channels.push(convert_channel(channel, last_message_date)?);
+        }
+
+        Ok((channels, has_more))
+    }
+
+    async fn get_all_subscribed_channels(&self) -> Result<Vec<Channel>, Error> {
+        self.subscribed_channels_stream().try_collect().await
+    }
+
+    async fn get_channel_history(
+        &self,
+        channel: ChannelId,
+        limit: u32,
+        before_id: Option<MessageId>,
+    ) -> Result<Vec<Message>, Error> {
+        if limit == 0 {
+            return Err(Error::InvalidInput(
+                "History limit must be greater than 0".to_string(),
+            ));
+        }
+
+        let (tg_channel, _) = self
+            .find_dialog_channel(channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", channel)))?;
+        let info = convert_channel(&tg_channel, None)?;
+
+        let chat = Chat::Channel(tg_channel);
+        let mut history = self.client.iter_messages(chat.pack()).limit(limit as usize);
+        if let Some(before_id) = before_id {
+            let offset_id = i32::try_from(before_id.get()).map_err(|_| {
+                Error::InvalidInput(format!("Message ID out of range: {}", before_id))
+            })?;
+            history = history.offset_id(offset_id);
+        }
+
+        let mut messages = Vec::with_capacity(limit as usize);
+        while let Some(message) = history.next().await? {
+            messages.push(convert_message(
+                &message,
+                &info,
+                self.max_stored_text_chars,
+            )?);
+        }
+
+        Ok(messages)
+    }
+
+    async fn get_replies(
+        &self,
+        channel: ChannelId,
+        message: MessageId,
+    ) -> Result<Vec<Message>, Error> {
+        let (tg_channel, _) = self
+            .find_dialog_channel(channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", channel)))?;
+        let info = convert_channel(&tg_channel, None)?;
+
+        // Replies always have higher IDs than their parent, so scanning newest-first
+        // can stop once the parent is reached
+        let chat = Chat::Channel(tg_channel);
+        let mut history = self.client.iter_messages(chat.pack()).limit(MAX_REPLY_SCAN);
+        let mut replies = Vec::new();
+        while let Some(tg_message) = history.next().await? {
+            if i64::from(tg_message.id()) <= message.get() {
+                break;
+            }
+            if tg_message.reply_to_message_id().map(i64::from) == Some(message.get()) {
+                replies.push(convert_message(
+                    &tg_message,
+                    &info,
+                    self.max_stored_text_chars,
+                )?);
+            }
+        }
+
+        replies.reverse();
+        Ok(replies)
+    }
+
+    async fn channel_stats(
+        &self,
+        channel: ChannelId,
+        hours_back: u32,
+    ) -> Result<ChannelStats, Error> {
+        let hours_back = hours_back.min(SearchParams::MAX_HOURS_BACK);
+        let cutoff = Utc::now() - chrono::Duration::hours(i64::from(hours_back));
+
+        let (tg_channel, _) = self
+            .find_dialog_channel(channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", channel)))?;
+
+        // History is newest-first, so stop at the first message before the window
+        let chat = Chat::Channel(tg_channel);
+        let mut history = self.client.iter_messages(chat.pack()).limit(MAX_STATS_SCAN);
+        let mut stats = ChannelStats::default();
+        while let Some(message) = history.next().await? {
+            if message.date() < cutoff {
+                break;
+            }
+            stats.record(message.date(), media_type(message.media().as_ref()));
+        }
+
+        Ok(stats)
+    }
+
+    async fn get_folders(&self) -> Result<Vec<ChannelFolder>, Error> {
+        let tl::enums::messages::DialogFilters::Filters(filters) = self
+            .client
+            .invoke(&tl::functions::messages::GetDialogFilters {})
+            .await?;
+
+        Ok(filters.filters.iter().filter_map(convert_folder).collect())
+    }
+
+    async fn get_channel_info(&self, identifier: &str) -> Result<Channel, Error> {
+        with_reconnect(self, || self.get_channel_info_once(identifier)).await
+    }
+
+    async fn forward_message(
+        &self,
+        from_channel: ChannelId,
+        message_id: MessageId,
+        to: &str,
+    ) -> Result<Option<MessageId>, Error> {
+        // Validate destination
+        let to = to.trim();
+        if to.is_empty() {
+            return Err(Error::InvalidInput(
+                "Forward destination cannot be empty".to_string(),
+            ));
+        }
+
+        let (source, _) = self
+            .find_dialog_channel(from_channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", from_channel)))?;
+        let destination = self.forward_destination(to).await?;
+
+        let id = i32::try_from(message_id.get())
+            .map_err(|_| Error::InvalidInput(format!("Message ID out of range: {}", message_id)))?;
+        // grammers sends messages.forwardMessages with a fresh random_id
+        let forwarded = self
+            .client
+            .forward_messages(destination, &[id], Chat::Channel(source).pack())
+            .await
+            .map_err(forward_error)?;
+
+        forwarded
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|message| MessageId::new(i64::from(message.id())))
+            .transpose()
+    }
+
+    async fn send_message(&self, channel: ChannelId, text: &str) -> Result<MessageId, Error> {
+        if text.trim().is_empty() {
+            return Err(Error::InvalidInput(
+                "Message text cannot be empty".to_string(),
+            ));
+        }
+
+        let (tg_channel, _) = self
+            .find_dialog_channel(channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", channel)))?;
+
+        let chat = Chat::Channel(tg_channel);
+        let message = self.client.send_message(chat.pack(), text).await?;
+
+        MessageId::new(i64::from(message.id()))
+    }
+
+    async fn download_media(
+        &self,
+        channel: ChannelId,
+        message: MessageId,
+        dest_dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let (tg_channel, _) = self
+            .find_dialog_channel(channel)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("channel {}", channel)))?;
+
+        let message_id = i32::try_from(message.get())
+            .map_err(|_| Error::InvalidInput(format!("Message ID out of range: {}", message)))?;
+        let chat = Chat::Channel(tg_channel);
+        let tg_message = self
+            .client
+            .get_messages_by_id(chat.pack(), &[message_id])
+            .await?
+            .into_iter()
+            .next()
+            .flatten()
+            .ok_or_else(|| {
+                Error::NotFound(format!("message {} in channel {}", message, channel))
+            })?;
+
+        let media = tg_message.media();
+        let original_name = match &media {
+            Some(Media::Document(document)) => Some(document.name()),
+            _ => None,
+        };
+        let file_name = media_file_name(message, media_type(media.as_ref()), original_name)?;
+        let media = media.ok_or_else(|| {
+            Error::InvalidInput(format!("Message {} has no media to download", message))
+        })?;
+
+        let path = dest_dir.join(file_name);
+        self.client.download_media(&media, &path).await?;
+
+        Ok(path)
+    }
+
+    async fn search_messages(&self, params: &SearchParams) -> Result<SearchResult, Error> {
+        with_reconnect(self, || self.search_messages_once(params)).await
+    }
+
+    async fn reconnect(&self) -> Result<(), Error> {
+        // grammers redials dropped connections using `reconnection_policy`; a cheap
+        // authorized call waits for that and confirms the session survived
+        match self.client.is_authorized().await {
+            Ok(true) => {
+                tracing::info!("Reconnected to Telegram");
+                Ok(())
+            }
+            Ok(false) => Err(Error::Auth(
+                "session is no longer authorized, please log in again".to_string(),
+            )),
+            Err(e) => Err(Error::Network(format!("reconnect failed: {}", e))),
+        }
+    }
+
+    async fn save_session(&self) -> Result<(), Error> {
+        save_session(&self.session_file, &self.client.session().save())
+    }
+}
+
+#[async_trait::async_trait]
+impl UpdateSource for TelegramClient {
+    async fn next_post(&self) -> Result<Option<Message>, Error> {
+        loop {
+            let Update::NewMessage(message) = self.client.next_update().await? else {
+                continue;
+            };
+            if message.outgoing() {
+                continue;
+            }
+            let Chat::Channel(channel) = message.chat() else {
+                continue;
+            };
+
+            let info = convert_channel(&channel, None)?;
+            return convert_message(&message, &info, self.max_stored_text_chars).map(Some);
+        }
+    }
+
+    async fn reconnect(&self) -> Result<(), Error> {
+        TelegramClientTrait::reconnect(self).await
+    }
+}
+
+/// Most recent channel messages scanned when collecting replies
+const MAX_REPLY_SCAN: usize = 500;
+
+/// Most recent channel messages counted by `channel_stats`
+const MAX_STATS_SCAN: usize = 5000;
+
+/// Longest sanitized file name kept for downloaded media
+const MAX_FILE_NAME_CHARS: usize = 200;
+
+/// Telegram errors that ask the client to wait before retrying
+const WAIT_ERRORS: [&str; 3] = ["FLOOD_WAIT", "FLOOD_PREMIUM_WAIT", "SLOWMODE_WAIT"];
+
+/// Map a grammers invocation error to our error type
+///
+/// Flood waits become `Error::RateLimit`, dropped connections `Error::Network`,
+/// everything else `Error::TelegramApi`.
+pub(crate) fn classify_invocation_error(error: InvocationError) -> Error {
+    match &error {
+        InvocationError::Rpc(rpc) => match flood_wait_seconds(rpc) {
+            Some(retry_after_seconds) => Error::RateLimit {
+                retry_after_seconds,
+            },
+            None => Error::TelegramApi(error.to_string()),
+        },
+        InvocationError::Io(_) | InvocationError::Dropped => Error::Network(error.to_string()),
+        _ => Error::TelegramApi(error.to_string()),
+    }
+}
+
+/// Wait time of a flood-wait RPC error, if it is one
+///
+/// grammers normally moves the trailing number of `FLOOD_WAIT_X` into `value`,
+/// but an unparsed name is handled too.
+fn flood_wait_seconds(rpc: &RpcError) -> Option<u64> {
+    let (name, seconds) = match rpc.name.rsplit_once('_') {
+        Some((prefix, suffix)) if suffix.parse::<u64>().is_ok() => (prefix, suffix.parse().ok()),
+        _ => (rpc.name.as_str(), rpc.value.map(u64::from)),
+    };
+
+    WAIT_ERRORS.contains(&name).then(|| seconds.unwrap_or(0))
+}
+
+/// Classify a dialog listing failure, keeping context on generic API errors
+fn dialogs_error(error: InvocationError) -> Error {
+    match classify_invocation_error(error) {
+        Error::TelegramApi(message) => {
+            Error::TelegramApi(format!("Failed to fetch dialogs: {}", message))
+        }
+        other => other,
+    }
+}
+
+/// Classify a forwarding failure, reporting channels that forbid forwarding as bad input
+fn forward_error(error: InvocationError) -> Error {
+    if let InvocationError::Rpc(rpc) = &error
+        && rpc.is("CHAT_FORWARDS_RESTRICTED")
+    {
+        return Error::InvalidInput("forwarding is restricted in this channel".to_string());
+    }
+    classify_invocation_error(error)
+}
+
+/// Convert a dialog filter into a folder, or `None` for the default "All chats"
+///
+/// Pinned and included channel peers become the folder's channels, in that order.
+fn convert_folder(filter: &tl::enums::DialogFilter) -> Option<ChannelFolder> {
+    let (title, pinned_peers, include_peers) = match filter {
+        tl::enums::DialogFilter::Filter(filter) => {
+            (&filter.title, &filter.pinned_peers, &filter.include_peers)
+        }
+        tl::enums::DialogFilter::Chatlist(chatlist) => (
+            &chatlist.title,
+            &chatlist.pinned_peers,
+            &chatlist.include_peers,
+        ),
+        tl::enums::DialogFilter::Default => return None,
+    };
+    let tl::enums::TextWithEntities::Entities(title) = title;
+
+    let mut channel_ids = Vec::new();
+    for peer in pinned_peers.iter().chain(include_peers) {
+        if let tl::enums::InputPeer::Channel(channel) = peer
+            && let Ok(id) = ChannelId::from_raw(channel.channel_id)
+            && !channel_ids.contains(&id)
+        {
+            channel_ids.push(id);
+        }
+    }
+
+    Some(ChannelFolder {
+        folder_name: title.text.clone(),
+        channel_ids,
+    })
+}
+
+/// Convert a grammers channel into our `Channel` type
+fn convert_channel(
+    channel: &TgChannel,
+    last_message_date: Option<DateTime<Utc>>,
+) -> Result<Channel, Error> {
+    let id = ChannelId::from_raw(channel.id())?;
+    let username = public_username(channel.username());
+    let is_public = username.is_some();
+    let name = ChannelName::new(channel.title()).or_else(|_| ChannelName::new(id.to_string()))?;
+
+    Ok(Channel {
+        id,
+        name,
+        username,
+        description: None,
+        member_count: channel
+            .raw
+            .participants_count
+            .and_then(|count| u64::try_from(count).ok())
+            .unwrap_or(0),
+        is_verified: channel.raw.verified,
+        is_public,
+        is_subscribed: !channel.raw.left,
+        last_message_date,
+    })
+}
+
+/// Convert a grammers message into our `Message` type, truncating long text
+fn convert_message(
+    message: &TgMessage,
+    channel: &Channel,
+    max_text_chars: usize,
+) -> Result<Message, Error> {
+    let sender = message.sender();
+    let media_type = media_type(message.media().as_ref());
+    let (forward_from_channel, forward_from_name) = message
+        .forward_header()
+        .map(|header| forward_origin(&header))
+        .unwrap_or_default();
+
+    let mut result = Message {
+        id: MessageId::new(i64::from(message.id()))?,
+        channel_id: channel.id,
+        channel_name: channel.name.clone(),
+        channel_username: channel.username.clone(),
+        text: message.text().to_string(),
+        timestamp: message.date(),
+        sender_id: sender.as_ref().and_then(|s| s.id().try_into().ok()),
+        sender_name: sender
+            .as_ref()
+            .map(|s| s.name().to_string())
+            .filter(|name| !name.is_empty()),
+        has_media: media_type != MediaType::None,
+        media_type,
+        original_length: None,
+        score: None,
+        highlighted_text: None,
+        detected_language: detect_language(message.text()).map(str::to_string),
+        forward_from_channel,
+        forward_from_name,
+        views: message
+            .view_count()
+            .and_then(|count| u32::try_from(count).ok()),
+        forwards: message
+            .forward_count()
+            .and_then(|count| u32::try_from(count).ok()),
+        reactions: reactions(&message.raw),
+        reply_to: message
+            .reply_to_message_id()
+            .and_then(|id| MessageId::new(i64::from(id)).ok()),
+        edit_date: message.edit_date(),
+    };
+    result.truncate_text(max_text_chars);
+
+    Ok(result)
+}
+
+/// Original channel and author name from a forward header
+fn forward_origin(header: &tl::enums::MessageFwdHeader) -> (Option<ChannelId>, Option<String>) {
+    let tl::enums::MessageFwdHeader::Header(header) = header;
+    let channel = match &header.from_id {
+        Some(tl::enums::Peer::Channel(peer)) => peer.channel_id.try_into().ok(),
+        _ => None,
+    };
+    let name = header
+        .from_name
+        .clone()
+        .or_else(|| header.post_author.clone())
+        .filter(|name| !name.is_empty());
+
+    (channel, name)
+}
+
+/// Reaction counts from a raw message, skipping paid and empty reactions
+fn reactions(raw: &tl::enums::Message) -> Option<Vec<(String, u32)>> {
+    let tl::enums::Message::Message(message) = raw else {
+        return None;
+    };
+    let tl::enums::MessageReactions::Reactions(reactions) = message.reactions.as_ref()?;
+
+    let counts: Vec<(String, u32)> = reactions
+        .results
+        .iter()
+        .filter_map(|result| {
+            let tl::enums::ReactionCount::Count(result) = result;
+            let reaction = match &result.reaction {
+                tl::enums::Reaction::Emoji(emoji) => emoji.emoticon.clone(),
+                tl::enums::Reaction::CustomEmoji(custom) => {
+                    format!("custom:{}", custom.document_id)
+                }
+                _ => return None,
+            };
+            Some((reaction, u32::try_from(result.count).ok()?))
+        })
+        .collect();
+
+    (!counts.is_empty()).then_some(counts)
+}
+
+/// Map grammers media to our `MediaType`
+fn media_type(media: Option<&Media>) -> MediaType {
+    match media {
+        None | Some(Media::WebPage(_)) => MediaType::None,
+        Some(Media::Photo(_)) => MediaType::Photo,
+        Some(Media::Document(document)) => document_media_type(document.mime_type()),
+        Some(Media::Sticker(_)) => MediaType::Sticker,
+        Some(Media::Contact(_)) => MediaType::Contact,
+        Some(Media::Poll(_)) => MediaType::Poll,
+        Some(Media::Geo(_)) | Some(Media::GeoLive(_)) => MediaType::Location,
+        Some(Media::Venue(_)) => MediaType::Venue,
+        Some(Media::Dice(_)) => MediaType::Dice,
+        Some(_) => MediaType::Unknown,
+    }
+}
+
+/// Classify a document by its MIME type
+fn document_media_type(mime_type: Option<&str>) -> MediaType {
+    match mime_type {
+        Some("image/gif") => MediaType::Animation,
+        Some("audio/ogg") => MediaType::Voice,
+        Some(mime) if mime.starts_with("video/") => MediaType::Video,
+        Some(mime) if mime.starts_with("audio/") => MediaType::Audio,
+        _ => MediaType::Document,
+    }
+}
+
+/// File name for a message's downloaded media, safe to join onto a directory
+///
+/// Rejects messages without a downloadable file. The original document name is
+/// sanitized; otherwise the name is derived from the message ID and media type.
+fn media_file_name(
+    message: MessageId,
+    media_type: MediaType,
+    original_name: Option<&str>,
+) -> Result<String, Error> {
+    let extension = match media_type {
+        MediaType::None => {
+            return Err(Error::InvalidInput(format!(
+                "Message {} has no media to download",
+                message
+            )));
+        }
+        MediaType::Contact
+        | MediaType::Location
+        | MediaType::Venue
+        | MediaType::Poll
+        | MediaType::Dice => {
+            return Err(Error::InvalidInput(format!(
+                "Message {} has {:?} media, which has no file to download",
+                message, media_type
+            )));
+        }
+        MediaType::Photo => "jpg",
+        MediaType::Video | MediaType::VideoNote => "mp4",
+        MediaType::Audio => "mp3",
+        MediaType::Voice => "ogg",
+        MediaType::Animation => "gif",
+        MediaType::Sticker => "webp",
+        MediaType::Document | MediaType::Unknown => "bin",
+    };
+
+    match original_name
+        .map(sanitize_file_name)
+        .filter(|name| !name.is_empty())
+    {
+        Some(name) => Ok(format!("{}_{}", message, name)),
+        None => Ok(format!("{}.{}", message, extension)),
+    }
+}
+
+/// Strip path separators, control characters and leading dots from a file name
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    cleaned
+        .trim()
+        .trim_start_matches('.')
+        .chars()
+        .take(MAX_FILE_NAME_CHARS)
+        .collect()
+}
+
+/// Query text sent to Telegram, normalized like the text it is matched against
+fn telegram_query(term: &str) -> String {
+    normalize_text(term.trim())
+}
+
+/// Whether a grammers channel matches the blocklist by ID or username
+fn is_blocked_channel(blocklist: &ChannelBlocklist, channel: &TgChannel) -> bool {
+    ChannelId::from_raw(channel.id())
+        .is_ok_and(|id| blocklist.is_blocked(id, public_username(channel.username()).as_ref()))
+}
+
+/// Keep a channel's username only if it is a valid public username
+fn public_username(username: Option<&str>) -> Option<Username> {
+    username.and_then(|username| Username::new(username).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram::{
+        ChannelId, ChannelName, Message, QueryMetadata,
+        types::{MediaType, UserId, Username},
+    };
+
+    fn config_with_proxy(proxy: Option<&str>) -> Config {
+        let proxy_line = proxy
+            .map(|url| format!("proxy = \"{}\"", url))
+            .unwrap_or_default();
+        toml::from_str(&format!(
+            "[telegram]\napi_id = 12345\napi_hash = \"hash\"\nphone_number = \"+1\"\n{}\n",
+            proxy_line
         ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_connection_params_include_proxy() {
+        let config = config_with_proxy(Some("socks5://127.0.0.1:1080"));
+
+        let params = ConnectionParams::from_config(&config);
+
+        assert_eq!(params.api_id, 12345);
+        assert_eq!(params.proxy_url.as_deref(), Some("socks5://127.0.0.1:1080"));
+        assert_eq!(params.retry, config.connection);
+    }
+
+    #[test]
+    fn test_client_config_carries_proxy() {
+        let config = config_with_proxy(Some("socks5://127.0.0.1:1080"));
+
+        let client_config = ConnectionParams::from_config(&config)
+            .client_config(Session::new(), "hash".to_string());
+
+        assert_eq!(
+            client_config.params.proxy_url.as_deref(),
+            Some("socks5://127.0.0.1:1080")
+        );
+    }
+
+    #[test]
+    fn test_client_config_carries_reconnection_policy() {
+        let config = config_with_proxy(None);
+
+        let client_config = ConnectionParams::from_config(&config)
+            .client_config(Session::new(), "hash".to_string());
+
+        assert_eq!(
+            client_config.params.reconnection_policy.should_retry(0),
+            ControlFlow::Continue(config.connection.backoff_delay(1))
+        );
+    }
+
+    #[test]
+    fn test_reconnection_policy_backs_off_then_gives_up() {
+        let policy = ConnectionConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 150,
+        };
+
+        assert_eq!(
+            policy.should_retry(0),
+            ControlFlow::Continue(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.should_retry(2),
+            ControlFlow::Continue(Duration::from_millis(150))
+        );
+        assert_eq!(policy.should_retry(3), ControlFlow::Break(()));
+        assert_eq!(policy.should_retry(usize::MAX), ControlFlow::Break(()));
+    }
+
+    #[test]
+    fn test_connection_params_without_proxy() {
+        let config = config_with_proxy(None);
+
+        let params = ConnectionParams::from_config(&config);
+
+        assert_eq!(params.api_id, 12345);
+        assert!(params.proxy_url.is_none());
+        assert!(params.init_params().proxy_url.is_none());
+    }
+
+    #[test]
+    fn test_init_params_use_configured_labels() {
+        let config: Config = toml::from_str(
+            "[telegram]\napi_id = 12345\napi_hash = \"hash\"\nphone_number = \"+1\"\n\
+             device_model = \"telegram-connector on myserver\"\n\
+             system_version = \"Debian 13\"\napp_version = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let init_params = ConnectionParams::from_config(&config).init_params();
+
+        assert_eq!(init_params.device_model, "telegram-connector on myserver");
+        assert_eq!(init_params.system_version, "Debian 13");
+        assert_eq!(init_params.app_version, "0.1.0");
+    }
+
+    #[test]
+    fn test_client_config_carries_init_params() {
+        let config: Config = toml::from_str(
+            "[telegram]\napi_id = 12345\napi_hash = \"hash\"\nphone_number = \"+1\"\n\
+             device_model = \"telegram-connector on myserver\"\n\
+             system_version = \"Debian 13\"\napp_version = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let client_config = ConnectionParams::from_config(&config)
+            .client_config(Session::new(), "hash".to_string());
+
+        assert_eq!(client_config.api_id, 12345);
+        assert_eq!(client_config.api_hash, "hash");
+        assert_eq!(
+            client_config.params.device_model,
+            "telegram-connector on myserver"
+        );
+        assert_eq!(client_config.params.system_version, "Debian 13");
+        assert_eq!(client_config.params.app_version, "0.1.0");
+    }
+
+    #[test]
+    fn test_init_params_keep_grammers_defaults_when_unset() {
+        let config = config_with_proxy(None);
+
+        let init_params = ConnectionParams::from_config(&config).init_params();
+        let defaults = InitParams::default();
+
+        assert_eq!(init_params.device_model, defaults.device_model);
+        assert_eq!(init_params.system_version, defaults.system_version);
+        assert_eq!(init_params.app_version, defaults.app_version);
+    }
+
+    fn fast_retry_policy(max_attempts: u32) -> ConnectionConfig {
+        ConnectionConfig {
+            max_attempts,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_succeeds_after_transient_failures() {
+        let mut attempts = 0;
+
+        let result = connect_with_retry(&fast_retry_policy(3), || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 3 {
+                    Err(Error::Network("DC unreachable".to_string()))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_gives_up_with_network_error() {
+        let mut attempts = 0;
+
+        let result: Result<(), Error> = connect_with_retry(&fast_retry_policy(3), || {
+            attempts += 1;
+            async { Err(Error::Network("DC unreachable".to_string())) }
+        })
+        .await;
+
+        assert_eq!(attempts, 3);
+        let error = result.unwrap_err();
+        assert!(matches!(error, Error::Network(_)));
+        assert!(error.to_string().contains("after 3 attempts"));
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_does_not_retry_auth_errors() {
+        let mut attempts = 0;
+
+        let result: Result<(), Error> = connect_with_retry(&fast_retry_policy(3), || {
+            attempts += 1;
+            async { Err(Error::Auth("AUTH_KEY_UNREGISTERED".to_string())) }
+        })
+        .await;
+
+        assert_eq!(attempts, 1);
+        assert!(matches!(result, Err(Error::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn test_with_reconnect_reconnects_once_on_network_error() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_reconnect().times(1).returning(|| Ok(()));
+        let mut attempts = 0;
+
+        let result = with_reconnect(&mock, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt == 1 {
+                    Err(Error::Network("connection reset".to_string()))
+                } else {
+                    Ok("recovered")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "recovered");
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_with_reconnect_skips_reconnect_for_other_errors() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_reconnect().never();
+
+        let result: Result<(), Error> = with_reconnect(&mock, || async {
+            Err(Error::NotFound("channel 123".to_string()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_with_reconnect_returns_reconnect_failure() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_reconnect()
+            .times(1)
+            .returning(|| Err(Error::Network("reconnect failed: timeout".to_string())));
+        let mut attempts = 0;
+
+        let result: Result<(), Error> = with_reconnect(&mock, || {
+            attempts += 1;
+            async { Err(Error::Network("connection reset".to_string())) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+        assert_eq!(attempts, 1);
+    }
+
+    fn rpc_error(name: &str, value: Option<u32>) -> InvocationError {
+        InvocationError::Rpc(RpcError {
+            code: 420,
+            name: name.to_string(),
+            value,
+            caused_by: None,
+        })
+    }
+
+    #[test]
+    fn test_classify_flood_wait_with_parsed_value() {
+        let error = classify_invocation_error(rpc_error("FLOOD_WAIT", Some(42)));
+        assert!(matches!(
+            error,
+            Error::RateLimit {
+                retry_after_seconds: 42
+            }
+        ));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_flood_wait_with_seconds_in_name() {
+        let error = classify_invocation_error(rpc_error("FLOOD_WAIT_17", None));
+        assert!(matches!(
+            error,
+            Error::RateLimit {
+                retry_after_seconds: 17
+            }
+        ));
     }
 
-    async fn get_channel_info(&self, identifier: &str) -> Result<Channel, Error> {
-        // Validate identifier
-        if identifier.is_empty() {
-            return Err(Error::InvalidInput(
-                "Channel identifier cannot be empty".to_string(),
-            ));
-        }
+    #[test]
+    fn test_classify_premium_and_slowmode_waits() {
+        assert!(matches!(
+            classify_invocation_error(rpc_error("FLOOD_PREMIUM_WAIT", Some(5))),
+            Error::RateLimit {
+                retry_after_seconds: 5
+            }
+        ));
+        assert!(matches!(
+            classify_invocation_error(rpc_error("SLOWMODE_WAIT_30", None)),
+            Error::RateLimit {
+                retry_after_seconds: 30
+            }
+        ));
+    }
 
-        // Implementation note: Parse identifier and resolve channel
-        //
-        // Pseudocode:
-        // 1. Parse identifier:
-        //    - If starts with @: username
-        //    - If numeric: channel ID
-        //    - Otherwise: invalid
-        // 2. Resolve via grammers:
-        //    - Username: client.resolve_username()
-        //    - ID: client.get_entity_by_id()
-        // 3. Convert to Channel type
-        //
-        // For now, return error indicating not yet implemented
-        Err(Error::TelegramApi(
-            "get_channel_info not yet fully implemented - Phase 9 TODO".to_string(),
-        ))
+    #[test]
+    fn test_classify_non_flood_rpc_error() {
+        let error = classify_invocation_error(rpc_error("CHANNEL_PRIVATE", None));
+        assert!(matches!(error, Error::TelegramApi(_)));
+        assert!(error.to_string().contains("CHANNEL_PRIVATE"));
     }
 
-    async fn search_messages(&self, params: &SearchParams) -> Result<SearchResult, Error> {
-        // Validate parameters
-        if params.query.is_empty() {
-            return Err(Error::InvalidInput(
-                "Search query cannot be empty".to_string(),
-            ));
-        }
+    #[test]
+    fn test_classify_dropped_request_as_network() {
+        let error = classify_invocation_error(InvocationError::Dropped);
+        assert!(matches!(error, Error::Network(_)));
+    }
 
-        if params.limit == 0 {
-            return Err(Error::InvalidInput(
-                "Search limit must be greater than 0".to_string(),
-            ));
-        }
+    #[test]
+    fn test_dialogs_error_keeps_rate_limit() {
+        assert!(matches!(
+            dialogs_error(rpc_error("FLOOD_WAIT", Some(3))),
+            Error::RateLimit {
+                retry_after_seconds: 3
+            }
+        ));
+        assert!(
+            dialogs_error(rpc_error("AUTH_KEY_UNREGISTERED", None))
+                .to_string()
+                .contains("Failed to fetch dialogs")
+        );
+    }
 
-        // Implementation note: Search messages across channels
-        //
-        // Pseudocode:
-        // 1. Calculate time range (now - hours_back)
-        // 2. Get channels to search:
-        //    - If channel_id provided: search that channel
-        //    - Otherwise: search all subscribed channels
-        // 3. For each channel:
-        //    - Use grammers search API
-        //    - Filter by date range
-        //    - Collect matching messages
-        // 4. Aggregate and sort results by date (newest first)
-        // 5. Apply limit
-        //
-        // For now, return error indicating not yet implemented
-        Err(Error::TelegramApi(
-            "search_messages not yet fully implemented - Phase 9 TODO".to_string(),
-        ))
+    #[test]
+    fn test_forward_error_maps_restricted_channel_to_invalid_input() {
+        let error = forward_error(rpc_error("CHAT_FORWARDS_RESTRICTED", None));
+
+        assert!(matches!(error, Error::InvalidInput(_)));
+        assert_eq!(
+            error.to_string(),
+            "invalid input: forwarding is restricted in this channel"
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::telegram::{
-        ChannelId, ChannelName, Message, QueryMetadata,
-        types::{MediaType, UserId, Username},
-    };
+    #[test]
+    fn test_forward_error_keeps_other_classifications() {
+        assert!(matches!(
+            forward_error(rpc_error("FLOOD_WAIT", Some(5))),
+            Error::RateLimit {
+                retry_after_seconds: 5
+            }
+        ));
+        assert!(matches!(
+            forward_error(rpc_error("MESSAGE_ID_INVALID", None)),
+            Error::TelegramApi(_)
+        ));
+    }
 
     // Helper to create test channel
     fn create_test_channel(id: i64, name: &str) -> Channel {
         Channel {
             id: ChannelId::new(id).unwrap(),
             name: ChannelName::new(name).unwrap(),
-            username: Username::new("testchannel").unwrap(),
+            username: Some(Username::new("testchannel").unwrap()),
             description: Some("Test channel".to_string()),
             member_count: 1000,
             is_verified: false,
@@ -172,16 +1735,27 @@ mod tests {
     // Helper to create test message
     fn create_test_message(id: i32, text: &str, channel_id: i64) -> Message {
         Message {
-            id: crate::telegram::types::MessageId::new(id as i64).unwrap(),
+            id: MessageId::new(id as i64).unwrap(),
             channel_id: ChannelId::new(channel_id).unwrap(),
             channel_name: ChannelName::new("TestChannel").unwrap(),
-            channel_username: Username::new("testchannel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
             text: text.to_string(),
             timestamp: chrono::Utc::now(),
             sender_id: Some(UserId::new(123).unwrap()),
             sender_name: Some("Test User".to_string()),
             has_media: false,
             media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
         }
     }
 
@@ -205,6 +1779,36 @@ mod tests {
         assert!(!mock.is_connected().await);
     }
 
+    #[tokio::test]
+    async fn mock_ping_returns_latency() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_ping()
+            .times(1)
+            .returning(|| Ok(Duration::from_millis(42)));
+
+        assert_eq!(mock.ping().await.unwrap(), Duration::from_millis(42));
+    }
+
+    #[tokio::test]
+    async fn mock_ping_reports_network_failure() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_ping()
+            .times(1)
+            .returning(|| Err(Error::Network("connection reset".to_string())));
+
+        assert!(matches!(mock.ping().await, Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn mock_save_session_reports_failure() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_save_session()
+            .times(1)
+            .returning(|| Err(Error::Auth("Failed to write session file".to_string())));
+
+        assert!(matches!(mock.save_session().await, Err(Error::Auth(_))));
+    }
+
     #[tokio::test]
     async fn mock_get_subscribed_channels_returns_list() {
         let mut mock = MockTelegramClientTrait::new();
@@ -218,11 +1822,12 @@ mod tests {
         mock.expect_get_subscribed_channels()
             .with(mockall::predicate::eq(10), mockall::predicate::eq(0))
             .times(1)
-            .returning(move |_, _| Ok(expected_clone.clone()));
+            .returning(move |_, _| Ok((expected_clone.clone(), false)));
 
         let result = mock.get_subscribed_channels(10, 0).await;
         assert!(result.is_ok());
-        let channels = result.unwrap();
+        let (channels, has_more) = result.unwrap();
+        assert!(!has_more);
         assert_eq!(channels.len(), 2);
         assert_eq!(channels[0].name.as_str(), "Channel1");
     }
@@ -236,23 +1841,480 @@ mod tests {
             .with(mockall::predicate::eq(2), mockall::predicate::eq(0))
             .times(1)
             .returning(|_, _| {
-                Ok(vec![
-                    create_test_channel(1, "Channel1"),
-                    create_test_channel(2, "Channel2"),
-                ])
+                Ok((
+                    vec![
+                        create_test_channel(1, "Channel1"),
+                        create_test_channel(2, "Channel2"),
+                    ],
+                    true,
+                ))
             });
 
         // Second page
         mock.expect_get_subscribed_channels()
             .with(mockall::predicate::eq(2), mockall::predicate::eq(2))
             .times(1)
-            .returning(|_, _| Ok(vec![create_test_channel(3, "Channel3")]));
+            .returning(|_, _| Ok((vec![create_test_channel(3, "Channel3")], false)));
 
-        let page1 = mock.get_subscribed_channels(2, 0).await.unwrap();
+        let (page1, more1) = mock.get_subscribed_channels(2, 0).await.unwrap();
         assert_eq!(page1.len(), 2);
+        assert!(more1);
 
-        let page2 = mock.get_subscribed_channels(2, 2).await.unwrap();
+        let (page2, more2) = mock.get_subscribed_channels(2, 2).await.unwrap();
         assert_eq!(page2.len(), 1);
+        assert!(!more2);
+    }
+
+    #[tokio::test]
+    async fn subscribed_channels_stream_emits_every_page_once() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_get_subscribed_channels()
+            .times(3)
+            .returning(|limit, offset| match offset {
+                0 => Ok((
+                    vec![create_test_channel(1, "One"), create_test_channel(2, "Two")],
+                    true,
+                )),
+                2 => Ok((
+                    vec![
+                        create_test_channel(3, "Three"),
+                        create_test_channel(4, "Four"),
+                    ],
+                    true,
+                )),
+                4 => {
+                    assert_eq!(limit, 2);
+                    Ok((vec![create_test_channel(5, "Five")], false))
+                }
+                _ => panic!("unexpected offset {offset}"),
+            });
+
+        let channels: Vec<Channel> = subscribed_channels_stream(&mock, 2)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let ids: Vec<i64> = channels.iter().map(|channel| channel.id.get()).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn subscribed_channels_stream_ends_after_error() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_get_subscribed_channels()
+            .times(2)
+            .returning(|_, offset| {
+                if offset == 0 {
+                    Ok((vec![create_test_channel(1, "One")], true))
+                } else {
+                    Err(Error::Network("connection reset".to_string()))
+                }
+            });
+
+        let items: Vec<Result<Channel, Error>> =
+            subscribed_channels_stream(&mock, 1).collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_ok());
+        assert!(matches!(items[1], Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn search_channels_bounded_respects_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let channels: Vec<i64> = (1..=10).collect();
+
+        let (messages, channels_searched) = search_channels_bounded(&channels, 3, |&channel| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(vec![create_test_message(channel as i32, "hit", channel)])
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        assert_eq!(channels_searched, 10);
+        assert_eq!(messages.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn search_channels_bounded_keeps_results_on_flood_wait() {
+        let channels: Vec<i64> = (1..=4).collect();
+
+        let (messages, channels_searched) =
+            search_channels_bounded(&channels, 1, |&channel| async move {
+                if channel == 3 {
+                    return Err(Error::RateLimit {
+                        retry_after_seconds: 30,
+                    });
+                }
+                Ok(vec![create_test_message(channel as i32, "hit", channel)])
+            })
+            .await
+            .unwrap();
+
+        // Sequential order: channels 1 and 2 finish before the flood wait
+        assert_eq!(channels_searched, 2);
+        assert_eq!(messages.len(), 2);
+    }
+
+    /// Search hit `hours_ago` old, newest-first order is up to the caller
+    fn search_hit(id: i32, hours_ago: i64) -> Message {
+        Message {
+            timestamp: chrono::Utc::now() - chrono::Duration::hours(hours_ago),
+            ..create_test_message(id, "rust news", 1)
+        }
+    }
+
+    /// Stream of hits that fails if read past its end
+    fn search_hits(hits: Vec<Message>) -> impl Stream<Item = Result<Message, Error>> {
+        stream::iter(hits.into_iter().map(Ok)).chain(stream::once(async {
+            Err(Error::Network("read past the last hit".to_string()))
+        }))
+    }
+
+    fn hit_ids(messages: &[Message]) -> Vec<i64> {
+        messages.iter().map(|m| m.id.get()).collect()
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_fills_limit_from_past_window() {
+        let now = chrono::Utc::now();
+        let params = SearchParams {
+            after: Some(now - chrono::Duration::days(10)),
+            before: Some(now - chrono::Duration::days(7)),
+            limit: 2,
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(now);
+        // Newer hits than `before` still come first when Telegram ignores offset_date
+        let hits = vec![
+            search_hit(1, 2),
+            search_hit(2, 24 * 3),
+            search_hit(3, 24 * 8),
+            search_hit(4, 24 * 8 + 12),
+            search_hit(5, 24 * 9),
+        ];
+
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![3, 4]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_stops_at_cutoff() {
+        let now = chrono::Utc::now();
+        let params = SearchParams {
+            after: Some(now - chrono::Duration::days(10)),
+            before: Some(now - chrono::Duration::days(7)),
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(now);
+        let hits = vec![search_hit(1, 24 * 8), search_hit(2, 24 * 11)];
+
+        // The hit older than `after` ends the scan before the failing read
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_pages_past_other_media_types() {
+        let params = SearchParams {
+            media_types: Some(vec![MediaType::Photo]),
+            limit: 2,
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(chrono::Utc::now());
+        // A full first page of text-only hits must not end the search
+        let mut hits: Vec<Message> = (1..=150).map(|id| search_hit(id, 1)).collect();
+        for id in [151, 152, 153] {
+            hits.push(Message {
+                has_media: true,
+                media_type: MediaType::Photo,
+                ..search_hit(id, 2)
+            });
+        }
+
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![151, 152]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_pages_past_other_senders() {
+        let params = SearchParams {
+            sender_id: Some(UserId::new(7).unwrap()),
+            limit: 2,
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(chrono::Utc::now());
+        let mut hits: Vec<Message> = (1..=150).map(|id| search_hit(id, 1)).collect();
+        for id in [151, 152, 153] {
+            hits.push(Message {
+                sender_id: Some(UserId::new(7).unwrap()),
+                ..search_hit(id, 2)
+            });
+        }
+
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![151, 152]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_pages_past_low_view_counts() {
+        let params = SearchParams {
+            min_views: Some(1000),
+            limit: 2,
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(chrono::Utc::now());
+        let mut hits: Vec<Message> = (1..=150)
+            .map(|id| Message {
+                views: Some(10),
+                ..search_hit(id, 1)
+            })
+            .collect();
+        for id in [151, 152, 153] {
+            hits.push(Message {
+                views: Some(5000),
+                ..search_hit(id, 2)
+            });
+        }
+
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![151, 152]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_pages_past_unedited_messages() {
+        let params = SearchParams {
+            edited_only: true,
+            limit: 2,
+            ..SearchParams::new("rust")
+        };
+        let (cutoff, _) = params.time_range(chrono::Utc::now());
+        let mut hits: Vec<Message> = (1..=150).map(|id| search_hit(id, 1)).collect();
+        for id in [151, 152, 153] {
+            hits.push(Message {
+                edit_date: Some(chrono::Utc::now()),
+                ..search_hit(id, 2)
+            });
+        }
+
+        let messages = collect_search_hits(search_hits(hits), &params, cutoff)
+            .await
+            .unwrap();
+
+        assert_eq!(hit_ids(&messages), vec![151, 152]);
+    }
+
+    #[tokio::test]
+    async fn collect_search_hits_reports_fetch_errors() {
+        let params = SearchParams::new("rust");
+        let (cutoff, _) = params.time_range(chrono::Utc::now());
+
+        let result =
+            collect_search_hits(search_hits(vec![search_hit(1, 1)]), &params, cutoff).await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn search_channels_bounded_fails_on_other_errors() {
+        let channels: Vec<i64> = (1..=3).collect();
+
+        let result = search_channels_bounded(&channels, 2, |&channel| async move {
+            if channel == 2 {
+                return Err(Error::Network("connection reset".to_string()));
+            }
+            Ok(Vec::new())
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn resolve_channels_keeps_order_and_partial_failures() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_get_channel_info()
+            .times(4)
+            .returning(|identifier| match identifier {
+                "@one_channel" => Ok(create_test_channel(1, "One")),
+                "2" => Ok(create_test_channel(2, "Two")),
+                "@three_channel" => Ok(create_test_channel(3, "Three")),
+                _ => Err(Error::NotFound(format!("channel {}", identifier))),
+            });
+
+        let identifiers: Vec<String> = ["@three_channel", "@missing", "@one_channel", "2"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let results = resolve_channels(&mock, &identifiers, 2).await;
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().id.get(), 3);
+        assert!(matches!(results[1], Err(Error::NotFound(_))));
+        assert_eq!(results[2].as_ref().unwrap().id.get(), 1);
+        assert_eq!(results[3].as_ref().unwrap().id.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn resolve_channels_handles_empty_input_and_zero_concurrency() {
+        let mut mock = MockTelegramClientTrait::new();
+        mock.expect_get_channel_info()
+            .times(1)
+            .returning(|_| Ok(create_test_channel(1, "One")));
+
+        assert!(resolve_channels(&mock, &[], 4).await.is_empty());
+
+        let results = resolve_channels(&mock, &["@one_channel".to_string()], 0).await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+    }
+
+    #[tokio::test]
+    async fn mock_get_channel_history_with_cursor() {
+        let mut mock = MockTelegramClientTrait::new();
+
+        mock.expect_get_channel_history()
+            .with(
+                mockall::predicate::eq(ChannelId::new(100).unwrap()),
+                mockall::predicate::eq(2),
+                mockall::predicate::eq(Some(MessageId::new(50).unwrap())),
+            )
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![
+                    create_test_message(49, "Newer", 100),
+                    create_test_message(48, "Older", 100),
+                ])
+            });
+
+        let messages = mock
+            .get_channel_history(
+                ChannelId::new(100).unwrap(),
+                2,
+                Some(MessageId::new(50).unwrap()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, MessageId::new(49).unwrap());
+    }
+
+    #[tokio::test]
+    async fn mock_get_replies_returns_thread() {
+        let mut mock = MockTelegramClientTrait::new();
+
+        mock.expect_get_replies()
+            .with(
+                mockall::predicate::eq(ChannelId::new(100).unwrap()),
+                mockall::predicate::eq(MessageId::new(40).unwrap()),
+            )
+            .times(1)
+            .returning(|_, parent| {
+                let mut first = create_test_message(41, "First reply", 100);
+                first.reply_to = Some(parent);
+                let mut second = create_test_message(45, "Second reply", 100);
+                second.reply_to = Some(parent);
+                Ok(vec![first, second])
+            });
+
+        let replies = mock
+            .get_replies(ChannelId::new(100).unwrap(), MessageId::new(40).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0].id, MessageId::new(41).unwrap());
+        assert!(
+            replies
+                .iter()
+                .all(|reply| reply.reply_to == Some(MessageId::new(40).unwrap()))
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_get_folders_returns_list() {
+        let mut mock = MockTelegramClientTrait::new();
+
+        mock.expect_get_folders().times(1).returning(|| {
+            Ok(vec![ChannelFolder {
+                folder_name: "News".to_string(),
+                channel_ids: vec![ChannelId::new(1).unwrap(), ChannelId::new(2).unwrap()],
+            }])
+        });
+
+        let folders = mock.get_folders().await.unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].folder_name, "News");
+        assert_eq!(folders[0].channel_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn mock_forward_message_to_saved() {
+        let mut mock = MockTelegramClientTrait::new();
+
+        mock.expect_forward_message()
+            .with(
+                mockall::predicate::eq(ChannelId::new(100).unwrap()),
+                mockall::predicate::eq(MessageId::new(42).unwrap()),
+                mockall::predicate::eq("saved"),
+            )
+            .times(1)
+            .returning(|_, _, _| Ok(Some(MessageId::new(7).unwrap())));
+
+        let result = mock
+            .forward_message(
+                ChannelId::new(100).unwrap(),
+                MessageId::new(42).unwrap(),
+                "saved",
+            )
+            .await;
+
+        assert_eq!(result.unwrap(), Some(MessageId::new(7).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn mock_send_message_returns_new_id() {
+        let mut mock = MockTelegramClientTrait::new();
+
+        mock.expect_send_message()
+            .with(
+                mockall::predicate::eq(ChannelId::new(100).unwrap()),
+                mockall::predicate::eq("hello"),
+            )
+            .times(1)
+            .returning(|_, _| Ok(MessageId::new(55).unwrap()));
+
+        let result = mock
+            .send_message(ChannelId::new(100).unwrap(), "hello")
+            .await;
+
+        assert_eq!(result.unwrap(), MessageId::new(55).unwrap());
     }
 
     #[tokio::test]
@@ -429,6 +2491,232 @@ mod tests {
         assert_eq!(search_result.query_metadata.channels_searched, 1);
     }
 
+    // ========================================
+    // Conversion helper tests
+    // ========================================
+
+    fn channel_peer(channel_id: i64) -> tl::enums::InputPeer {
+        tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+            channel_id,
+            access_hash: 0,
+        })
+    }
+
+    fn folder_title(text: &str) -> tl::enums::TextWithEntities {
+        tl::enums::TextWithEntities::Entities(tl::types::TextWithEntities {
+            text: text.to_string(),
+            entities: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn convert_folder_collects_pinned_and_included_channels() {
+        let filter = tl::enums::DialogFilter::Filter(tl::types::DialogFilter {
+            contacts: false,
+            non_contacts: false,
+            groups: false,
+            broadcasts: false,
+            bots: false,
+            exclude_muted: false,
+            exclude_read: false,
+            exclude_archived: false,
+            title_noanimate: false,
+            id: 2,
+            title: folder_title("Crypto"),
+            emoticon: None,
+            color: None,
+            pinned_peers: vec![channel_peer(300)],
+            include_peers: vec![
+                channel_peer(100),
+                tl::enums::InputPeer::User(tl::types::InputPeerUser {
+                    user_id: 42,
+                    access_hash: 0,
+                }),
+                channel_peer(300),
+            ],
+            exclude_peers: vec![channel_peer(200)],
+        });
+
+        let folder = convert_folder(&filter).unwrap();
+
+        assert_eq!(folder.folder_name, "Crypto");
+        assert_eq!(
+            folder.channel_ids,
+            vec![ChannelId::new(300).unwrap(), ChannelId::new(100).unwrap()]
+        );
+    }
+
+    #[test]
+    fn convert_folder_keeps_shared_chatlists() {
+        let filter = tl::enums::DialogFilter::Chatlist(tl::types::DialogFilterChatlist {
+            has_my_invites: false,
+            title_noanimate: false,
+            id: 3,
+            title: folder_title("Shared"),
+            emoticon: None,
+            color: None,
+            pinned_peers: Vec::new(),
+            include_peers: vec![channel_peer(500)],
+        });
+
+        let folder = convert_folder(&filter).unwrap();
+
+        assert_eq!(folder.folder_name, "Shared");
+        assert!(folder.contains(ChannelId::new(500).unwrap()));
+    }
+
+    #[test]
+    fn convert_folder_skips_default_all_chats() {
+        assert!(convert_folder(&tl::enums::DialogFilter::Default).is_none());
+    }
+
+    #[test]
+    fn telegram_query_is_normalized() {
+        // Zero-width space, curly quotes and a Latin "p" and "y" in a Cyrillic word
+        assert_eq!(telegram_query(" pyб\u{200B}ль “курс” "), "рубль \"курс\"");
+        assert_eq!(telegram_query("bitcoin"), "bitcoin");
+    }
+
+    #[test]
+    fn public_username_keeps_valid_username() {
+        let username = public_username(Some("rust_news")).unwrap();
+
+        assert_eq!(username.as_str(), "rust_news");
+    }
+
+    #[test]
+    fn public_username_is_none_for_private_channel() {
+        assert!(public_username(None).is_none());
+    }
+
+    #[test]
+    fn public_username_drops_invalid_username() {
+        // Collectible 4-character usernames fail our validation
+        assert!(public_username(Some("abcd")).is_none());
+    }
+
+    #[test]
+    fn media_file_name_rejects_messages_without_media() {
+        let result = media_file_name(MessageId::new(5).unwrap(), MediaType::None, None);
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn media_file_name_rejects_media_without_file() {
+        let result = media_file_name(MessageId::new(5).unwrap(), MediaType::Poll, None);
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn media_file_name_derives_name_from_type() {
+        let name = media_file_name(MessageId::new(5).unwrap(), MediaType::Photo, None).unwrap();
+
+        assert_eq!(name, "5.jpg");
+    }
+
+    #[test]
+    fn media_file_name_keeps_sanitized_original_name() {
+        let name = media_file_name(
+            MessageId::new(5).unwrap(),
+            MediaType::Document,
+            Some("../../etc/passwd"),
+        )
+        .unwrap();
+
+        assert_eq!(name, "5__.._etc_passwd");
+        assert_eq!(std::path::Path::new(&name).components().count(), 1);
+    }
+
+    #[test]
+    fn sanitize_file_name_strips_traversal_and_separators() {
+        assert_eq!(sanitize_file_name("report.pdf"), "report.pdf");
+        assert_eq!(sanitize_file_name("..\\secret.txt"), "_secret.txt");
+        assert_eq!(sanitize_file_name("a/b\nc"), "a_b_c");
+        assert_eq!(sanitize_file_name(".."), "");
+    }
+
+    #[test]
+    fn media_file_name_falls_back_when_original_sanitizes_to_empty() {
+        let name =
+            media_file_name(MessageId::new(5).unwrap(), MediaType::Document, Some("..")).unwrap();
+
+        assert_eq!(name, "5.bin");
+    }
+
+    #[test]
+    fn document_media_type_classifies_by_mime() {
+        assert_eq!(document_media_type(Some("video/mp4")), MediaType::Video);
+        assert_eq!(document_media_type(Some("audio/mpeg")), MediaType::Audio);
+        assert_eq!(document_media_type(Some("audio/ogg")), MediaType::Voice);
+        assert_eq!(document_media_type(Some("image/gif")), MediaType::Animation);
+        assert_eq!(
+            document_media_type(Some("application/pdf")),
+            MediaType::Document
+        );
+        assert_eq!(document_media_type(None), MediaType::Document);
+    }
+
+    #[test]
+    fn search_result_aggregation_keeps_newest_messages() {
+        let now = chrono::Utc::now();
+        let mut old = create_test_message(1, "old", 100);
+        old.timestamp = now - chrono::Duration::hours(2);
+        let mut newest = create_test_message(2, "newest", 200);
+        newest.timestamp = now;
+        let mut middle = create_test_message(3, "middle", 100);
+        middle.timestamp = now - chrono::Duration::hours(1);
+
+        let mut result = SearchResult {
+            messages: vec![old, newest.clone(), middle, newest],
+            total_found: 4,
+            search_time_ms: 0,
+            query_metadata: QueryMetadata {
+                query: "test".to_string(),
+                hours_back: 48,
+                channels_searched: 2,
+            },
+        };
+        result.dedup_by_link();
+        result.sort_by_timestamp_desc();
+        result.messages.truncate(2);
+
+        assert_eq!(result.total_found, 3);
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.messages[0].text, "newest");
+        assert_eq!(result.messages[1].text, "middle");
+    }
+
+    #[test]
+    fn search_result_aggregation_merges_overlapping_queries() {
+        let now = chrono::Utc::now();
+        let mut both = create_test_message(1, "bitcoin and ethereum", 100);
+        both.timestamp = now;
+        let mut bitcoin_only = create_test_message(2, "bitcoin", 100);
+        bitcoin_only.timestamp = now - chrono::Duration::hours(1);
+        let mut ethereum_only = create_test_message(3, "ethereum", 100);
+        ethereum_only.timestamp = now - chrono::Duration::hours(2);
+
+        // Hits for "bitcoin" followed by hits for "ethereum"
+        let mut result = SearchResult {
+            messages: vec![both.clone(), bitcoin_only, both, ethereum_only],
+            total_found: 4,
+            search_time_ms: 0,
+            query_metadata: QueryMetadata {
+                query: "bitcoin OR ethereum".to_string(),
+                hours_back: 48,
+                channels_searched: 1,
+            },
+        };
+        result.dedup_by_link();
+        result.sort_by_timestamp_desc();
+
+        assert_eq!(result.total_found, 3);
+        let ids: Vec<i64> = result.messages.iter().map(|m| m.id.get()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
     // ========================================
     // Real implementation validation tests
     // ========================================
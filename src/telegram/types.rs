@@ -1,7 +1,11 @@
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
+use std::str::FromStr;
 
 use crate::error::Error;
 
@@ -14,6 +18,9 @@ use crate::error::Error;
 pub struct ChannelId(i64);
 
 impl ChannelId {
+    /// Offset MTProto adds to channel IDs in their negative `-100…` form
+    const RAW_CHANNEL_OFFSET: i64 = 1_000_000_000_000;
+
     pub fn new(id: i64) -> Result<Self, Error> {
         if id <= 0 {
             return Err(Error::InvalidInput(format!(
@@ -24,11 +31,54 @@ impl ChannelId {
         Ok(Self(id))
     }
 
+    /// Create from an API-level ID, accepting the `-100` prefixed form
+    ///
+    /// `-1001234567890` and `1234567890` both yield `ChannelId(1234567890)`.
+    pub fn from_raw(raw: i64) -> Result<Self, Error> {
+        if raw >= 0 {
+            return Self::new(raw);
+        }
+
+        match raw.checked_neg().map(|id| id - Self::RAW_CHANNEL_OFFSET) {
+            Some(id) if id > 0 => Ok(Self(id)),
+            _ => Err(Error::InvalidInput(format!(
+                "Channel ID {} is not a -100 prefixed channel ID",
+                raw
+            ))),
+        }
+    }
+
+    /// The `-100` prefixed form used by MTProto and the Bot API
+    pub fn to_raw(&self) -> i64 {
+        -(Self::RAW_CHANNEL_OFFSET + self.0)
+    }
+
     pub fn get(&self) -> i64 {
         self.0
     }
 }
 
+impl FromStr for ChannelId {
+    type Err = Error;
+
+    /// Parse a positive ID (the `-100` form is handled by `ChannelIdentifier`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| Error::InvalidInput(format!("Invalid channel ID: '{}'", s)))?;
+        Self::new(id)
+    }
+}
+
+impl TryFrom<i64> for ChannelId {
+    type Error = Error;
+
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        Self::new(id)
+    }
+}
+
 impl fmt::Display for ChannelId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -55,6 +105,26 @@ impl MessageId {
     }
 }
 
+impl FromStr for MessageId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| Error::InvalidInput(format!("Invalid message ID: '{}'", s)))?;
+        Self::new(id)
+    }
+}
+
+impl TryFrom<i64> for MessageId {
+    type Error = Error;
+
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        Self::new(id)
+    }
+}
+
 impl fmt::Display for MessageId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -81,6 +151,14 @@ impl UserId {
     }
 }
 
+impl TryFrom<i64> for UserId {
+    type Error = Error;
+
+    fn try_from(id: i64) -> Result<Self, Self::Error> {
+        Self::new(id)
+    }
+}
+
 impl fmt::Display for UserId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -92,6 +170,8 @@ impl fmt::Display for UserId {
 // =============================================================================
 
 /// Telegram username (alphanumeric + underscore, 5-32 chars)
+///
+/// A single leading `@` is accepted and stripped.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
 pub struct Username(String);
@@ -99,6 +179,10 @@ pub struct Username(String);
 impl Username {
     pub fn new(username: impl Into<String>) -> Result<Self, Error> {
         let username = username.into();
+        let username = match username.strip_prefix('@') {
+            Some(stripped) => stripped.to_string(),
+            None => username,
+        };
 
         if username.len() < 5 || username.len() > 32 {
             return Err(Error::InvalidInput(format!(
@@ -127,6 +211,89 @@ impl fmt::Display for Username {
     }
 }
 
+/// Channel reference given by a tool caller: a username or a numeric ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelIdentifier {
+    Username(Username),
+    Id(ChannelId),
+}
+
+impl FromStr for ChannelIdentifier {
+    type Err = Error;
+
+    /// Parse `@name`, `name`, `12345` or the `-100` prefixed form `-1001234567890`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let identifier = s.trim();
+
+        if let Some(username) = identifier.strip_prefix('@') {
+            return Username::new(username).map(Self::Username);
+        }
+
+        // Supergroup/channel IDs are often written with the Bot API `-100` prefix
+        let digits = identifier.strip_prefix('-').unwrap_or(identifier);
+        if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+            let raw = identifier.parse::<i64>().map_err(|_| {
+                Error::InvalidInput(format!("Invalid channel ID: '{}'", identifier))
+            })?;
+            return ChannelId::from_raw(raw).map(Self::Id);
+        }
+
+        match Username::new(identifier) {
+            Ok(username) if identifier.starts_with(|c: char| c.is_alphabetic()) => {
+                Ok(Self::Username(username))
+            }
+            _ => Err(Error::InvalidInput(format!(
+                "Invalid channel identifier '{}': expected @username, username or numeric ID",
+                identifier
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ChannelIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Username(username) => write!(f, "@{}", username),
+            Self::Id(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// Account phone number in E.164 form: `+` followed by 7-15 digits
+///
+/// Kept secret-wrapped so it never shows up in `Debug` output or logs.
+#[derive(Debug, Clone)]
+pub struct PhoneNumber(SecretString);
+
+impl PhoneNumber {
+    pub fn new(phone: &str) -> Result<Self, Error> {
+        let phone = phone.trim();
+        // Errors never echo the number itself
+        let digits = phone.strip_prefix('+').ok_or_else(|| {
+            Error::InvalidInput("Phone number must start with '+' and a country code".into())
+        })?;
+
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::InvalidInput(
+                "Phone number must contain only digits after '+'".into(),
+            ));
+        }
+
+        if digits.len() < 7 || digits.len() > 15 {
+            return Err(Error::InvalidInput(format!(
+                "Phone number must have 7-15 digits, got {}",
+                digits.len()
+            )));
+        }
+
+        Ok(Self(SecretString::from(phone)))
+    }
+
+    pub fn expose(&self) -> &str {
+        self.0.expose_secret()
+    }
+}
+
 /// Non-empty channel/chat name
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
@@ -178,6 +345,56 @@ pub enum MediaType {
     Venue,     // Location with venue info
     Poll,      // Poll/quiz
     Dice,      // Dice/dart/etc game
+    Unknown,   // Media present but not classified
+}
+
+impl MediaType {
+    /// Serialized (lowercase) name, as accepted by `from_str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Photo => "photo",
+            Self::Video => "video",
+            Self::Document => "document",
+            Self::Audio => "audio",
+            Self::Voice => "voice",
+            Self::VideoNote => "videonote",
+            Self::Animation => "animation",
+            Self::Sticker => "sticker",
+            Self::Contact => "contact",
+            Self::Location => "location",
+            Self::Venue => "venue",
+            Self::Poll => "poll",
+            Self::Dice => "dice",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = Error;
+
+    /// Parse the serialized (lowercase) name, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "photo" => Ok(Self::Photo),
+            "video" => Ok(Self::Video),
+            "document" => Ok(Self::Document),
+            "audio" => Ok(Self::Audio),
+            "voice" => Ok(Self::Voice),
+            "videonote" => Ok(Self::VideoNote),
+            "animation" => Ok(Self::Animation),
+            "sticker" => Ok(Self::Sticker),
+            "contact" => Ok(Self::Contact),
+            "location" => Ok(Self::Location),
+            "venue" => Ok(Self::Venue),
+            "poll" => Ok(Self::Poll),
+            "dice" => Ok(Self::Dice),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(Error::InvalidInput(format!("Unknown media type: '{}'", s))),
+        }
+    }
 }
 
 // =============================================================================
@@ -189,13 +406,47 @@ pub struct Message {
     pub id: MessageId,
     pub channel_id: ChannelId,
     pub channel_name: ChannelName,
-    pub channel_username: Username,
+    /// Public username of the channel; private and invite-only channels have none
+    pub channel_username: Option<Username>,
     pub text: String,
     pub timestamp: DateTime<Utc>,
     pub sender_id: Option<UserId>,
     pub sender_name: Option<String>,
     pub has_media: bool,
     pub media_type: MediaType,
+    /// Original text length in characters, set only when `text` was truncated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_length: Option<usize>,
+    /// Relevance to the search query, set only when ranking by relevance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    /// Full text with query matches marked, set only when highlighting was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlighted_text: Option<String>,
+    /// Heuristic language of `text` ("ru" or "en"), if it could be guessed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_language: Option<String>,
+    /// Channel the message was originally posted in, when it is a forward
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward_from_channel: Option<ChannelId>,
+    /// Original author name shown on the forward, when Telegram provides one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forward_from_name: Option<String>,
+    /// View count, available for channel posts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub views: Option<u32>,
+    /// Number of times the post was forwarded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forwards: Option<u32>,
+    /// Reaction counts as `(emoji, count)` pairs; custom emoji appear as `custom:<id>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<Vec<(String, u32)>>,
+    /// Message this one replies to, within the same channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<MessageId>,
+    /// When the message was last edited, if ever
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_date: Option<DateTime<Utc>>,
 }
 
 impl Message {
@@ -205,17 +456,294 @@ impl Message {
         self.timestamp > threshold
     }
 
+    /// Time elapsed since the message was posted
+    pub fn age(&self) -> chrono::Duration {
+        Utc::now() - self.timestamp
+    }
+
+    /// Check if the message was posted within `[after, before]` (both inclusive)
+    pub fn is_within(&self, after: DateTime<Utc>, before: DateTime<Utc>) -> bool {
+        after <= self.timestamp && self.timestamp <= before
+    }
+
+    /// Whether the message was edited after posting
+    pub fn was_edited(&self) -> bool {
+        self.edit_date.is_some()
+    }
+
+    /// Number of words in the text, ignoring tokens without letters or digits
+    pub fn word_count(&self) -> usize {
+        self.text
+            .split_whitespace()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .count()
+    }
+
     /// Check if message is text-only (no media)
     pub fn is_text_only(&self) -> bool {
         self.media_type == MediaType::None
     }
+
+    /// Replace text with its snippet around the first match of `query` (see `snippet`)
+    pub fn apply_snippet(&mut self, query: &str, radius: usize) {
+        self.text = self.snippet(query, radius);
+    }
+
+    /// Window of `context_chars` chars on each side of the first match of `query`
+    ///
+    /// The matched term is wrapped in `SNIPPET_MARKER` and `…` marks cut-off text.
+    /// Matching is case-insensitive on normalized text (see `normalize_text`); without
+    /// a match the leading window is kept.
+    pub fn snippet(&self, query: &str, context_chars: usize) -> String {
+        let chars: Vec<char> = self.text.chars().collect();
+
+        let Some(&(match_start, match_end)) = find_normalized_matches(&self.text, query).first()
+        else {
+            let window = context_chars.saturating_mul(2);
+            if chars.len() > window {
+                let mut snippet: String = chars[..window].iter().collect();
+                snippet.push(ELLIPSIS);
+                return snippet;
+            }
+            return self.text.clone();
+        };
+
+        let start = match_start.saturating_sub(context_chars);
+        let end = match_end.saturating_add(context_chars).min(chars.len());
+
+        let mut snippet = String::new();
+        if start > 0 {
+            snippet.push(ELLIPSIS);
+        }
+        snippet.extend(&chars[start..match_start]);
+        snippet.push_str(SNIPPET_MARKER);
+        snippet.extend(&chars[match_start..match_end]);
+        snippet.push_str(SNIPPET_MARKER);
+        snippet.extend(&chars[match_end..end]);
+        if end < chars.len() {
+            snippet.push(ELLIPSIS);
+        }
+
+        snippet
+    }
+
+    /// Text with every case-insensitive match of `query` wrapped in `«` and `»`
+    pub fn highlight(&self, query: &str) -> String {
+        self.highlight_with(query, HIGHLIGHT_OPEN, HIGHLIGHT_CLOSE)
+    }
+
+    /// Text with every case-insensitive match of `query` wrapped in `open` and `close`
+    ///
+    /// Matches are found on normalized text but the original text is returned.
+    /// Overlapping or adjacent matches are merged into a single highlighted span.
+    pub fn highlight_with(&self, query: &str, open: &str, close: &str) -> String {
+        let chars: Vec<char> = self.text.chars().collect();
+
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in find_normalized_matches(&self.text, query) {
+            match spans.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => spans.push((start, end)),
+            }
+        }
+
+        let mut highlighted = String::with_capacity(self.text.len());
+        let mut position = 0;
+        for (start, end) in spans {
+            highlighted.extend(&chars[position..start]);
+            highlighted.push_str(open);
+            highlighted.extend(&chars[start..end]);
+            highlighted.push_str(close);
+            position = end;
+        }
+        highlighted.extend(&chars[position..]);
+
+        highlighted
+    }
+
+    /// Truncate text to at most `max_chars` characters, recording the original length
+    ///
+    /// Applied when converting from grammers so oversized text never enters a collection.
+    pub fn truncate_text(&mut self, max_chars: usize) {
+        let char_count = self.text.chars().count();
+        if char_count <= max_chars {
+            return;
+        }
+
+        self.text = self.text.chars().take(max_chars).collect();
+        self.original_length = Some(char_count);
+    }
+}
+
+/// Letters needed before `detect_language` makes a guess
+const MIN_LANGUAGE_LETTERS: usize = 3;
+
+/// Guess whether `text` is Russian ("ru") or English ("en") from its script
+///
+/// A lightweight heuristic: more than half the letters Cyrillic means "ru",
+/// more than half Latin means "en". Returns `None` for too little text or a
+/// mix of other scripts.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let (mut letters, mut cyrillic, mut latin) = (0usize, 0usize, 0usize);
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        if matches!(c, '\u{0400}'..='\u{04FF}') {
+            cyrillic += 1;
+        } else if c.is_ascii_alphabetic() || matches!(c, '\u{00C0}'..='\u{024F}') {
+            latin += 1;
+        }
+    }
+
+    if letters < MIN_LANGUAGE_LETTERS {
+        None
+    } else if cyrillic * 2 > letters {
+        Some("ru")
+    } else if latin * 2 > letters {
+        Some("en")
+    } else {
+        None
+    }
+}
+
+/// Normalize text for matching
+///
+/// Strips zero-width characters and soft hyphens, folds quote and dash variants to
+/// `"`, `'` and `-`, and maps Latin look-alikes to Cyrillic inside words that already
+/// contain Cyrillic letters (e.g. `pyбль` becomes `рубль`). Pure Latin words are kept.
+pub fn normalize_text(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .filter(|c| !is_invisible(*c))
+        .map(fold_punctuation)
+        .collect();
+
+    let mut normalized = String::with_capacity(cleaned.len());
+    let mut word = String::new();
+    for c in cleaned.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            push_word(&mut normalized, &word);
+            word.clear();
+            normalized.push(c);
+        }
+    }
+    push_word(&mut normalized, &word);
+
+    normalized
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}'
+    )
+}
+
+fn fold_punctuation(c: char) -> char {
+    match c {
+        '«' | '»' | '„' | '“' | '”' | '‟' | '″' => '"',
+        '‘' | '’' | '‚' | '‛' | '′' => '\'',
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => '-',
+        _ => c,
+    }
+}
+
+/// Append `word`, mapping Latin homoglyphs to Cyrillic if it mixes both scripts
+fn push_word(out: &mut String, word: &str) {
+    if word.chars().any(|c| matches!(c, '\u{0400}'..='\u{04FF}')) {
+        out.extend(word.chars().map(cyrillic_homoglyph));
+    } else {
+        out.push_str(word);
+    }
+}
+
+fn cyrillic_homoglyph(c: char) -> char {
+    match c {
+        'a' => 'а',
+        'c' => 'с',
+        'e' => 'е',
+        'o' => 'о',
+        'p' => 'р',
+        'x' => 'х',
+        'y' => 'у',
+        'A' => 'А',
+        'B' => 'В',
+        'C' => 'С',
+        'E' => 'Е',
+        'H' => 'Н',
+        'K' => 'К',
+        'M' => 'М',
+        'O' => 'О',
+        'P' => 'Р',
+        'T' => 'Т',
+        'X' => 'Х',
+        'Y' => 'У',
+        _ => c,
+    }
+}
+
+/// Marker wrapped around the matched term in search snippets
+pub const SNIPPET_MARKER: &str = "**";
+
+const ELLIPSIS: char = '…';
+
+/// Default delimiters wrapped around matches by `Message::highlight`
+pub const HIGHLIGHT_OPEN: &str = "«";
+pub const HIGHLIGHT_CLOSE: &str = "»";
+
+/// Case-insensitive matches of `query` in normalized `text`, as char ranges of the raw text
+///
+/// `normalize_text` only drops invisible characters and maps the rest one to one,
+/// so every normalized char points back at exactly one raw char.
+fn find_normalized_matches(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let raw_positions: Vec<usize> = text
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !is_invisible(*c))
+        .map(|(position, _)| position)
+        .collect();
+    let normalized: Vec<char> = normalize_text(text).chars().collect();
+    let query: Vec<char> = normalize_text(query.trim()).chars().collect();
+
+    find_matches(&normalized, &query)
+        .filter_map(|(start, end)| {
+            let raw_start = *raw_positions.get(start)?;
+            let raw_end = *raw_positions.get(end.checked_sub(1)?)? + 1;
+            Some((raw_start, raw_end))
+        })
+        .collect()
+}
+
+/// Iterate all case-insensitive occurrences of `query` in `text` as char ranges
+fn find_matches<'a>(
+    text: &'a [char],
+    query: &'a [char],
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    let last_start = if query.is_empty() || query.len() > text.len() {
+        None
+    } else {
+        Some(text.len() - query.len())
+    };
+
+    last_start
+        .into_iter()
+        .flat_map(|last| 0..=last)
+        .filter(move |&start| {
+            text[start..start + query.len()]
+                .iter()
+                .zip(query)
+                .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+        })
+        .map(move |start| (start, start + query.len()))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Channel {
     pub id: ChannelId,
     pub name: ChannelName,
-    pub username: Username,
+    /// Public username; private and invite-only channels have none
+    pub username: Option<Username>,
     pub description: Option<String>,
     pub member_count: u64,
     pub is_verified: bool,
@@ -224,16 +752,293 @@ pub struct Channel {
     pub last_message_date: Option<DateTime<Utc>>,
 }
 
+impl Channel {
+    /// Case-insensitive substring match on the channel name or username
+    pub fn matches_name(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        self.name.as_str().to_lowercase().contains(&filter)
+            || self
+                .username
+                .as_ref()
+                .is_some_and(|username| username.as_str().to_lowercase().contains(&filter))
+    }
+}
+
+/// Message counts for one channel over a recent time window
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelStats {
+    pub message_count: u64,
+    /// Messages with any attachment
+    pub media_count: u64,
+    /// Oldest message in the window (null when the window is empty)
+    pub first_seen: Option<DateTime<Utc>>,
+    /// Newest message in the window (null when the window is empty)
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+impl ChannelStats {
+    /// Count one message; messages may arrive in any order
+    pub fn record(&mut self, timestamp: DateTime<Utc>, media_type: MediaType) {
+        self.message_count += 1;
+        if media_type != MediaType::None {
+            self.media_count += 1;
+        }
+        self.first_seen = Some(
+            self.first_seen
+                .map_or(timestamp, |first| first.min(timestamp)),
+        );
+        self.last_seen = Some(self.last_seen.map_or(timestamp, |last| last.max(timestamp)));
+    }
+}
+
+/// The account the current session is signed in as
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct UserProfile {
+    pub id: UserId,
+    pub username: Option<Username>,
+    pub display_name: String,
+}
+
+/// Sort key for channel listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelSort {
+    /// Alphabetical by name, ignoring case
+    Name,
+    /// By subscriber count
+    MemberCount,
+    /// By the date of the latest message
+    LastMessageDate,
+}
+
+impl ChannelSort {
+    /// Sort `channels` in place (stable); channels without a last message date go last
+    pub fn sort(self, channels: &mut [Channel], descending: bool) {
+        let directed = |ordering: Ordering| {
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        };
+
+        match self {
+            Self::Name => channels.sort_by(|a, b| {
+                directed(
+                    a.name
+                        .as_str()
+                        .to_lowercase()
+                        .cmp(&b.name.as_str().to_lowercase()),
+                )
+            }),
+            Self::MemberCount => {
+                channels.sort_by(|a, b| directed(a.member_count.cmp(&b.member_count)))
+            }
+            Self::LastMessageDate => {
+                channels.sort_by(|a, b| match (a.last_message_date, b.last_message_date) {
+                    (Some(a), Some(b)) => directed(a.cmp(&b)),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                })
+            }
+        }
+    }
+}
+
+/// Telegram dialog folder (dialog filter) and the channels it contains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelFolder {
+    pub folder_name: String,
+    pub channel_ids: Vec<ChannelId>,
+}
+
+impl ChannelFolder {
+    /// Check if the folder includes the given channel
+    pub fn contains(&self, channel_id: ChannelId) -> bool {
+        self.channel_ids.contains(&channel_id)
+    }
+}
+
+/// Channels excluded from every result and listing, matched by ID or username
+#[derive(Debug, Clone, Default)]
+pub struct ChannelBlocklist {
+    ids: HashSet<ChannelId>,
+    usernames: HashSet<String>,
+}
+
+impl ChannelBlocklist {
+    /// Build from config entries: channel IDs (plain or `-100` prefixed) or usernames
+    /// (with or without @)
+    ///
+    /// Invalid entries are skipped with a warning; use `try_new` to reject them.
+    pub fn new(entries: &[String]) -> Self {
+        let mut blocklist = Self::default();
+
+        for entry in entries {
+            match entry.parse::<ChannelIdentifier>() {
+                Ok(identifier) => blocklist.insert(identifier),
+                Err(e) => tracing::warn!("Ignoring channel blocklist entry '{}': {}", entry, e),
+            }
+        }
+
+        blocklist
+    }
+
+    /// Build from config entries, failing on the first invalid one
+    pub fn try_new(entries: &[String]) -> Result<Self, Error> {
+        let mut blocklist = Self::default();
+
+        for entry in entries {
+            let identifier = entry.parse::<ChannelIdentifier>().map_err(|e| {
+                Error::InvalidInput(format!("Invalid blocklist entry '{}': {}", entry, e))
+            })?;
+            blocklist.insert(identifier);
+        }
+
+        Ok(blocklist)
+    }
+
+    fn insert(&mut self, identifier: ChannelIdentifier) {
+        match identifier {
+            ChannelIdentifier::Id(id) => {
+                self.ids.insert(id);
+            }
+            ChannelIdentifier::Username(username) => {
+                self.usernames.insert(username.as_str().to_lowercase());
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.usernames.is_empty()
+    }
+
+    /// Whether any entry is a username, which needs the channel resolved to check
+    pub fn has_usernames(&self) -> bool {
+        !self.usernames.is_empty()
+    }
+
+    /// Whether an identifier as given by a caller is blocked, without resolving it
+    pub fn is_blocked_identifier(&self, identifier: &ChannelIdentifier) -> bool {
+        match identifier {
+            ChannelIdentifier::Id(id) => self.ids.contains(id),
+            ChannelIdentifier::Username(username) => {
+                self.usernames.contains(&username.as_str().to_lowercase())
+            }
+        }
+    }
+
+    /// Check if a channel is blocked by its ID or username (case-insensitive)
+    pub fn is_blocked(&self, id: ChannelId, username: Option<&Username>) -> bool {
+        self.ids.contains(&id)
+            || username
+                .is_some_and(|username| self.usernames.contains(&username.as_str().to_lowercase()))
+    }
+}
+
 // =============================================================================
 // Request/Response Types
 // =============================================================================
 
+/// How the query is matched against message text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// Telegram's own matching on the normalized query, no extra filtering
+    #[default]
+    Substring,
+    /// Case-insensitive match bounded by Unicode word boundaries
+    WholeWord,
+    /// Exact, case-sensitive substring match
+    CaseSensitive,
+}
+
+impl MatchMode {
+    /// Whether `text` matches `query` under this mode
+    ///
+    /// `Substring` accepts everything, leaving matching to Telegram. The other modes
+    /// run both sides through `normalize_text`, so homoglyphs and invisible characters
+    /// do not prevent a match.
+    pub fn matches(&self, text: &str, query: &str) -> bool {
+        match self {
+            Self::Substring => true,
+            Self::CaseSensitive => normalize_text(text).contains(&normalize_text(query)),
+            Self::WholeWord => {
+                let text: Vec<char> = normalize_text(text).chars().collect();
+                let query: Vec<char> = normalize_text(query).chars().collect();
+                let is_word_char = |c: &char| c.is_alphanumeric() || *c == '_';
+
+                find_matches(&text, &query).any(|(start, end)| {
+                    let before = start.checked_sub(1).map(|i| &text[i]);
+                    !before.is_some_and(is_word_char) && !text.get(end).is_some_and(is_word_char)
+                })
+            }
+        }
+    }
+}
+
+/// How search results are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RankMode {
+    /// Newest messages first
+    #[default]
+    Recency,
+    /// Messages mentioning the query terms most often first
+    Relevance,
+}
+
+/// Relevance of `text` to `query` based on query term frequency
+///
+/// Counts case-insensitive occurrences of each whitespace-separated query term,
+/// damped by the square root of the message's word count so long messages do
+/// not win by size alone. Returns 0.0 when no term occurs.
+pub fn relevance_score(text: &str, query: &str) -> f32 {
+    let text_chars: Vec<char> = normalize_text(text).chars().collect();
+    let hits: usize = query
+        .split_whitespace()
+        .map(|term| {
+            let term: Vec<char> = normalize_text(term).chars().collect();
+            find_matches(&text_chars, &term).count()
+        })
+        .sum();
+    if hits == 0 {
+        return 0.0;
+    }
+
+    let word_count = text.split_whitespace().count().max(1);
+    hits as f32 / (word_count as f32).sqrt()
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchParams {
     pub query: String,
+    /// Additional queries; a message matching any of them (or `query`) is returned
+    pub queries: Vec<String>,
     pub channel_id: Option<ChannelId>,
     pub hours_back: u32,
     pub limit: u32,
+    /// Only return messages with one of these media types
+    pub media_types: Option<Vec<MediaType>>,
+    /// Only return messages from this sender
+    pub sender_id: Option<UserId>,
+    /// Explicit start of the time range (takes precedence over `hours_back`)
+    pub after: Option<DateTime<Utc>>,
+    /// Explicit end of the time range (defaults to now)
+    pub before: Option<DateTime<Utc>>,
+    /// Post-filter applied to message text
+    pub match_mode: MatchMode,
+    /// Result ordering
+    pub rank: RankMode,
+    /// Drop messages with fewer views (or no view count at all)
+    pub min_views: Option<u32>,
+    /// Only return messages that were edited after posting
+    pub edited_only: bool,
+    /// Paging cursor: only messages older than this ID (requires `channel_id`)
+    pub before_id: Option<MessageId>,
+    /// Channels skipped before searching and dropped from results
+    pub blocklist: ChannelBlocklist,
 }
 
 impl SearchParams {
@@ -245,10 +1050,112 @@ impl SearchParams {
     pub fn new(query: impl Into<String>) -> Self {
         Self {
             query: query.into(),
+            queries: Vec::new(),
             channel_id: None,
             hours_back: Self::DEFAULT_HOURS_BACK,
             limit: Self::DEFAULT_LIMIT,
+            media_types: None,
+            sender_id: None,
+            after: None,
+            before: None,
+            match_mode: MatchMode::default(),
+            rank: RankMode::default(),
+            min_views: None,
+            edited_only: false,
+            before_id: None,
+            blocklist: ChannelBlocklist::default(),
+        }
+    }
+
+    /// Validate the query and clamp `hours_back` and `limit` to their maximums
+    pub fn sanitized(self) -> Result<Self, Error> {
+        if self.terms().is_empty() {
+            return Err(Error::InvalidInput(
+                "Search query cannot be empty: provide query or queries".to_string(),
+            ));
+        }
+        if self.limit == 0 {
+            return Err(Error::InvalidInput(
+                "Search limit must be greater than 0".to_string(),
+            ));
+        }
+        // Message IDs are per channel, so a cursor is meaningless across channels
+        if self.before_id.is_some() && self.channel_id.is_none() {
+            return Err(Error::InvalidInput(
+                "before_id requires channel_id".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            hours_back: self.hours_back.min(Self::MAX_HOURS_BACK),
+            limit: self.limit.min(Self::MAX_LIMIT),
+            ..self
+        })
+    }
+
+    /// Non-blank search terms from `query` and `queries`, without duplicates
+    pub fn terms(&self) -> Vec<&str> {
+        let mut terms: Vec<&str> = Vec::new();
+        for term in std::iter::once(&self.query).chain(&self.queries) {
+            let term = term.trim();
+            if !term.is_empty() && !terms.contains(&term) {
+                terms.push(term);
+            }
         }
+        terms
+    }
+
+    /// Search term that `text` matches under the current match mode
+    ///
+    /// Substring mode trusts Telegram's matching, so a term that literally occurs
+    /// in the text (ignoring case) is preferred over the first accepted one.
+    pub fn matched_term(&self, text: &str) -> Option<&str> {
+        let matching: Vec<&str> = self
+            .terms()
+            .into_iter()
+            .filter(|term| self.match_mode.matches(text, term))
+            .collect();
+        let text = normalize_text(text).to_lowercase();
+
+        matching
+            .iter()
+            .copied()
+            .find(|term| text.contains(&normalize_text(term).to_lowercase()))
+            .or_else(|| matching.first().copied())
+    }
+
+    /// Time range to search as `(start, end)`
+    ///
+    /// Without an explicit `after`, the range covers `hours_back` hours before the end.
+    pub fn time_range(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let end = self.before.unwrap_or(now);
+        let start = self
+            .after
+            .unwrap_or_else(|| end - chrono::Duration::hours(i64::from(self.hours_back)));
+        (start, end)
+    }
+
+    /// Whether a message passes the optional result filters
+    pub fn accepts(&self, message: &Message) -> bool {
+        self.media_types
+            .as_ref()
+            .is_none_or(|types| types.contains(&message.media_type))
+            && self
+                .sender_id
+                .is_none_or(|sender_id| message.sender_id == Some(sender_id))
+            && self.after.is_none_or(|after| message.timestamp >= after)
+            && self.before.is_none_or(|before| message.timestamp <= before)
+            && self
+                .min_views
+                .is_none_or(|min_views| message.views.is_some_and(|views| views >= min_views))
+            && (!self.edited_only || message.was_edited())
+            && self
+                .before_id
+                .is_none_or(|before_id| message.id.get() < before_id.get())
+            && !self
+                .blocklist
+                .is_blocked(message.channel_id, message.channel_username.as_ref())
+            && self.matched_term(&message.text).is_some()
     }
 }
 
@@ -266,6 +1173,89 @@ pub struct SearchResult {
     pub query_metadata: QueryMetadata,
 }
 
+impl SearchResult {
+    /// Remove repeated hits for the same `(channel_id, message_id)`, keeping the first
+    ///
+    /// `total_found` is reduced by the number of duplicates removed.
+    pub fn dedup_by_link(&mut self) {
+        let mut seen = HashSet::new();
+        let found = self.messages.len();
+        self.messages
+            .retain(|message| seen.insert((message.channel_id, message.id)));
+        let removed = (found - self.messages.len()) as u64;
+        self.total_found = self.total_found.saturating_sub(removed);
+    }
+
+    /// Order messages newest first
+    pub fn sort_by_timestamp_desc(&mut self) {
+        self.messages
+            .sort_by_key(|message| std::cmp::Reverse(message.timestamp));
+    }
+
+    /// One JSON-encoded `Message` per line, each terminated by `\n`
+    pub fn to_jsonl(&self) -> String {
+        self.messages
+            .iter()
+            // Serializing a `Message` cannot fail: every field maps to plain JSON
+            .filter_map(|message| serde_json::to_string(message).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Key message fields as CSV rows: id, channel_id, channel_username, timestamp,
+    /// media_type, text
+    pub fn to_csv(&self, include_header: bool) -> String {
+        let mut csv = String::new();
+        if include_header {
+            csv.push_str(CSV_HEADER);
+            csv.push('\n');
+        }
+
+        for message in &self.messages {
+            let row = [
+                message.id.to_string(),
+                message.channel_id.to_string(),
+                message
+                    .channel_username
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                message.timestamp.to_rfc3339(),
+                message.media_type.as_str().to_string(),
+                message.text.clone(),
+            ];
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Score every message against `query` and order by descending score
+    ///
+    /// The sort is stable, so equally relevant messages keep their previous order.
+    pub fn rank_by_relevance(&mut self, query: &str) {
+        for message in &mut self.messages {
+            message.score = Some(relevance_score(&message.text, query));
+        }
+        self.messages
+            .sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+    }
+}
+
+/// Column names written by `SearchResult::to_csv`
+const CSV_HEADER: &str = "id,channel_id,channel_username,timestamp,media_type,text";
+
+/// Quote a CSV field (RFC 4180) when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct QueryMetadata {
     pub query: String,
@@ -306,43 +1296,201 @@ mod tests {
     }
 
     #[test]
-    fn channel_id_display() {
-        let id = ChannelId::new(123456).unwrap();
-        assert_eq!(format!("{}", id), "123456");
+    fn channel_identifier_with_at_prefix() {
+        assert_eq!(
+            "@rust_news".parse::<ChannelIdentifier>().unwrap(),
+            ChannelIdentifier::Username(Username::new("rust_news").unwrap())
+        );
     }
 
     #[test]
-    fn channel_id_serde_transparent() {
-        let id = ChannelId::new(123456).unwrap();
-        let json = serde_json::to_string(&id).unwrap();
-        assert_eq!(json, "123456"); // No wrapping object
+    fn channel_identifier_bare_username() {
+        assert_eq!(
+            "rust_news".parse::<ChannelIdentifier>().unwrap(),
+            ChannelIdentifier::Username(Username::new("rust_news").unwrap())
+        );
     }
 
     #[test]
-    fn message_id_rejects_negative() {
-        assert!(MessageId::new(-1).is_err());
+    fn channel_identifier_numeric_id() {
+        assert_eq!(
+            " 12345 ".parse::<ChannelIdentifier>().unwrap(),
+            ChannelIdentifier::Id(ChannelId::new(12345).unwrap())
+        );
     }
 
     #[test]
-    fn message_id_rejects_zero() {
-        assert!(MessageId::new(0).is_err());
+    fn channel_identifier_strips_supergroup_prefix() {
+        assert_eq!(
+            "-1001234567890".parse::<ChannelIdentifier>().unwrap(),
+            ChannelIdentifier::Id(ChannelId::new(1234567890).unwrap())
+        );
     }
 
     #[test]
-    fn message_id_accepts_positive() {
-        let result = MessageId::new(456);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().get(), 456);
+    fn channel_identifier_rejects_invalid_input() {
+        for identifier in [
+            "",
+            "@ab",
+            "-42",
+            "not a channel",
+            "0",
+            "1abcdef",
+            "99999999999999999999",
+        ] {
+            let result = identifier.parse::<ChannelIdentifier>();
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                identifier
+            );
+        }
     }
 
     #[test]
-    fn message_id_display() {
-        let id = MessageId::new(789).unwrap();
-        assert_eq!(format!("{}", id), "789");
+    fn channel_identifier_display_round_trips() {
+        for identifier in ["@rust_news", "12345"] {
+            let parsed: ChannelIdentifier = identifier.parse().unwrap();
+            assert_eq!(parsed.to_string(), identifier);
+        }
     }
 
     #[test]
-    fn user_id_rejects_negative() {
+    fn channel_id_from_str_accepts_positive_ids() {
+        assert_eq!("12345".parse::<ChannelId>().unwrap().get(), 12345);
+        assert_eq!(" 12345 ".parse::<ChannelId>().unwrap().get(), 12345);
+    }
+
+    #[test]
+    fn channel_id_from_str_rejects_invalid_input() {
+        for value in [
+            "",
+            "abc",
+            "12a",
+            "0",
+            "-5",
+            "-1001234567890",
+            "99999999999999999999",
+        ] {
+            let result = value.parse::<ChannelId>();
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn message_id_from_str_accepts_positive_ids() {
+        assert_eq!("42".parse::<MessageId>().unwrap().get(), 42);
+        assert_eq!(" 42 ".parse::<MessageId>().unwrap().get(), 42);
+    }
+
+    #[test]
+    fn message_id_from_str_rejects_invalid_input() {
+        for value in ["", "abc", "4.2", "0", "-1", "99999999999999999999"] {
+            let result = value.parse::<MessageId>();
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn id_try_from_matches_new() {
+        for id in [i64::MIN, -1, 0, 1, 42, i64::MAX] {
+            assert_eq!(ChannelId::try_from(id).ok(), ChannelId::new(id).ok());
+            assert_eq!(MessageId::try_from(id).ok(), MessageId::new(id).ok());
+            assert_eq!(UserId::try_from(id).ok(), UserId::new(id).ok());
+        }
+    }
+
+    #[test]
+    fn id_try_into_from_i64() {
+        let channel_id: ChannelId = 100i64.try_into().unwrap();
+        let message_id: MessageId = 42i64.try_into().unwrap();
+        let user_id: UserId = 7i64.try_into().unwrap();
+        assert_eq!(channel_id.get(), 100);
+        assert_eq!(message_id.get(), 42);
+        assert_eq!(user_id.get(), 7);
+
+        let result: Result<MessageId, Error> = 0i64.try_into();
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn channel_id_from_raw_strips_prefix() {
+        let id = ChannelId::from_raw(-1001234567890).unwrap();
+        assert_eq!(id.get(), 1234567890);
+    }
+
+    #[test]
+    fn channel_id_from_raw_accepts_positive() {
+        let id = ChannelId::from_raw(1234567890).unwrap();
+        assert_eq!(id.get(), 1234567890);
+    }
+
+    #[test]
+    fn channel_id_from_raw_rejects_non_channel_ids() {
+        // Zero, basic group IDs and the bare prefix are not channels
+        for raw in [0, -42, -1000000000000, i64::MIN] {
+            let result = ChannelId::from_raw(raw);
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {}",
+                raw
+            );
+        }
+    }
+
+    #[test]
+    fn channel_id_to_raw_round_trips() {
+        let id = ChannelId::new(1234567890).unwrap();
+        assert_eq!(id.to_raw(), -1001234567890);
+        assert_eq!(ChannelId::from_raw(id.to_raw()).unwrap(), id);
+    }
+
+    #[test]
+    fn channel_id_display() {
+        let id = ChannelId::new(123456).unwrap();
+        assert_eq!(format!("{}", id), "123456");
+    }
+
+    #[test]
+    fn channel_id_serde_transparent() {
+        let id = ChannelId::new(123456).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "123456"); // No wrapping object
+    }
+
+    #[test]
+    fn message_id_rejects_negative() {
+        assert!(MessageId::new(-1).is_err());
+    }
+
+    #[test]
+    fn message_id_rejects_zero() {
+        assert!(MessageId::new(0).is_err());
+    }
+
+    #[test]
+    fn message_id_accepts_positive() {
+        let result = MessageId::new(456);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().get(), 456);
+    }
+
+    #[test]
+    fn message_id_display() {
+        let id = MessageId::new(789).unwrap();
+        assert_eq!(format!("{}", id), "789");
+    }
+
+    #[test]
+    fn user_id_rejects_negative() {
         assert!(UserId::new(-1).is_err());
     }
 
@@ -407,6 +1555,22 @@ mod tests {
         assert_eq!(result.unwrap().as_str(), "valid_user123");
     }
 
+    #[test]
+    fn username_strips_leading_at() {
+        let with_at = Username::new("@valid_user").unwrap();
+        let without_at = Username::new("valid_user").unwrap();
+
+        assert_eq!(with_at, without_at);
+        assert_eq!(with_at.as_str(), "valid_user");
+    }
+
+    #[test]
+    fn username_rejects_embedded_or_repeated_at() {
+        assert!(Username::new("inv@lid").is_err());
+        assert!(Username::new("@@valid_user").is_err());
+        assert!(Username::new("@").is_err());
+    }
+
     #[test]
     fn username_display() {
         let username = Username::new("telegram_user").unwrap();
@@ -472,6 +1636,12 @@ mod tests {
         assert_eq!(json, "\"videonote\"");
     }
 
+    #[test]
+    fn media_type_unknown_serializes() {
+        let json = serde_json::to_string(&MediaType::Unknown).unwrap();
+        assert_eq!(json, "\"unknown\"");
+    }
+
     #[test]
     fn media_type_all_variants_serialize() {
         let variants = vec![
@@ -489,6 +1659,7 @@ mod tests {
             MediaType::Venue,
             MediaType::Poll,
             MediaType::Dice,
+            MediaType::Unknown,
         ];
 
         for variant in variants {
@@ -497,6 +1668,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn media_type_from_str_accepts_serialized_names() {
+        assert_eq!("photo".parse::<MediaType>().unwrap(), MediaType::Photo);
+        assert_eq!(
+            "Document".parse::<MediaType>().unwrap(),
+            MediaType::Document
+        );
+        assert_eq!(
+            "videonote".parse::<MediaType>().unwrap(),
+            MediaType::VideoNote
+        );
+    }
+
+    #[test]
+    fn media_type_from_str_rejects_invalid() {
+        for value in ["pdf", "", "video_note"] {
+            let result = value.parse::<MediaType>();
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                value
+            );
+        }
+    }
+
     // =========================================================================
     // Message Tests
     // =========================================================================
@@ -507,32 +1703,92 @@ mod tests {
             id: MessageId::new(1).unwrap(),
             channel_id: ChannelId::new(100).unwrap(),
             channel_name: ChannelName::new("Test").unwrap(),
-            channel_username: Username::new("testchan").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
             text: "test".to_string(),
             timestamp: Utc::now() - chrono::Duration::hours(24),
             sender_id: None,
             sender_name: None,
             has_media: false,
             media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
         };
 
         assert!(msg.is_recent(48));
         assert!(!msg.is_recent(12));
     }
 
+    #[test]
+    fn message_age_is_time_since_posting() {
+        let mut message = snippet_test_message("old news");
+        message.timestamp = Utc::now() - chrono::Duration::hours(25);
+
+        let age = message.age();
+
+        assert!(age >= chrono::Duration::hours(25));
+        assert!(age < chrono::Duration::hours(25) + chrono::Duration::minutes(1));
+    }
+
+    #[test]
+    fn message_is_within_explicit_windows() {
+        let now = Utc::now();
+        let mut message = snippet_test_message("old news");
+        message.timestamp = now - chrono::Duration::hours(25);
+        let hours_ago = |hours| now - chrono::Duration::hours(hours);
+
+        assert!(message.is_within(hours_ago(48), now));
+        assert!(message.is_within(hours_ago(26), hours_ago(24)));
+        assert!(!message.is_within(hours_ago(24), now));
+        assert!(!message.is_within(hours_ago(72), hours_ago(26)));
+        // Empty or inverted windows contain nothing
+        assert!(!message.is_within(now, hours_ago(48)));
+    }
+
+    #[test]
+    fn message_is_within_includes_boundaries() {
+        let now = Utc::now();
+        let mut message = snippet_test_message("old news");
+        message.timestamp = now - chrono::Duration::hours(25);
+
+        assert!(message.is_within(message.timestamp, now));
+        assert!(message.is_within(now - chrono::Duration::hours(48), message.timestamp));
+        assert!(message.is_within(message.timestamp, message.timestamp));
+        assert!(!message.is_within(message.timestamp + chrono::Duration::seconds(1), now));
+    }
+
     #[test]
     fn message_is_text_only() {
         let msg = Message {
             id: MessageId::new(1).unwrap(),
             channel_id: ChannelId::new(100).unwrap(),
             channel_name: ChannelName::new("Test").unwrap(),
-            channel_username: Username::new("testchan").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
             text: "test".to_string(),
             timestamp: Utc::now(),
             sender_id: None,
             sender_name: None,
             has_media: false,
             media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
         };
 
         assert!(msg.is_text_only());
@@ -544,13 +1800,53 @@ mod tests {
             id: MessageId::new(1).unwrap(),
             channel_id: ChannelId::new(100).unwrap(),
             channel_name: ChannelName::new("Test").unwrap(),
-            channel_username: Username::new("testchan").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
             text: "".to_string(),
             timestamp: Utc::now(),
             sender_id: None,
             sender_name: None,
             has_media: true,
             media_type: MediaType::Photo,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        };
+
+        assert!(!msg.is_text_only());
+    }
+
+    #[test]
+    fn message_with_unknown_media_not_text_only() {
+        let msg = Message {
+            id: MessageId::new(1).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
+            text: "".to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: true,
+            media_type: MediaType::Unknown,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
         };
 
         assert!(!msg.is_text_only());
@@ -562,13 +1858,24 @@ mod tests {
             id: MessageId::new(1).unwrap(),
             channel_id: ChannelId::new(100).unwrap(),
             channel_name: ChannelName::new("Test").unwrap(),
-            channel_username: Username::new("testchan").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
             text: "Hello world".to_string(),
             timestamp: Utc::now(),
             sender_id: Some(UserId::new(42).unwrap()),
             sender_name: Some("Alice".to_string()),
             has_media: false,
             media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -579,16 +1886,322 @@ mod tests {
         assert_eq!(deserialized.text, msg.text);
     }
 
+    #[test]
+    fn message_serialization_without_channel_username() {
+        let mut msg = snippet_test_message("Hello from a private channel");
+        msg.channel_username = None;
+
+        let value = serde_json::to_value(&msg).unwrap();
+        assert!(value["channel_username"].is_null());
+
+        let deserialized: Message = serde_json::from_value(value).unwrap();
+        assert!(deserialized.channel_username.is_none());
+        assert!(
+            search_result(vec![msg])
+                .to_csv(false)
+                .starts_with("1,100,,")
+        );
+    }
+
+    #[test]
+    fn message_truncate_text_records_original_length() {
+        let mut msg = Message {
+            id: MessageId::new(1).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
+            text: "Привет, мир!".to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        };
+
+        msg.truncate_text(6);
+
+        assert_eq!(msg.text, "Привет");
+        assert_eq!(msg.original_length, Some(12));
+    }
+
+    #[test]
+    fn message_truncate_text_keeps_short_text() {
+        let mut msg = Message {
+            id: MessageId::new(1).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
+            text: "short".to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        };
+
+        msg.truncate_text(5);
+
+        assert_eq!(msg.text, "short");
+        assert!(msg.original_length.is_none());
+    }
+
+    fn snippet_test_message(text: &str) -> Message {
+        Message {
+            id: MessageId::new(1).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test").unwrap(),
+            channel_username: Some(Username::new("testchan").unwrap()),
+            text: text.to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    #[test]
+    fn apply_snippet_match_in_middle() {
+        let mut msg = snippet_test_message("Сегодня вышла новая модель ИИ для перевода текстов");
+
+        msg.apply_snippet("модель", 6);
+
+        assert_eq!(msg.text, "…новая **модель** ИИ дл…");
+    }
+
+    #[test]
+    fn apply_snippet_match_at_start() {
+        let mut msg = snippet_test_message("Bitcoin hits a new all-time high today");
+
+        msg.apply_snippet("bitcoin", 5);
+
+        assert_eq!(msg.text, "**Bitcoin** hits…");
+    }
+
+    #[test]
+    fn apply_snippet_match_near_end() {
+        let mut msg = snippet_test_message("Обзор рынка: растёт крипта");
+
+        msg.apply_snippet("КРИПТА", 7);
+
+        assert_eq!(msg.text, "…растёт **крипта**");
+    }
+
+    #[test]
+    fn apply_snippet_without_match_keeps_leading_window() {
+        let mut msg = snippet_test_message("abcdefghij");
+
+        msg.apply_snippet("xyz", 2);
+
+        assert_eq!(msg.text, "abcd…");
+    }
+
     // =========================================================================
     // Channel Tests
     // =========================================================================
 
+    #[test]
+    fn channel_matches_name_or_username_ignoring_case() {
+        let channel = Channel {
+            id: ChannelId::new(1).unwrap(),
+            name: ChannelName::new("Крипто Новости").unwrap(),
+            username: Some(Username::new("crypto_news").unwrap()),
+            description: None,
+            member_count: 0,
+            is_verified: false,
+            is_public: true,
+            is_subscribed: true,
+            last_message_date: None,
+        };
+
+        assert!(channel.matches_name("КРИПТО"));
+        assert!(channel.matches_name("новости"));
+        assert!(channel.matches_name("Crypto"));
+        assert!(channel.matches_name(""));
+        assert!(!channel.matches_name("weather"));
+    }
+
+    fn sort_test_channel(
+        id: i64,
+        name: &str,
+        members: u64,
+        last_hours_ago: Option<i64>,
+    ) -> Channel {
+        Channel {
+            id: ChannelId::new(id).unwrap(),
+            name: ChannelName::new(name).unwrap(),
+            username: Some(Username::new(format!("channel_{}", id)).unwrap()),
+            description: None,
+            member_count: members,
+            is_verified: false,
+            is_public: true,
+            is_subscribed: true,
+            last_message_date: last_hours_ago
+                .map(|hours| Utc::now() - chrono::Duration::hours(hours)),
+        }
+    }
+
+    fn sort_test_channels() -> Vec<Channel> {
+        vec![
+            sort_test_channel(1, "bravo", 500, None),
+            sort_test_channel(2, "Alpha", 100, Some(5)),
+            sort_test_channel(3, "charlie", 900, Some(1)),
+        ]
+    }
+
+    fn channel_ids(channels: &[Channel]) -> Vec<i64> {
+        channels.iter().map(|channel| channel.id.get()).collect()
+    }
+
+    #[test]
+    fn channel_sort_by_name_ignores_case() {
+        let mut channels = sort_test_channels();
+
+        ChannelSort::Name.sort(&mut channels, false);
+        assert_eq!(channel_ids(&channels), vec![2, 1, 3]);
+
+        ChannelSort::Name.sort(&mut channels, true);
+        assert_eq!(channel_ids(&channels), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn channel_sort_by_member_count() {
+        let mut channels = sort_test_channels();
+
+        ChannelSort::MemberCount.sort(&mut channels, false);
+        assert_eq!(channel_ids(&channels), vec![2, 1, 3]);
+
+        ChannelSort::MemberCount.sort(&mut channels, true);
+        assert_eq!(channel_ids(&channels), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn channel_sort_by_last_message_date_puts_missing_dates_last() {
+        let mut channels = sort_test_channels();
+
+        ChannelSort::LastMessageDate.sort(&mut channels, false);
+        assert_eq!(channel_ids(&channels), vec![2, 3, 1]);
+
+        ChannelSort::LastMessageDate.sort(&mut channels, true);
+        assert_eq!(channel_ids(&channels), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn channel_sort_deserializes_snake_case() {
+        let sort: ChannelSort = serde_json::from_str("\"last_message_date\"").unwrap();
+        assert_eq!(sort, ChannelSort::LastMessageDate);
+        assert!(serde_json::from_str::<ChannelSort>("\"views\"").is_err());
+    }
+
+    #[test]
+    fn phone_number_accepts_e164() {
+        for phone in [
+            "+1234567",
+            "+12345678901",
+            " +447911123456 ",
+            "+123456789012345",
+        ] {
+            let number = PhoneNumber::new(phone).unwrap();
+            assert_eq!(number.expose(), phone.trim());
+        }
+    }
+
+    #[test]
+    fn phone_number_rejects_invalid_formats() {
+        for phone in [
+            "",
+            "+",
+            "1234567890",
+            "+123456",
+            "+1234567890123456",
+            "+1 234 567 890",
+            "+12-345-67890",
+            "+12345abc90",
+            "++1234567890",
+        ] {
+            let result = PhoneNumber::new(phone);
+            assert!(
+                matches!(result, Err(Error::InvalidInput(_))),
+                "expected InvalidInput for {:?}",
+                phone
+            );
+        }
+    }
+
+    #[test]
+    fn phone_number_is_redacted_in_debug_and_errors() {
+        let number = PhoneNumber::new("+1234567890").unwrap();
+        assert!(!format!("{:?}", number).contains("1234567890"));
+
+        let error = PhoneNumber::new("+1234567890123456").unwrap_err();
+        assert!(!error.to_string().contains("1234567890123456"));
+    }
+
+    #[test]
+    fn channel_stats_record_aggregates_messages() {
+        let newest = Utc::now();
+        let oldest = newest - chrono::Duration::hours(5);
+        let mut stats = ChannelStats::default();
+
+        stats.record(newest - chrono::Duration::hours(1), MediaType::Photo);
+        stats.record(newest, MediaType::None);
+        stats.record(oldest, MediaType::Document);
+
+        assert_eq!(stats.message_count, 3);
+        assert_eq!(stats.media_count, 2);
+        assert_eq!(stats.first_seen, Some(oldest));
+        assert_eq!(stats.last_seen, Some(newest));
+    }
+
+    #[test]
+    fn channel_stats_default_is_empty() {
+        let stats = ChannelStats::default();
+
+        assert_eq!(stats.message_count, 0);
+        assert_eq!(stats.media_count, 0);
+        assert!(stats.first_seen.is_none());
+        assert!(stats.last_seen.is_none());
+    }
+
     #[test]
     fn channel_serialization() {
         let channel = Channel {
             id: ChannelId::new(200).unwrap(),
             name: ChannelName::new("Tech News").unwrap(),
-            username: Username::new("technews").unwrap(),
+            username: Some(Username::new("technews").unwrap()),
             description: Some("Latest tech updates".to_string()),
             member_count: 5000,
             is_verified: true,
@@ -601,39 +2214,455 @@ mod tests {
         let deserialized: Channel = serde_json::from_str(&json).unwrap();
 
         assert_eq!(deserialized.id, channel.id);
+        assert_eq!(deserialized.username, channel.username);
         assert_eq!(deserialized.member_count, channel.member_count);
         assert_eq!(deserialized.is_verified, channel.is_verified);
     }
 
-    // =========================================================================
-    // SearchParams Tests
-    // =========================================================================
-
     #[test]
-    fn search_params_default() {
-        let params = SearchParams::default();
-        assert_eq!(params.query, "");
-        assert_eq!(params.hours_back, SearchParams::DEFAULT_HOURS_BACK);
-        assert_eq!(params.limit, SearchParams::DEFAULT_LIMIT);
-        assert!(params.channel_id.is_none());
+    fn channel_serialization_without_username() {
+        let channel = Channel {
+            id: ChannelId::new(300).unwrap(),
+            name: ChannelName::new("Private Group").unwrap(),
+            username: None,
+            description: None,
+            member_count: 12,
+            is_verified: false,
+            is_public: false,
+            is_subscribed: true,
+            last_message_date: None,
+        };
+
+        let value = serde_json::to_value(&channel).unwrap();
+        assert!(value["username"].is_null());
+
+        let deserialized: Channel = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized.username, None);
+        assert!(!deserialized.matches_name("channel_300"));
+        assert!(deserialized.matches_name("private"));
     }
 
     #[test]
-    fn search_params_new() {
-        let params = SearchParams::new("AI news");
-        assert_eq!(params.query, "AI news");
-        assert_eq!(params.hours_back, 48);
-        assert_eq!(params.limit, 20);
+    fn channel_folder_contains() {
+        let folder = ChannelFolder {
+            folder_name: "News".to_string(),
+            channel_ids: vec![ChannelId::new(1).unwrap(), ChannelId::new(2).unwrap()],
+        };
+
+        assert!(folder.contains(ChannelId::new(2).unwrap()));
+        assert!(!folder.contains(ChannelId::new(3).unwrap()));
     }
 
+    // =========================================================================
+    // ChannelBlocklist Tests
+    // =========================================================================
+
     #[test]
-    fn search_params_constants() {
+    fn channel_blocklist_matches_id() {
+        let blocklist = ChannelBlocklist::new(&["12345".to_string()]);
+        let username = Username::new("somechannel").unwrap();
+
+        assert!(blocklist.is_blocked(ChannelId::new(12345).unwrap(), Some(&username)));
+        assert!(!blocklist.is_blocked(ChannelId::new(54321).unwrap(), Some(&username)));
+        assert!(blocklist.is_blocked(ChannelId::new(12345).unwrap(), None));
+    }
+
+    #[test]
+    fn channel_blocklist_matches_username_case_insensitive() {
+        let blocklist = ChannelBlocklist::new(&["@SpamChannel".to_string()]);
+        let id = ChannelId::new(1).unwrap();
+
+        assert!(blocklist.is_blocked(id, Some(&Username::new("spamchannel").unwrap())));
+        assert!(!blocklist.is_blocked(id, Some(&Username::new("goodchannel").unwrap())));
+        assert!(!blocklist.is_blocked(id, None));
+    }
+
+    #[test]
+    fn channel_blocklist_accepts_prefixed_ids() {
+        let blocklist = ChannelBlocklist::new(&["-1001234567890".to_string()]);
+
+        assert!(blocklist.is_blocked(ChannelId::new(1234567890).unwrap(), None));
+    }
+
+    #[test]
+    fn channel_blocklist_skips_invalid_entries() {
+        let entries = ["-5".to_string(), "@no".to_string(), "12345".to_string()];
+
+        let blocklist = ChannelBlocklist::new(&entries);
+
+        assert!(blocklist.is_blocked(ChannelId::new(12345).unwrap(), None));
+        assert!(!blocklist.is_blocked(ChannelId::new(5).unwrap(), None));
+        assert!(matches!(
+            ChannelBlocklist::try_new(&entries),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn channel_blocklist_empty_by_default() {
+        let blocklist = ChannelBlocklist::default();
+        assert!(blocklist.is_empty());
+        assert!(!ChannelBlocklist::new(&["spam_channel".to_string()]).is_empty());
+    }
+
+    // =========================================================================
+    // SearchParams Tests
+    // =========================================================================
+
+    #[test]
+    fn search_params_default() {
+        let params = SearchParams::default();
+        assert_eq!(params.query, "");
+        assert_eq!(params.hours_back, SearchParams::DEFAULT_HOURS_BACK);
+        assert_eq!(params.limit, SearchParams::DEFAULT_LIMIT);
+        assert!(params.channel_id.is_none());
+    }
+
+    #[test]
+    fn search_params_new() {
+        let params = SearchParams::new("AI news");
+        assert_eq!(params.query, "AI news");
+        assert_eq!(params.hours_back, 48);
+        assert_eq!(params.limit, 20);
+    }
+
+    #[test]
+    fn search_params_constants() {
         assert_eq!(SearchParams::DEFAULT_HOURS_BACK, 48);
         assert_eq!(SearchParams::MAX_HOURS_BACK, 72);
         assert_eq!(SearchParams::DEFAULT_LIMIT, 20);
         assert_eq!(SearchParams::MAX_LIMIT, 100);
     }
 
+    fn filter_test_message(text: &str, media_type: MediaType) -> Message {
+        Message {
+            id: MessageId::new(1).unwrap(),
+            channel_id: ChannelId::new(100).unwrap(),
+            channel_name: ChannelName::new("Test").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: text.to_string(),
+            timestamp: Utc::now(),
+            sender_id: Some(UserId::new(7).unwrap()),
+            sender_name: None,
+            has_media: media_type != MediaType::None,
+            media_type,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    #[test]
+    fn search_params_accepts_everything_without_filters() {
+        let params = SearchParams::new("report");
+
+        assert!(params.accepts(&filter_test_message("report", MediaType::None)));
+        assert!(params.accepts(&filter_test_message("report", MediaType::Photo)));
+    }
+
+    #[test]
+    fn search_params_filters_by_media_type() {
+        let params = SearchParams {
+            media_types: Some(vec![MediaType::Document]),
+            ..SearchParams::new("report")
+        };
+
+        assert!(params.accepts(&filter_test_message("report.pdf", MediaType::Document)));
+        assert!(!params.accepts(&filter_test_message("report", MediaType::None)));
+        assert!(!params.accepts(&filter_test_message("report", MediaType::Photo)));
+    }
+
+    #[test]
+    fn search_params_drops_blocklisted_channels() {
+        let by_id = SearchParams {
+            blocklist: ChannelBlocklist::new(&["100".to_string()]),
+            ..SearchParams::new("report")
+        };
+        let by_username = SearchParams {
+            blocklist: ChannelBlocklist::new(&["@TestChannel".to_string()]),
+            ..SearchParams::new("report")
+        };
+        let other = SearchParams {
+            blocklist: ChannelBlocklist::new(&["200".to_string()]),
+            ..SearchParams::new("report")
+        };
+
+        assert!(!by_id.accepts(&filter_test_message("report", MediaType::None)));
+        assert!(!by_username.accepts(&filter_test_message("report", MediaType::None)));
+        assert!(other.accepts(&filter_test_message("report", MediaType::None)));
+    }
+
+    #[test]
+    fn search_params_filters_by_sender() {
+        let params = SearchParams {
+            sender_id: Some(UserId::new(7).unwrap()),
+            ..SearchParams::new("report")
+        };
+        let mut other_sender = filter_test_message("report", MediaType::None);
+        other_sender.sender_id = Some(UserId::new(8).unwrap());
+        let mut anonymous = filter_test_message("report", MediaType::None);
+        anonymous.sender_id = None;
+
+        assert!(params.accepts(&filter_test_message("report", MediaType::None)));
+        assert!(!params.accepts(&other_sender));
+        assert!(!params.accepts(&anonymous));
+    }
+
+    #[test]
+    fn sanitized_caps_hours_back_and_limit() {
+        let params = SearchParams {
+            hours_back: 1000,
+            limit: 5000,
+            ..SearchParams::new("bitcoin")
+        }
+        .sanitized()
+        .unwrap();
+
+        assert_eq!(params.hours_back, SearchParams::MAX_HOURS_BACK);
+        assert_eq!(params.limit, SearchParams::MAX_LIMIT);
+    }
+
+    #[test]
+    fn sanitized_keeps_values_within_limits() {
+        let params = SearchParams {
+            hours_back: 12,
+            limit: 5,
+            ..SearchParams::new("bitcoin")
+        }
+        .sanitized()
+        .unwrap();
+
+        assert_eq!(params.hours_back, 12);
+        assert_eq!(params.limit, 5);
+        assert_eq!(params.query, "bitcoin");
+    }
+
+    #[test]
+    fn sanitized_rejects_empty_query() {
+        for query in ["", "   "] {
+            let result = SearchParams::new(query).sanitized();
+            assert!(matches!(result, Err(Error::InvalidInput(_))));
+        }
+    }
+
+    #[test]
+    fn sanitized_rejects_empty_query_and_queries() {
+        let result = SearchParams {
+            queries: vec!["".to_string(), "  ".to_string()],
+            ..SearchParams::new("")
+        }
+        .sanitized();
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn sanitized_accepts_queries_without_query() {
+        let params = SearchParams {
+            queries: vec!["bitcoin".to_string()],
+            ..SearchParams::new("")
+        }
+        .sanitized()
+        .unwrap();
+
+        assert_eq!(params.terms(), vec!["bitcoin"]);
+    }
+
+    #[test]
+    fn terms_merge_query_and_queries_without_duplicates() {
+        let params = SearchParams {
+            queries: vec![
+                "ethereum".to_string(),
+                " bitcoin ".to_string(),
+                "".to_string(),
+                "solana".to_string(),
+            ],
+            ..SearchParams::new("bitcoin")
+        };
+
+        assert_eq!(params.terms(), vec!["bitcoin", "ethereum", "solana"]);
+    }
+
+    #[test]
+    fn accepts_messages_matching_any_query() {
+        let params = SearchParams {
+            queries: vec!["ethereum".to_string()],
+            match_mode: MatchMode::WholeWord,
+            ..SearchParams::new("bitcoin")
+        };
+
+        assert!(params.accepts(&filter_test_message("bitcoin rally", MediaType::None)));
+        assert!(params.accepts(&filter_test_message("ethereum upgrade", MediaType::None)));
+        assert!(!params.accepts(&filter_test_message("solana outage", MediaType::None)));
+        assert_eq!(params.matched_term("ethereum upgrade"), Some("ethereum"));
+    }
+
+    #[test]
+    fn matched_term_prefers_term_present_in_text() {
+        let params = SearchParams {
+            queries: vec!["ethereum".to_string()],
+            ..SearchParams::new("bitcoin")
+        };
+
+        assert_eq!(params.matched_term("Ethereum upgrade"), Some("ethereum"));
+        assert_eq!(params.matched_term("BTC rally"), Some("bitcoin"));
+    }
+
+    #[test]
+    fn sanitized_rejects_zero_limit() {
+        let result = SearchParams {
+            limit: 0,
+            ..SearchParams::new("bitcoin")
+        }
+        .sanitized();
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn sanitized_rejects_cursor_without_channel() {
+        let result = SearchParams {
+            before_id: Some(MessageId::new(50).unwrap()),
+            ..SearchParams::new("bitcoin")
+        }
+        .sanitized();
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn accepts_only_messages_older_than_cursor() {
+        let params = SearchParams {
+            channel_id: Some(ChannelId::new(100).unwrap()),
+            before_id: Some(MessageId::new(50).unwrap()),
+            ..SearchParams::new("report")
+        };
+        let mut older = filter_test_message("report", MediaType::None);
+        older.id = MessageId::new(49).unwrap();
+        let mut cursor = filter_test_message("report", MediaType::None);
+        cursor.id = MessageId::new(50).unwrap();
+        let mut newer = filter_test_message("report", MediaType::None);
+        newer.id = MessageId::new(51).unwrap();
+
+        assert!(params.accepts(&older));
+        assert!(!params.accepts(&cursor));
+        assert!(!params.accepts(&newer));
+    }
+
+    #[test]
+    fn accepts_filters_edited_only() {
+        let params = SearchParams {
+            edited_only: true,
+            ..SearchParams::new("report")
+        };
+        let mut edited = filter_test_message("report", MediaType::None);
+        edited.edit_date = Some(edited.timestamp + chrono::Duration::minutes(5));
+        let original = filter_test_message("report", MediaType::None);
+
+        assert!(params.accepts(&edited));
+        assert!(!params.accepts(&original));
+        assert!(SearchParams::new("report").accepts(&original));
+    }
+
+    #[test]
+    fn accepts_filters_by_min_views() {
+        let params = SearchParams {
+            min_views: Some(1000),
+            ..SearchParams::new("report")
+        };
+        let mut popular = filter_test_message("report", MediaType::None);
+        popular.views = Some(1000);
+        let mut quiet = filter_test_message("report", MediaType::None);
+        quiet.views = Some(999);
+        let unknown = filter_test_message("report", MediaType::None);
+
+        assert!(params.accepts(&popular));
+        assert!(!params.accepts(&quiet));
+        assert!(!params.accepts(&unknown));
+        assert!(SearchParams::new("report").accepts(&unknown));
+    }
+
+    #[test]
+    fn match_mode_substring_keeps_telegram_results() {
+        assert!(MatchMode::Substring.matches("Bitcoin ETF approved", "bitcoin"));
+        assert!(MatchMode::Substring.matches("Новости рынка", "новость"));
+    }
+
+    #[test]
+    fn match_mode_case_sensitive() {
+        assert!(MatchMode::CaseSensitive.matches("Bitcoin ETF approved", "ETF"));
+        assert!(!MatchMode::CaseSensitive.matches("Bitcoin etf approved", "ETF"));
+        assert!(MatchMode::CaseSensitive.matches("Курс Рубля", "Рубля"));
+        assert!(!MatchMode::CaseSensitive.matches("курс рубля", "Рубля"));
+    }
+
+    #[test]
+    fn match_mode_whole_word_respects_boundaries() {
+        assert!(MatchMode::WholeWord.matches("The AI revolution", "ai"));
+        assert!(MatchMode::WholeWord.matches("News: AI, again", "AI"));
+        assert!(!MatchMode::WholeWord.matches("Said the chairman", "ai"));
+        assert!(!MatchMode::WholeWord.matches("snake_ai_case", "ai"));
+    }
+
+    #[test]
+    fn match_mode_whole_word_cyrillic() {
+        assert!(MatchMode::WholeWord.matches("Курс РУБЛЯ вырос", "рубля"));
+        assert!(MatchMode::WholeWord.matches("рубль", "Рубль"));
+        assert!(!MatchMode::WholeWord.matches("Курс рубля вырос", "руб"));
+        assert!(!MatchMode::WholeWord.matches("Санкции против России", "кции"));
+        // Later occurrence on a boundary still matches
+        assert!(MatchMode::WholeWord.matches("рублевый рубль", "рубль"));
+    }
+
+    #[test]
+    fn match_mode_serde_snake_case() {
+        let json = serde_json::to_string(&MatchMode::WholeWord).unwrap();
+        assert_eq!(json, "\"whole_word\"");
+
+        let mode: MatchMode = serde_json::from_str("\"case_sensitive\"").unwrap();
+        assert_eq!(mode, MatchMode::CaseSensitive);
+        assert_eq!(MatchMode::default(), MatchMode::Substring);
+    }
+
+    #[test]
+    fn search_params_time_range_defaults_to_hours_back() {
+        let now = Utc::now();
+        let params = SearchParams::new("report");
+
+        let (start, end) = params.time_range(now);
+
+        assert_eq!(end, now);
+        assert_eq!(end - start, chrono::Duration::hours(48));
+    }
+
+    #[test]
+    fn search_params_explicit_range_ignores_hours_back() {
+        let after = "2025-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let before = "2025-01-07T23:59:59Z".parse::<DateTime<Utc>>().unwrap();
+        let params = SearchParams {
+            after: Some(after),
+            before: Some(before),
+            ..SearchParams::new("report")
+        };
+
+        assert_eq!(params.time_range(Utc::now()), (after, before));
+
+        let mut inside = filter_test_message("report", MediaType::None);
+        inside.timestamp = "2025-01-03T12:00:00Z".parse().unwrap();
+        let mut outside = filter_test_message("report", MediaType::None);
+        outside.timestamp = "2025-01-08T00:00:00Z".parse().unwrap();
+        assert!(params.accepts(&inside));
+        assert!(!params.accepts(&outside));
+    }
+
     // =========================================================================
     // SearchResult Tests
     // =========================================================================
@@ -658,4 +2687,531 @@ mod tests {
         assert_eq!(deserialized.search_time_ms, 150);
         assert_eq!(deserialized.query_metadata.query, "test");
     }
+
+    fn search_result(messages: Vec<Message>) -> SearchResult {
+        SearchResult {
+            total_found: messages.len() as u64,
+            messages,
+            search_time_ms: 10,
+            query_metadata: QueryMetadata {
+                query: "launch".to_string(),
+                hours_back: 48,
+                channels_searched: 2,
+            },
+        }
+    }
+
+    fn hit(channel_id: i64, message_id: i64, minutes_ago: i64) -> Message {
+        let mut message = snippet_test_message("launch announcement");
+        message.channel_id = ChannelId::new(channel_id).unwrap();
+        message.id = MessageId::new(message_id).unwrap();
+        message.timestamp = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        message
+    }
+
+    #[test]
+    fn search_result_dedup_by_link_removes_repeated_hits() {
+        let mut result = search_result(vec![
+            hit(100, 1, 5),
+            hit(200, 1, 5),
+            hit(100, 1, 5),
+            hit(100, 2, 3),
+            hit(200, 1, 5),
+        ]);
+
+        result.dedup_by_link();
+
+        let keys: Vec<(i64, i64)> = result
+            .messages
+            .iter()
+            .map(|message| (message.channel_id.get(), message.id.get()))
+            .collect();
+        assert_eq!(keys, vec![(100, 1), (200, 1), (100, 2)]);
+        assert_eq!(result.total_found, 3);
+    }
+
+    #[test]
+    fn search_result_dedup_by_link_without_duplicates_is_noop() {
+        let mut result = search_result(vec![hit(100, 1, 5), hit(100, 2, 3)]);
+
+        result.dedup_by_link();
+
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.total_found, 2);
+    }
+
+    #[test]
+    fn search_result_sort_by_timestamp_desc_orders_newest_first() {
+        let mut result = search_result(vec![hit(100, 1, 30), hit(200, 7, 1), hit(100, 2, 10)]);
+
+        result.sort_by_timestamp_desc();
+
+        let ids: Vec<i64> = result
+            .messages
+            .iter()
+            .map(|message| message.id.get())
+            .collect();
+        assert_eq!(ids, vec![7, 2, 1]);
+    }
+
+    #[test]
+    fn normalize_text_strips_zero_width_characters() {
+        assert_eq!(normalize_text("ру\u{200B}бль"), "рубль");
+        assert_eq!(normalize_text("\u{FEFF}курс\u{00AD}ы"), "курсы");
+    }
+
+    #[test]
+    fn normalize_text_folds_quotes_and_dashes() {
+        assert_eq!(
+            normalize_text("«Газпром» — “лидер” ‘рынка’ 1–2"),
+            "\"Газпром\" - \"лидер\" 'рынка' 1-2"
+        );
+    }
+
+    #[test]
+    fn normalize_text_maps_homoglyphs_only_in_cyrillic_words() {
+        // Latin "p", "y" inside a Cyrillic word
+        assert_eq!(normalize_text("pyбль"), "рубль");
+        // Latin "C", "a" mixed into Cyrillic
+        assert_eq!(normalize_text("Сбербанк Cбepбaнк"), "Сбербанк Сбербанк");
+        // Pure Latin words are left alone
+        assert_eq!(normalize_text("copy paste"), "copy paste");
+    }
+
+    #[test]
+    fn whole_word_query_matches_through_homoglyphs_and_zero_width() {
+        let params = SearchParams {
+            match_mode: MatchMode::WholeWord,
+            ..SearchParams::new("рубль")
+        };
+        let mut message = filter_test_message("Курс pyбль падает", MediaType::None);
+        assert!(params.accepts(&message));
+
+        message.text = "Курс ру\u{200B}бль падает".to_string();
+        assert!(params.accepts(&message));
+
+        message.text = "Курс рублёвый".to_string();
+        assert!(!params.accepts(&message));
+    }
+
+    #[test]
+    fn case_sensitive_query_matches_through_homoglyphs() {
+        assert!(MatchMode::CaseSensitive.matches("Акции Cбepбaнкa растут", "Сбербанка"));
+        assert!(!MatchMode::CaseSensitive.matches("Акции сбербанка растут", "Сбербанка"));
+    }
+
+    #[test]
+    fn relevance_score_counts_normalized_matches() {
+        assert_eq!(
+            relevance_score("pyбль рубль", "рубль"),
+            relevance_score("рубль рубль", "рубль")
+        );
+    }
+
+    #[test]
+    fn relevance_score_is_zero_without_match() {
+        assert_eq!(relevance_score("nothing relevant here", "rust"), 0.0);
+        assert_eq!(relevance_score("", "rust"), 0.0);
+        assert_eq!(relevance_score("rust", ""), 0.0);
+    }
+
+    #[test]
+    fn relevance_score_grows_with_term_frequency() {
+        let once = relevance_score("rust release notes today", "rust");
+        let twice = relevance_score("rust release notes rust", "rust");
+        assert!(twice > once);
+        assert_eq!(once, 0.5);
+    }
+
+    #[test]
+    fn relevance_score_counts_every_query_term_case_insensitively() {
+        let both = relevance_score("Новая модель ИИ и новая МОДЕЛЬ", "модель ии");
+        let one = relevance_score("Новая модель и новая версия", "модель ии");
+        assert!(both > one);
+    }
+
+    #[test]
+    fn relevance_score_damps_long_messages() {
+        let short = relevance_score("rust news", "rust");
+        let long = relevance_score("rust news with a lot of unrelated words around it", "rust");
+        assert!(short > long);
+    }
+
+    #[test]
+    fn search_result_rank_by_relevance_orders_by_score() {
+        let mut low = hit(100, 1, 1);
+        low.text = "launch of something".to_string();
+        let mut high = hit(100, 2, 30);
+        high.text = "launch launch launch".to_string();
+        let mut none = hit(100, 3, 0);
+        none.text = "unrelated".to_string();
+        let mut result = search_result(vec![none, low, high]);
+
+        result.rank_by_relevance("launch");
+
+        let ids: Vec<i64> = result
+            .messages
+            .iter()
+            .map(|message| message.id.get())
+            .collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+        assert_eq!(result.messages[2].score, Some(0.0));
+    }
+
+    #[test]
+    fn message_score_is_skipped_when_unset() {
+        let mut message = hit(100, 1, 1);
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("score"));
+
+        message.score = Some(1.5);
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"score\":1.5"));
+    }
+
+    #[test]
+    fn highlight_marks_every_match_case_insensitively() {
+        let msg = snippet_test_message("Rust news: RUST 2.0 and rust tooling");
+        assert_eq!(
+            msg.highlight("rust"),
+            "«Rust» news: «RUST» 2.0 and «rust» tooling"
+        );
+    }
+
+    #[test]
+    fn highlight_merges_overlapping_matches() {
+        let msg = snippet_test_message("baaab");
+        assert_eq!(msg.highlight("aa"), "b«aaa»b");
+    }
+
+    #[test]
+    fn highlight_merges_adjacent_matches() {
+        let msg = snippet_test_message("abab!");
+        assert_eq!(msg.highlight("ab"), "«abab»!");
+    }
+
+    #[test]
+    fn highlight_cyrillic_query() {
+        let msg = snippet_test_message("Новая Модель ИИ: модель для перевода");
+        assert_eq!(
+            msg.highlight("МОДЕЛЬ"),
+            "Новая «Модель» ИИ: «модель» для перевода"
+        );
+    }
+
+    #[test]
+    fn highlight_with_custom_delimiters() {
+        let msg = snippet_test_message("emoji 🙂 test 🙂");
+        assert_eq!(
+            msg.highlight_with("🙂", "<b>", "</b>"),
+            "emoji <b>🙂</b> test <b>🙂</b>"
+        );
+    }
+
+    #[test]
+    fn highlight_without_match_returns_text_unchanged() {
+        let msg = snippet_test_message("nothing to see");
+        assert_eq!(msg.highlight("rust"), "nothing to see");
+        assert_eq!(msg.highlight("   "), "nothing to see");
+    }
+
+    #[test]
+    fn highlight_matches_normalized_text() {
+        // Latin "p" and "y" inside a Cyrillic word, and a zero-width space in "rust"
+        let msg = snippet_test_message("Курс pyбля: ru\u{200B}st");
+        assert_eq!(msg.highlight("рубля"), "Курс «pyбля»: ru\u{200B}st");
+        assert_eq!(msg.highlight("rust"), "Курс pyбля: «ru\u{200B}st»");
+    }
+
+    #[test]
+    fn snippet_matches_normalized_text() {
+        let msg = snippet_test_message("Курс pyбля вырос");
+        assert_eq!(msg.snippet("рубля", 2), "…с **pyбля** в…");
+    }
+
+    #[test]
+    fn snippet_match_near_start_has_no_leading_ellipsis() {
+        let msg = snippet_test_message("Rust 2.0 released today with many new features");
+        assert_eq!(msg.snippet("rust", 5), "**Rust** 2.0 …");
+    }
+
+    #[test]
+    fn snippet_match_in_middle_has_both_ellipses() {
+        let msg = snippet_test_message("Yesterday the team announced Rust 2.0 to everyone");
+        assert_eq!(msg.snippet("rust", 4), "…ced **Rust** 2.0…");
+    }
+
+    #[test]
+    fn snippet_match_near_end_has_no_trailing_ellipsis() {
+        let msg = snippet_test_message("Everything you need to know about Rust");
+        assert_eq!(msg.snippet("rust", 6), "…about **Rust**");
+    }
+
+    #[test]
+    fn snippet_respects_multibyte_boundaries() {
+        let msg = snippet_test_message("🙂🙂🙂 Привет мир 🙂🙂🙂");
+        assert_eq!(msg.snippet("мир", 2), "…т **мир** 🙂…");
+    }
+
+    #[test]
+    fn snippet_with_huge_radius_keeps_whole_text() {
+        let msg = snippet_test_message("Yesterday the team announced Rust 2.0 to everyone");
+        assert_eq!(
+            msg.snippet("rust", usize::MAX),
+            "Yesterday the team announced **Rust** 2.0 to everyone"
+        );
+        assert_eq!(
+            msg.snippet("golang", usize::MAX),
+            "Yesterday the team announced Rust 2.0 to everyone"
+        );
+    }
+
+    #[test]
+    fn snippet_leaves_message_unchanged() {
+        let msg = snippet_test_message("Yesterday the team announced Rust 2.0 to everyone");
+        let _ = msg.snippet("rust", 4);
+        assert_eq!(
+            msg.text,
+            "Yesterday the team announced Rust 2.0 to everyone"
+        );
+    }
+
+    #[test]
+    fn word_count_counts_unicode_words() {
+        assert_eq!(
+            snippet_test_message("Привет, мир! Hello world").word_count(),
+            4
+        );
+        assert_eq!(
+            snippet_test_message("  spaced\tout\nwords  ").word_count(),
+            3
+        );
+    }
+
+    #[test]
+    fn word_count_ignores_punctuation_and_emoji_tokens() {
+        assert_eq!(snippet_test_message("Новости — 🙂 сегодня").word_count(), 2);
+        assert_eq!(snippet_test_message("").word_count(), 0);
+    }
+
+    #[test]
+    fn detect_language_recognizes_russian() {
+        assert_eq!(detect_language("Сегодня вышла новая модель ИИ"), Some("ru"));
+        assert_eq!(
+            detect_language("Релиз Rust 2.0 уже доступен всем"),
+            Some("ru")
+        );
+    }
+
+    #[test]
+    fn detect_language_recognizes_english() {
+        assert_eq!(detect_language("New AI model released today"), Some("en"));
+        assert_eq!(detect_language("Café déjà vu, naïve résumé"), Some("en"));
+    }
+
+    #[test]
+    fn detect_language_gives_up_on_short_or_other_scripts() {
+        assert_eq!(detect_language("ok"), None);
+        assert_eq!(detect_language("12345 🙂 !!!"), None);
+        assert_eq!(detect_language("東京で新しいモデル"), None);
+    }
+
+    #[test]
+    fn message_detected_language_is_skipped_when_unset() {
+        let mut message = snippet_test_message("text");
+        assert!(
+            !serde_json::to_string(&message)
+                .unwrap()
+                .contains("detected_language")
+        );
+
+        message.detected_language = Some("ru".to_string());
+        assert!(
+            serde_json::to_string(&message)
+                .unwrap()
+                .contains("\"detected_language\":\"ru\"")
+        );
+    }
+
+    #[test]
+    fn message_forward_fields_round_trip() {
+        let mut message = snippet_test_message("forwarded announcement");
+        message.forward_from_channel = Some(ChannelId::new(777).unwrap());
+        message.forward_from_name = Some("Original Author".to_string());
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"forward_from_channel\":777"));
+        assert!(json.contains("\"forward_from_name\":\"Original Author\""));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.forward_from_channel,
+            Some(ChannelId::new(777).unwrap())
+        );
+        assert_eq!(parsed.forward_from_name.as_deref(), Some("Original Author"));
+    }
+
+    #[test]
+    fn message_without_forward_fields_round_trips_compactly() {
+        let message = snippet_test_message("original post");
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("forward_from"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert!(parsed.forward_from_channel.is_none());
+        assert!(parsed.forward_from_name.is_none());
+    }
+
+    #[test]
+    fn message_engagement_fields_round_trip() {
+        let mut message = snippet_test_message("big announcement");
+        message.views = Some(12500);
+        message.forwards = Some(42);
+        message.reactions = Some(vec![("👍".to_string(), 120), ("custom:5".to_string(), 3)]);
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"views\":12500"));
+        assert!(json.contains("\"forwards\":42"));
+        assert!(json.contains("\"reactions\":[[\"👍\",120],[\"custom:5\",3]]"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.views, Some(12500));
+        assert_eq!(parsed.forwards, Some(42));
+        assert_eq!(parsed.reactions, message.reactions);
+    }
+
+    #[test]
+    fn message_without_engagement_fields_omits_them() {
+        let message = snippet_test_message("quiet post");
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("views"));
+        assert!(!json.contains("forwards"));
+        assert!(!json.contains("reactions"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert!(parsed.views.is_none());
+        assert!(parsed.forwards.is_none());
+        assert!(parsed.reactions.is_none());
+    }
+
+    #[test]
+    fn message_reply_to_round_trips() {
+        let mut message = snippet_test_message("follow-up");
+        message.reply_to = Some(MessageId::new(41).unwrap());
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"reply_to\":41"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.reply_to, Some(MessageId::new(41).unwrap()));
+    }
+
+    #[test]
+    fn message_without_reply_to_omits_it() {
+        let message = snippet_test_message("top-level post");
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("reply_to"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert!(parsed.reply_to.is_none());
+    }
+
+    #[test]
+    fn message_was_edited_follows_edit_date() {
+        let mut message = snippet_test_message("announcement");
+        assert!(!message.was_edited());
+
+        message.edit_date = Some(message.timestamp + chrono::Duration::hours(1));
+        assert!(message.was_edited());
+    }
+
+    #[test]
+    fn message_edit_date_round_trips() {
+        let mut message = snippet_test_message("announcement v2");
+        let edited_at = DateTime::parse_from_rfc3339("2026-01-15T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        message.edit_date = Some(edited_at);
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains("\"edit_date\":\"2026-01-15T10:30:00Z\""));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.edit_date, Some(edited_at));
+        assert!(parsed.was_edited());
+    }
+
+    #[test]
+    fn message_without_edit_date_omits_it() {
+        let message = snippet_test_message("announcement");
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(!json.contains("edit_date"));
+
+        let parsed: Message = serde_json::from_str(&json).unwrap();
+        assert!(parsed.edit_date.is_none());
+    }
+
+    #[test]
+    fn media_type_as_str_round_trips_through_from_str() {
+        for media_type in [MediaType::None, MediaType::VideoNote, MediaType::Unknown] {
+            assert_eq!(
+                media_type.as_str().parse::<MediaType>().unwrap(),
+                media_type
+            );
+        }
+    }
+
+    #[test]
+    fn search_result_to_jsonl_writes_one_message_per_line() {
+        let mut multiline = hit(100, 2, 1);
+        multiline.text = "line one\nline two".to_string();
+        let result = search_result(vec![hit(100, 1, 0), multiline]);
+
+        let jsonl = result.to_jsonl();
+
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(jsonl.ends_with('\n'));
+        let parsed: Message = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(parsed.text, "line one\nline two");
+    }
+
+    #[test]
+    fn search_result_to_jsonl_is_empty_without_messages() {
+        assert_eq!(search_result(vec![]).to_jsonl(), "");
+    }
+
+    #[test]
+    fn search_result_to_csv_escapes_commas_quotes_and_newlines() {
+        let mut message = hit(100, 7, 0);
+        message.text = "Price: 1,000 \"USD\"\nsecond line".to_string();
+        message.media_type = MediaType::Photo;
+        let timestamp = message.timestamp.to_rfc3339();
+        let result = search_result(vec![message]);
+
+        let csv = result.to_csv(true);
+
+        assert_eq!(
+            csv,
+            format!(
+                "id,channel_id,channel_username,timestamp,media_type,text\n\
+                 7,100,testchan,{timestamp},photo,\"Price: 1,000 \"\"USD\"\"\nsecond line\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn search_result_to_csv_without_header() {
+        let result = search_result(vec![hit(100, 1, 0), hit(200, 2, 0)]);
+
+        let csv = result.to_csv(false);
+
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.starts_with("1,100,"));
+        assert!(csv.ends_with(",none,launch announcement\n"));
+    }
 }
@@ -0,0 +1,250 @@
+use crate::config::ConnectionConfig;
+use crate::error::Error;
+use crate::telegram::client::{TelegramClient, connect_with_retry};
+use crate::telegram::types::{Message, SearchParams, normalize_text};
+
+/// Source of new channel posts, e.g. the Telegram update stream
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+pub trait UpdateSource: Send + Sync {
+    /// Wait for the next new channel post
+    ///
+    /// Updates that are not channel posts are skipped. `Ok(None)` means the
+    /// stream has ended and needs a reconnect.
+    async fn next_post(&self) -> Result<Option<Message>, Error>;
+
+    /// Re-establish the update stream after it dropped
+    async fn reconnect(&self) -> Result<(), Error>;
+}
+
+/// Whether a new post matches a watched query
+///
+/// Applies the same filters as a search except the `hours_back` window,
+/// which live posts always fall into.
+pub fn matches_watch(params: &SearchParams, message: &Message) -> bool {
+    // Substring mode normally trusts Telegram's search, so check the terms here
+    let text = normalize_text(&message.text).to_lowercase();
+    let contains_term = params
+        .terms()
+        .iter()
+        .any(|term| text.contains(&normalize_text(term).to_lowercase()));
+
+    contains_term
+        && params
+            .channel_id
+            .is_none_or(|channel_id| message.channel_id == channel_id)
+        && params.accepts(message)
+}
+
+/// Call `callback` for every new post from `source` matching `params`
+///
+/// A dropped stream or network error reconnects following `policy`; flood
+/// waits are honored first. Runs until a non-retryable error occurs or
+/// reconnecting gives up.
+pub async fn watch_updates<S>(
+    source: &S,
+    params: SearchParams,
+    policy: &ConnectionConfig,
+    callback: impl Fn(Message),
+) -> Result<(), Error>
+where
+    S: UpdateSource + ?Sized,
+{
+    let params = params.sanitized()?;
+
+    loop {
+        match source.next_post().await {
+            Ok(Some(message)) => {
+                if matches_watch(&params, &message) {
+                    callback(message);
+                }
+            }
+            Ok(None) => {
+                tracing::warn!("Update stream ended, reconnecting");
+                connect_with_retry(policy, || source.reconnect()).await?;
+            }
+            Err(e) if e.is_retryable() => {
+                tracing::warn!("Update stream failed, reconnecting: {}", e);
+                if let Some(delay) = e.retry_after() {
+                    tokio::time::sleep(delay).await;
+                }
+                connect_with_retry(policy, || source.reconnect()).await?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+impl TelegramClient {
+    /// Call `callback` for every new channel post matching `params`
+    ///
+    /// Listens on the account's update stream instead of polling search.
+    pub async fn watch_query(
+        &self,
+        params: SearchParams,
+        callback: impl Fn(Message),
+    ) -> Result<(), Error> {
+        watch_updates(self, params, self.reconnect_policy(), callback).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram::types::{ChannelId, ChannelName, MediaType, MessageId, Username};
+    use chrono::Utc;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    fn post(channel_id: i64, id: i64, text: &str) -> Message {
+        Message {
+            id: MessageId::new(id).unwrap(),
+            channel_id: ChannelId::new(channel_id).unwrap(),
+            channel_name: ChannelName::new("Test Channel").unwrap(),
+            channel_username: Some(Username::new("testchannel").unwrap()),
+            text: text.to_string(),
+            timestamp: Utc::now(),
+            sender_id: None,
+            sender_name: None,
+            has_media: false,
+            media_type: MediaType::None,
+            original_length: None,
+            score: None,
+            highlighted_text: None,
+            detected_language: None,
+            forward_from_channel: None,
+            forward_from_name: None,
+            views: None,
+            forwards: None,
+            reactions: None,
+            reply_to: None,
+            edit_date: None,
+        }
+    }
+
+    fn no_backoff(max_attempts: u32) -> ConnectionConfig {
+        ConnectionConfig {
+            max_attempts,
+            initial_backoff_ms: 0,
+            max_backoff_ms: 0,
+        }
+    }
+
+    /// Source replaying `updates` in order, failing with `NotFound` once exhausted
+    fn scripted_source(updates: Vec<Result<Option<Message>, Error>>) -> MockUpdateSource {
+        let updates = Mutex::new(VecDeque::from(updates));
+        let mut source = MockUpdateSource::new();
+        source.expect_next_post().returning(move || {
+            updates
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(Error::NotFound("end of script".to_string())))
+        });
+        source
+    }
+
+    fn received_ids(received: &Mutex<Vec<Message>>) -> Vec<i64> {
+        received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| m.id.get())
+            .collect()
+    }
+
+    #[test]
+    fn matches_watch_filters_query_and_channel() {
+        let params = SearchParams {
+            channel_id: Some(ChannelId::new(1).unwrap()),
+            ..SearchParams::new("rust")
+        };
+
+        assert!(matches_watch(&params, &post(1, 1, "Rust 1.90 is out")));
+        assert!(!matches_watch(&params, &post(2, 2, "Rust 1.90 is out")));
+        assert!(!matches_watch(&params, &post(1, 3, "Go 1.25 is out")));
+    }
+
+    #[tokio::test]
+    async fn watch_updates_calls_back_for_matching_posts() {
+        let mut source = scripted_source(vec![
+            Ok(Some(post(1, 1, "Bitcoin breaks a record"))),
+            Ok(Some(post(1, 2, "Weather today"))),
+            Ok(Some(post(2, 3, "bitcoin ETF news"))),
+        ]);
+        source.expect_reconnect().times(0);
+        let received = Mutex::new(Vec::new());
+
+        let result = watch_updates(
+            &source,
+            SearchParams::new("bitcoin"),
+            &no_backoff(1),
+            |message| received.lock().unwrap().push(message),
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        assert_eq!(received_ids(&received), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn watch_updates_reconnects_when_stream_drops() {
+        let mut source = scripted_source(vec![
+            Ok(Some(post(1, 1, "rust news"))),
+            Ok(None),
+            Err(Error::Network("connection reset".to_string())),
+            Ok(Some(post(1, 2, "more rust news"))),
+        ]);
+        source.expect_reconnect().times(2).returning(|| Ok(()));
+        let received = Mutex::new(Vec::new());
+
+        let result = watch_updates(
+            &source,
+            SearchParams::new("rust"),
+            &no_backoff(1),
+            |message| received.lock().unwrap().push(message),
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+        assert_eq!(received_ids(&received), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn watch_updates_retries_reconnect_then_gives_up() {
+        let mut source = scripted_source(vec![Ok(None)]);
+        source
+            .expect_reconnect()
+            .times(3)
+            .returning(|| Err(Error::Network("still offline".to_string())));
+
+        let result =
+            watch_updates(&source, SearchParams::new("rust"), &no_backoff(3), |_| {}).await;
+
+        assert!(matches!(result, Err(Error::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn watch_updates_stops_on_auth_error_during_reconnect() {
+        let mut source = scripted_source(vec![Ok(None)]);
+        source
+            .expect_reconnect()
+            .times(1)
+            .returning(|| Err(Error::Auth("session revoked".to_string())));
+
+        let result =
+            watch_updates(&source, SearchParams::new("rust"), &no_backoff(3), |_| {}).await;
+
+        assert!(matches!(result, Err(Error::Auth(_))));
+    }
+
+    #[tokio::test]
+    async fn watch_updates_rejects_empty_query() {
+        let mut source = MockUpdateSource::new();
+        source.expect_next_post().times(0);
+
+        let result = watch_updates(&source, SearchParams::new("  "), &no_backoff(1), |_| {}).await;
+
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}